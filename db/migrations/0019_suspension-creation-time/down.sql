@@ -0,0 +1 @@
+alter table suspension alter column created_at set not null;
@@ -0,0 +1,2 @@
+-- a suspension that arrived as-is has no creation time of its own, so `has_creation_time` requires this to be nullable
+alter table suspension alter column created_at drop not null;
@@ -0,0 +1,4 @@
+drop index suspension_pool_tag_idx;
+
+delete from multiplexing_tag
+where type in ('total_seq_a', 'total_seq_b', 'total_seq_c', 'cell_plex', 'flex_barcode');
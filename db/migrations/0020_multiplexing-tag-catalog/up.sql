@@ -0,0 +1,26 @@
+-- the multiplexing tags available for pooling suspensions. `type` matches the snake_case variants of
+-- `MultiplexingTagType`
+
+-- TotalSeq-A/B/C human (0251-0260) and mouse (0301-0310) hashtags
+insert into multiplexing_tag (tag_id, type)
+select 'TotalSeq-' || upper(chemistry) || lpad(hashtag::text, 4, '0'), 'total_seq_' || chemistry
+from
+    unnest(array['a', 'b', 'c']) as chemistry
+    cross join (
+        select generate_series(251, 260) as hashtag
+        union all
+        select generate_series(301, 310)
+    ) as hashtags;
+
+-- 3' CellPlex cell multiplexing oligos
+insert into multiplexing_tag (tag_id, type)
+select 'CMO' || cmo, 'cell_plex'
+from generate_series(301, 312) as cmo;
+
+-- Flex probe barcodes
+insert into multiplexing_tag (tag_id, type)
+select 'BC' || lpad(barcode::text, 3, '0'), 'flex_barcode'
+from generate_series(1, 16) as barcode;
+
+-- two suspensions in the same pool can't share a tag
+create unique index suspension_pool_tag_idx on suspension (pooled_into_id, multiplexing_tag_id);
@@ -0,0 +1,5 @@
+drop index chip_loading_multiplexed_suspension_idx;
+drop index chip_loading_suspension_idx;
+
+alter table chip_loading drop column id;
+alter table chip_loading add primary key (gems_id, suspension_id, multiplexed_suspension_id);
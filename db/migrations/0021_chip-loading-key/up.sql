@@ -0,0 +1,11 @@
+-- a primary key implicitly makes its columns non-null, so no row could ever satisfy `has_suspension`. Give chip loadings
+-- their own key instead, and keep a suspension from being loaded into the same GEMs twice with unique indexes (which
+-- treat nulls as distinct)
+alter table chip_loading drop constraint chip_loading_pkey;
+alter table chip_loading
+alter column suspension_id drop not null,
+alter column multiplexed_suspension_id drop not null;
+alter table chip_loading add column id uuid primary key default uuidv7();
+
+create unique index chip_loading_suspension_idx on chip_loading (gems_id, suspension_id);
+create unique index chip_loading_multiplexed_suspension_idx on chip_loading (gems_id, multiplexed_suspension_id);
@@ -0,0 +1,4 @@
+alter table chromium_dataset
+drop column sequencing_saturation,
+drop column median_genes_per_cell,
+drop column estimated_number_of_cells;
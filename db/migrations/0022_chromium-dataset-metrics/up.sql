@@ -0,0 +1,13 @@
+-- the metrics of the first (and for `cellranger count`, only) metrics file are copied into typed columns so that datasets
+-- can be filtered and ordered by them. Datasets uploaded before this migration (or by older pipelines) aren't guaranteed
+-- to have every key, so the columns are nullable and such a dataset simply never matches a filter on the missing metric
+alter table chromium_dataset
+add column estimated_number_of_cells integer generated always as (
+    (metrics[1] ->> 'estimated_number_of_cells')::integer
+) stored,
+add column median_genes_per_cell integer generated always as (
+    (metrics[1] ->> 'median_genes_per_cell')::integer
+) stored,
+add column sequencing_saturation real generated always as (
+    (metrics[1] ->> 'sequencing_saturation')::real
+) stored;
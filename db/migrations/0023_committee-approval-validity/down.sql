@@ -0,0 +1,3 @@
+alter table committee_approval
+drop column expires_at,
+drop column approved_at;
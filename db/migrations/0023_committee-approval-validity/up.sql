@@ -0,0 +1,6 @@
+-- approvals are only granted for a limited time. Approvals recorded before this migration are assumed to have been
+-- granted when they were recorded, and never to expire, since we have no way of knowing better
+alter table committee_approval
+add column approved_at timestamptz not null default now(),
+add column expires_at timestamptz,
+add constraint committee_approval_expires_after_approval check (expires_at > approved_at);
@@ -0,0 +1,3 @@
+alter table institution
+drop column country,
+drop column ror_id;
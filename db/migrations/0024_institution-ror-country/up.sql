@@ -0,0 +1,4 @@
+-- `country` is an ISO 3166-1 alpha-2 code, matching what ROR records for an organization
+alter table institution
+add column ror_id text unique,
+add column country text;
@@ -0,0 +1,2 @@
+drop table sample_status_transition;
+alter table sample_metadata drop column status;
@@ -0,0 +1,15 @@
+-- constrained by Rust enum, which also defines the allowed transitions
+alter table sample_metadata add column status text not null default 'received';
+update sample_metadata set status = 'returned' where returned_at is not null;
+
+create table sample_status_transition (
+    id uuid primary key default uuidv7(),
+    sample_id uuid references sample_metadata on delete restrict on update restrict not null,
+    from_status text not null,
+    to_status text not null,
+    transitioned_at timestamptz not null default now(),
+    transitioned_by uuid references person on delete restrict on update restrict not null
+);
+
+grant select on sample_status_transition to public;
+grant all on sample_status_transition to app_admin;
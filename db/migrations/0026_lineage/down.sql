@@ -0,0 +1,2 @@
+drop view lineage_node;
+drop view lineage_edge;
@@ -0,0 +1,52 @@
+-- Every step from a sample to the things made from it, one row per parent and child. Node types are constrained by the
+-- Rust enum `LineageNodeType`.
+create view lineage_edge as
+    select 'sample' as parent_type, metadata_id as parent_id, 'specimen' as child_type, id as child_id from specimen
+    union all
+    -- a suspension is made either directly from a sample or from one of its specimens
+    select
+        case when parent_specimen_id is null then 'sample' else 'specimen' end,
+        coalesce(parent_specimen_id, metadata_id),
+        'suspension',
+        id
+    from suspension
+    union all
+    select 'suspension', id, 'multiplexed_suspension', pooled_into_id from suspension where pooled_into_id is not null
+    union all
+    select
+        case when suspension_id is null then 'multiplexed_suspension' else 'suspension' end,
+        coalesce(suspension_id, multiplexed_suspension_id),
+        'gems',
+        gems_id
+    from chip_loading
+    union all
+    select 'gems', gems_id, 'cdna', id from cdna
+    union all
+    select 'cdna', cdna_id, 'library', id from chromium_library
+    union all
+    select 'library', library_id, 'sequencing_run', sequencing_run_id from chromium_sequencing_submissions
+    union all
+    select 'gems', gems_id, 'dataset', id from chromium_dataset;
+
+-- A human-readable label for every node that can appear in `lineage_edge`
+create view lineage_node as
+    select 'sample' as type, id, name as label from sample_metadata
+    union all
+    select 'specimen', id, readable_id from specimen
+    union all
+    select 'suspension', id, readable_id from suspension
+    union all
+    select 'multiplexed_suspension', id, readable_id from multiplexed_suspension
+    union all
+    select 'gems', id, readable_id from gems
+    union all
+    select 'cdna', id, readable_id from cdna
+    union all
+    select 'library', id, readable_id from chromium_library
+    union all
+    select 'sequencing_run', id, readable_id from sequencing_run
+    union all
+    select 'dataset', id, name from dataset_metadata inner join chromium_dataset using (id);
+
+grant select on lineage_edge to public;
+grant select on lineage_node to public;
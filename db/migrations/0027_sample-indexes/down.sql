@@ -0,0 +1,6 @@
+drop index sample_lab_idx,
+sample_submitter_idx,
+sample_received_at_idx,
+sample_returned_at_idx,
+tissue_idx,
+species_idx;
@@ -0,0 +1,8 @@
+-- Enables the sample indexes that were left commented out in `0018_indexes`, now that samples can be searched by these
+-- columns. Species are matched by array overlap and containment, which a btree index can't serve.
+create index sample_lab_idx on sample_metadata (lab_id);
+create index sample_submitter_idx on sample_metadata (submitted_by);
+create index sample_received_at_idx on sample_metadata (received_at);
+create index sample_returned_at_idx on sample_metadata (returned_at);
+create index tissue_idx on sample_metadata (tissue);
+create index species_idx on sample_metadata using gin (species);
@@ -0,0 +1,6 @@
+drop index person_name_trgm_idx,
+lab_name_trgm_idx,
+institution_name_trgm_idx,
+sample_name_trgm_idx;
+
+drop extension if exists pg_trgm;
@@ -0,0 +1,7 @@
+-- Names are searched by trigram similarity, which a btree index can't serve
+create extension if not exists pg_trgm;
+
+create index person_name_trgm_idx on person using gin (name gin_trgm_ops);
+create index lab_name_trgm_idx on lab using gin (name gin_trgm_ops);
+create index institution_name_trgm_idx on institution using gin (name gin_trgm_ops);
+create index sample_name_trgm_idx on sample_metadata using gin (name gin_trgm_ops);
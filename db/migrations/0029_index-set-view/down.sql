@@ -0,0 +1 @@
+drop view index_set;
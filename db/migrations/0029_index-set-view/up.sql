@@ -0,0 +1,24 @@
+-- Both kinds of index set in one relation, so that they can be searched, ordered and paginated together. `sequences`
+-- holds every oligo in the set, and the dual-index columns are null for a single-index set.
+create view index_set as
+    select
+        name,
+        kit,
+        well,
+        sequences,
+        null::text as index_i7,
+        null::text as index2_workflow_a_i5,
+        null::text as index2_workflow_b_i5
+    from single_index_set
+    union all
+    select
+        name,
+        kit,
+        well,
+        array[index_i7, index2_workflow_a_i5, index2_workflow_b_i5],
+        index_i7,
+        index2_workflow_a_i5,
+        index2_workflow_b_i5
+    from dual_index_set;
+
+grant select on index_set to public;
@@ -21,6 +21,14 @@ pub enum Error {
     },
     #[error("record not found")]
     RecordNotFound,
+    #[error("{entity} violates {constraint}: {message}")]
+    ConstraintViolation {
+        entity: String,
+        constraint: String,
+        message: String,
+    },
+    #[error("{message}")]
+    PermissionDenied { message: String },
     #[error("{message}")]
     Other { message: String },
 }
@@ -85,7 +93,9 @@ impl
             Box<dyn DatabaseErrorInformation + Send + Sync>,
         ),
     ) -> Self {
-        use diesel::result::DatabaseErrorKind::{ForeignKeyViolation, UniqueViolation};
+        use diesel::result::DatabaseErrorKind::{
+            CheckViolation, ForeignKeyViolation, UniqueViolation,
+        };
         let entity = info.table_name().unwrap_or_default();
 
         let detail_regex = Regex::new(r"Key \((.+)\)=\((.+)\).+").unwrap(); // This isn't perfect
@@ -131,9 +141,50 @@ impl
                     value: values.first().cloned(),
                 }
             }
+            CheckViolation => {
+                let constraint = info.constraint_name().unwrap_or_default();
+                let message = describe_check_constraint(constraint).unwrap_or(info.message());
+
+                Self::ConstraintViolation {
+                    entity: entity.to_string(),
+                    constraint: constraint.to_string(),
+                    message: message.to_string(),
+                }
+            }
+            // diesel doesn't distinguish insufficient privileges, so we recognize Postgres's message instead
+            _ if info.message().starts_with("permission denied") => Self::PermissionDenied {
+                message: info.message().to_string(),
+            },
             _ => Self::from_other_error(diesel::result::Error::DatabaseError(kind, info)),
         }
     }
 }
 
+// Postgres only tells us the name of the violated constraint, so we keep a human-readable explanation for each check
+// constraint in the schema
+fn describe_check_constraint(constraint: &str) -> Option<&'static str> {
+    let description = match constraint {
+        "has_metadata" => {
+            "a suspension must either have its own metadata or be derived from a specimen, but not both"
+        }
+        "has_creation_time" => {
+            "a suspension must have a creation time if and only if it is derived from a specimen"
+        }
+        "pooling_is_correctly_specified" => {
+            "a pooled suspension must specify both the multiplexed suspension it was pooled into and its multiplexing \
+             tag"
+        }
+        "has_suspension" => {
+            "a Chromium run must load either a suspension or a multiplexed suspension, but not both"
+        }
+        "has_index" => "a library must have either a single or a dual index set, but not both",
+        "committee_approval_expires_after_approval" => {
+            "a committee approval must expire after it was approved"
+        }
+        _ => return None,
+    };
+
+    Some(description)
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,18 +1,28 @@
 use super::error;
 use diesel_async::AsyncPgConnection;
+use scamplers_core::model::Page;
 
 use crate::db::util::{BoxedDieselExpression, NewBoxedDieselExpression};
 
+pub mod cdna;
+pub mod chemistry;
 pub mod chromium;
 pub mod dataset_metadata;
+pub mod filter;
+pub mod index_sets;
 pub mod institution;
 pub mod lab;
+pub mod library;
+pub mod library_type_specification;
+pub mod lineage;
 pub mod measurements;
+pub mod multiplexed_suspension;
 pub mod person;
 pub mod sample_metadata;
+pub mod search;
 pub mod sequencing_run;
 pub mod specimen;
-pub mod units;
+pub mod suspension;
 
 trait AsDieselFilter<QuerySource = ()> {
     fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
@@ -68,13 +78,20 @@ pub trait FetchById: Sized {
     ) -> impl Future<Output = error::Result<Self>> + Send;
 }
 
+pub trait FetchByReadableId: Sized {
+    fn fetch_by_readable_id(
+        readable_id: &str,
+        db_conn: &mut AsyncPgConnection,
+    ) -> impl Future<Output = error::Result<Self>> + Send;
+}
+
 pub trait FetchByQuery: Sized {
     type QueryParams;
 
     fn fetch_by_query(
         query: &Self::QueryParams,
         db_conn: &mut AsyncPgConnection,
-    ) -> impl Future<Output = error::Result<Vec<Self>>> + Send;
+    ) -> impl Future<Output = error::Result<Page<Self>>> + Send;
 }
 
 pub trait FetchRelatives<R>: diesel::Table {
@@ -86,6 +103,21 @@ pub trait FetchRelatives<R>: diesel::Table {
     ) -> impl Future<Output = error::Result<Vec<R>>> + Send;
 }
 
+pub trait WriteRelatives<R>: diesel::Table {
+    type Id;
+    type Returns;
+
+    fn write_relatives(
+        id: &Self::Id,
+        relatives: Vec<R>,
+        db_conn: &mut AsyncPgConnection,
+    ) -> impl Future<Output = error::Result<Vec<Self::Returns>>> + Send;
+}
+
+/// Fetches one page of records matching a query, ordered by the columns the query asks for. Passing the column that
+/// identifies a record enables cursor pagination, with that column breaking ties in the ordering. Passing
+/// `ranked_by_similarity(field, column)` ranks the records by the similarity of `column` to the query's `field`, if
+/// it's set, before any other ordering.
 #[macro_export]
 macro_rules! fetch_by_query {
     ($query:ident, [$(($ordinal_col_enum_variant:ident, $corresponding_db_col:ident)),*], $db_conn:ident) => {{
@@ -93,7 +125,7 @@ macro_rules! fetch_by_query {
 
         let Self::QueryParams {
             order_by,
-            pagination: Pagination { limit, offset },
+            pagination: Pagination { limit, offset, .. },
             ..
         } = $query;
 
@@ -118,6 +150,152 @@ macro_rules! fetch_by_query {
             };
         }
 
-        Ok(statement.load($db_conn).await?)
+        let total_count = $crate::fetch_by_query!(@total_count $query, $db_conn);
+
+        Ok(scamplers_core::model::Page {
+            results: statement.load($db_conn).await?,
+            next_cursor: None,
+            total_count,
+        })
+    }};
+    ($query:ident, [$(($ordinal_col_enum_variant:ident, $corresponding_db_col:ident)),*], $id_col:ident, $db_conn:ident $(, ranked_by_similarity($similarity_field:ident, $similarity_col:ident))?) => {{
+        use super::AsDieselFilter;
+        use $crate::db::util::{BoxedDieselExpression, NewBoxedDieselExpression};
+
+        let Self::QueryParams {
+            order_by,
+            pagination: Pagination { limit, offset, cursor, .. },
+            ..
+        } = $query;
+
+        // A record's rank depends on the search text rather than on its columns, so ranked results are paginated by
+        // offset. Validation rejects a cursor alongside a similarity search, and no next cursor is handed out for one.
+        let cursor = cursor.as_ref();
+        $(
+            if let Some(scamplers_core::model::SimilaritySearch { threshold, .. }) = &$query.$similarity_field {
+                $crate::db::util::set_similarity_threshold(*threshold, $db_conn).await?;
+            }
+        )?
+
+        let query = $query.as_diesel_filter();
+
+        // Fetching one more record than was asked for tells us whether there's another page
+        let mut statement = Self::as_diesel_query_base()
+            .select((Self::as_select(), $id_col))
+            .limit(limit.saturating_add(1))
+            .into_boxed();
+
+        if let Some(query) = query {
+            statement = statement.filter(query);
+        }
+
+        // A record comes after the cursor if it's ordered after the cursor's record by the first column on which they
+        // differ, or if they don't differ and its id is greater. Building that condition from the last column backwards
+        // nests each column's condition inside the previous one's. Postgres puts nulls last in ascending order and first
+        // in descending order, so a null compares as greater than any value.
+        if let Some(cursor) = cursor {
+            let mut after_cursor: BoxedDieselExpression<_> = Box::new($id_col.gt(cursor));
+
+            for ordering in order_by.iter().rev() {
+                let (ordered_after, tied): (BoxedDieselExpression<_>, BoxedDieselExpression<_>) =
+                    match (ordering.column, ordering.descending) {
+                        $(
+                            ($ordinal_col_enum_variant, descending) => {
+                                let column = $corresponding_db_col.nullable();
+                                let cursor_value = || {
+                                    Self::as_diesel_query_base()
+                                        .filter($id_col.eq(cursor))
+                                        .select(column)
+                                        .into_boxed()
+                                        .single_value()
+                                };
+
+                                let ordered_after: BoxedDieselExpression<_> = if descending {
+                                    Box::new(
+                                        column
+                                            .lt(cursor_value())
+                                            .is_not_distinct_from(true)
+                                            .or(column.is_not_null().and(cursor_value().is_null())),
+                                    )
+                                } else {
+                                    Box::new(
+                                        column
+                                            .gt(cursor_value())
+                                            .is_not_distinct_from(true)
+                                            .or(column.is_null().and(cursor_value().is_not_null())),
+                                    )
+                                };
+
+                                (
+                                    ordered_after,
+                                    Box::new(column.is_not_distinct_from(cursor_value())),
+                                )
+                            }
+                        )*
+                    };
+
+                let tied_and_after = BoxedDieselExpression::new_expression()
+                    .and_condition(tied)
+                    .and_condition(after_cursor)
+                    .build()
+                    .unwrap();
+
+                after_cursor = BoxedDieselExpression::new_expression()
+                    .and_condition(ordered_after)
+                    .or_condition(tied_and_after)
+                    .build()
+                    .unwrap();
+            }
+
+            statement = statement.filter(after_cursor);
+        } else {
+            statement = statement.offset(*offset);
+        }
+
+        $(
+            if let Some(scamplers_core::model::SimilaritySearch { text, .. }) = &$query.$similarity_field {
+                statement = statement.then_order_by($crate::db::util::similarity($similarity_col, text).desc());
+            }
+        )?
+
+        for ordering in order_by {
+            statement = match (ordering.column, ordering.descending) {
+                $(
+                    ($ordinal_col_enum_variant, false) => statement.then_order_by($corresponding_db_col.asc()),
+                    ($ordinal_col_enum_variant, true) => statement.then_order_by($corresponding_db_col.desc()),
+                )*
+            };
+        }
+        statement = statement.then_order_by($id_col.asc());
+
+        let mut records: Vec<(Self, ::uuid::Uuid)> = statement.load($db_conn).await?;
+
+        let next_cursor = if usize::try_from(*limit).is_ok_and(|limit| records.len() > limit) {
+            records.pop();
+            records.last().map(|(_, id)| *id)$(.filter(|_| $query.$similarity_field.is_none()))?
+        } else {
+            None
+        };
+
+        let total_count = $crate::fetch_by_query!(@total_count $query, $db_conn);
+
+        Ok(scamplers_core::model::Page {
+            results: records.into_iter().map(|(record, _)| record).collect(),
+            next_cursor,
+            total_count,
+        })
     }};
+    (@total_count $query:ident, $db_conn:ident) => {
+        if $query.pagination.count_total {
+            let mut statement = Self::as_diesel_query_base().count().into_boxed();
+
+            if let Some(query) = $query.as_diesel_filter() {
+                statement = statement.filter(query);
+            }
+
+            Some(statement.get_result::<i64>($db_conn).await?)
+        } else {
+            None
+        }
+    };
 }
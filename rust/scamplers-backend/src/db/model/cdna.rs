@@ -0,0 +1,403 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    cdna::{Cdna, CdnaQuery, CdnaSummary, NewCdna},
+    measurements::CdnaMeasurement,
+    person::PersonSummary,
+};
+use scamplers_schema::{
+    cdna::{
+        self, gems_id as gems_id_col, id as id_col, library_type as library_type_col,
+        prepared_at as prepared_at_col, readable_id as readable_id_col,
+        storage_location as storage_location_col,
+    },
+    cdna_preparers::{self, cdna_id as cdna_id_col, prepared_by as prepared_by_col},
+    chemistry, gems,
+};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{Error, Result},
+        model::{self, AsDieselQueryBase, FetchById, FetchRelatives, WriteRelatives},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewCdna {
+    type Returns = Cdna;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        check_library_type(&self.gems_id, &self.library_type, db_conn).await?;
+
+        let id = diesel::insert_into(cdna::table)
+            .values(&self)
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        let preparers: Vec<_> = self
+            .preparer_ids
+            .iter()
+            .map(|p| (cdna_id_col.eq(id), prepared_by_col.eq(p)))
+            .collect();
+
+        diesel::insert_into(cdna_preparers::table)
+            .values(preparers)
+            .execute(db_conn)
+            .await?;
+
+        cdna::table::write_relatives(&id, self.measurements, db_conn).await?;
+
+        Cdna::fetch_by_id(&id, db_conn).await
+    }
+}
+
+// The library types a chemistry supports are stored as an array on the `chemistry` table, so this can't be a foreign key
+async fn check_library_type(
+    gems_id: &Uuid,
+    library_type: &str,
+    db_conn: &mut AsyncPgConnection,
+) -> Result<()> {
+    let gems_chemistry: Option<(Option<String>, Option<Vec<String>>)> = gems::table
+        .left_join(chemistry::table)
+        .filter(gems::id.eq(gems_id))
+        .select((gems::chemistry, chemistry::library_types.nullable()))
+        .first(db_conn)
+        .await
+        .optional()?;
+
+    let Some((chemistry, library_types)) = gems_chemistry else {
+        return Err(Error::ReferenceNotFound {
+            entity: "cdna".to_string(),
+            referenced_entity: "gems".to_string(),
+            value: Some(gems_id.to_string()),
+        });
+    };
+
+    let violation = |message| Error::ConstraintViolation {
+        entity: "cdna".to_string(),
+        constraint: "library_type_matches_chemistry".to_string(),
+        message,
+    };
+
+    let (Some(chemistry), Some(library_types)) = (chemistry, library_types) else {
+        return Err(violation(format!(
+            "GEMs {gems_id} has no chemistry, so library type {library_type} cannot be validated"
+        )));
+    };
+
+    if !library_types.iter().any(|l| l == library_type) {
+        return Err(violation(format!(
+            "library type {library_type} is not one of the library types of chemistry {chemistry}: \
+             {library_types:?}"
+        )));
+    }
+
+    Ok(())
+}
+
+// Filtering by preparer requires a subquery on `cdna_preparers`, which diesel can only check against a concrete query
+// source
+impl model::AsDieselFilter<cdna::table> for CdnaQuery {
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, cdna::table>>
+    where
+        cdna::table: 'a,
+    {
+        let Self {
+            ids,
+            readable_id,
+            library_type,
+            gems_ids,
+            preparer_ids,
+            storage_location,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(readable_id) = readable_id {
+            query = query.and_condition(readable_id_col.ilike(readable_id.as_ilike()));
+        }
+
+        if let Some(library_type) = library_type {
+            query = query.and_condition(library_type_col.eq(library_type));
+        }
+
+        if !gems_ids.is_empty() {
+            query = query.and_condition(gems_id_col.eq_any(gems_ids));
+        }
+
+        if !preparer_ids.is_empty() {
+            let prepared_by_any = cdna_preparers::table
+                .filter(prepared_by_col.eq_any(preparer_ids))
+                .select(cdna_id_col);
+
+            query = query.and_condition(id_col.eq_any(prepared_by_any));
+        }
+
+        // A cDNA without a storage location compares as NULL, which excludes it just as a non-match would
+        if let Some(storage_location) = storage_location {
+            query = query.and_condition(
+                storage_location_col
+                    .assume_not_null()
+                    .ilike(storage_location.as_ilike()),
+            );
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for CdnaSummary {
+    type QueryBase = cdna::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        cdna::table
+    }
+}
+
+impl model::FetchById for CdnaSummary {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        Ok(Self::as_diesel_query_base()
+            .find(id)
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+impl model::FetchByQuery for CdnaSummary {
+    type QueryParams = CdnaQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::cdna::CdnaOrdinalColumn::{PreparedAt, ReadableId};
+
+        fetch_by_query!(
+            query,
+            [(ReadableId, readable_id_col), (PreparedAt, prepared_at_col)],
+            id_col,
+            db_conn
+        )
+    }
+}
+
+impl model::FetchRelatives<CdnaSummary> for gems::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        gems_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<CdnaSummary>> {
+        let cdna = CdnaSummary::as_diesel_query_base()
+            .filter(gems_id_col.eq(gems_id))
+            .select(CdnaSummary::as_select())
+            .order_by(readable_id_col)
+            .load(db_conn)
+            .await?;
+
+        Ok(cdna)
+    }
+}
+
+impl model::FetchRelatives<PersonSummary> for cdna::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        cdna_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<PersonSummary>> {
+        let preparers = cdna_preparers::table
+            .filter(cdna_id_col.eq(cdna_id))
+            .inner_join(PersonSummary::as_diesel_query_base())
+            .select(PersonSummary::as_select())
+            .load(db_conn)
+            .await?;
+
+        Ok(preparers)
+    }
+}
+
+impl model::FetchById for Cdna {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let summary = CdnaSummary::fetch_by_id(id, db_conn).await?;
+        let preparers =
+            <cdna::table as FetchRelatives<PersonSummary>>::fetch_relatives(id, db_conn).await?;
+        let measurements =
+            <cdna::table as FetchRelatives<CdnaMeasurement>>::fetch_relatives(id, db_conn).await?;
+
+        Ok(Self::new(summary, preparers, measurements))
+    }
+}
+
+impl model::FetchByReadableId for Cdna {
+    async fn fetch_by_readable_id(
+        readable_id: &str,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Self> {
+        let id = cdna::table
+            .select(id_col)
+            .filter(readable_id_col.eq(readable_id))
+            .get_result(db_conn)
+            .await?;
+
+        Self::fetch_by_id(&id, db_conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::prelude::*;
+    use diesel_async::{
+        AsyncConnection, AsyncPgConnection, RunQueryDsl, scoped_futures::ScopedFutureExt,
+    };
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        cdna::{CdnaQuery, CdnaSummary, NewCdna},
+        person::{PersonQuery, PersonSummary},
+    };
+    use scamplers_schema::gems;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, Write},
+        test_util::{DbConnection, N_CDNA, db_conn, test_query},
+    };
+
+    fn comparison_fn(c: &CdnaSummary) -> String {
+        c.readable_id().clone()
+    }
+
+    async fn gems_id(readable_id: &str, db_conn: &mut AsyncPgConnection) -> Uuid {
+        gems::table
+            .filter(gems::readable_id.eq(readable_id))
+            .select(gems::id)
+            .first(db_conn)
+            .await
+            .unwrap()
+    }
+
+    async fn first_person_id(db_conn: &mut AsyncPgConnection) -> Uuid {
+        *PersonSummary::fetch_by_query(&PersonQuery::default(), db_conn)
+            .await
+            .unwrap()
+            .results
+            .remove(0)
+            .id()
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn default_cdna_query(#[future] db_conn: DbConnection) {
+        let expected = [(0, "cdna0"), (N_CDNA - 1, &format!("cdna{}", N_CDNA - 1))];
+        test_query(
+            CdnaQuery::default(),
+            db_conn,
+            N_CDNA,
+            comparison_fn,
+            &expected,
+        )
+        .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn cdna_by_gems(#[future] mut db_conn: DbConnection) {
+        let query = CdnaQuery {
+            gems_ids: vec![gems_id("gems1", &mut db_conn).await],
+            ..Default::default()
+        };
+
+        let expected = [(0, "cdna1"), (1, "cdna3")];
+        test_query(query, db_conn, N_CDNA / 2, comparison_fn, &expected).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn cdna_by_preparer(#[future] mut db_conn: DbConnection) {
+        let query = CdnaQuery {
+            preparer_ids: vec![first_person_id(&mut db_conn).await],
+            ..Default::default()
+        };
+
+        let expected = [(0, "cdna0"), (1, "cdna2")];
+        test_query(query, db_conn, N_CDNA / 2, comparison_fn, &expected).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn cdna_by_storage_location(#[future] db_conn: DbConnection) {
+        let query = CdnaQuery {
+            storage_location: Some("freezer 1".to_string()),
+            ..Default::default()
+        };
+
+        let expected = [(0, "cdna1"), (1, "cdna3")];
+        test_query(query, db_conn, N_CDNA / 2, comparison_fn, &expected).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn library_type_must_match_chemistry(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let new_cdna = |library_type: &str, gems_id, preparer_id| NewCdna {
+                        library_type: library_type.to_string(),
+                        readable_id: "CDNA-new".to_string(),
+                        prepared_at: OffsetDateTime::now_utc(),
+                        gems_id,
+                        n_amplification_cycles: 11,
+                        storage_location: None,
+                        notes: None,
+                        preparer_ids: vec![preparer_id],
+                        measurements: vec![],
+                    };
+
+                    let gems_id = gems_id("gems0", tx).await;
+                    let person_id = first_person_id(tx).await;
+
+                    let err = new_cdna("Chromatin Accessibility", gems_id, person_id)
+                        .write(tx)
+                        .await
+                        .unwrap_err();
+
+                    let Error::ConstraintViolation { constraint, .. } = err else {
+                        panic!("expected constraint violation, found {err:?}");
+                    };
+                    assert_eq!(constraint, "library_type_matches_chemistry");
+
+                    let cdna = new_cdna("Gene Expression", gems_id, person_id)
+                        .write(tx)
+                        .await
+                        .unwrap();
+                    assert_eq!(cdna.preparers().len(), 1);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
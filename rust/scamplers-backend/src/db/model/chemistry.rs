@@ -0,0 +1,148 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    chemistry::{Chemistry, ChemistryQuery},
+};
+use scamplers_schema::chemistry::{
+    self, description as description_col, library_types as library_types_col, name as name_col,
+};
+
+use crate::{
+    db::{
+        error::Result,
+        model::{self, AsDieselQueryBase},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for ChemistryQuery
+where
+    name_col: SelectableExpression<QuerySource>,
+    description_col: SelectableExpression<QuerySource>,
+    library_types_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            names,
+            description,
+            library_type,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !names.is_empty() {
+            query = query.and_condition(name_col.eq_any(names));
+        }
+
+        if let Some(description) = description {
+            query = query.and_condition(description_col.ilike(description.as_ilike()));
+        }
+
+        if let Some(library_type) = library_type {
+            query =
+                query.and_condition(library_types_col.contains(std::slice::from_ref(library_type)));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for Chemistry {
+    type QueryBase = chemistry::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        chemistry::table
+    }
+}
+
+impl model::FetchByQuery for Chemistry {
+    type QueryParams = ChemistryQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::chemistry::ChemistryOrdinalColumn::Name;
+
+        fetch_by_query!(query, [(Name, name_col)], db_conn)
+    }
+}
+
+impl model::FetchById for Chemistry {
+    type Id = String;
+
+    async fn fetch_by_id(name: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        Ok(Self::as_diesel_query_base()
+            .find(name)
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::chemistry::{Chemistry, ChemistryQuery};
+
+    use crate::db::{
+        model::FetchById,
+        test_util::{DbConnection, TEST_CHEMISTRY, db_conn, test_query},
+    };
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn fetch_chemistry_by_name(#[future] mut db_conn: DbConnection) {
+        let chemistry = Chemistry::fetch_by_id(&TEST_CHEMISTRY.to_string(), &mut db_conn)
+            .await
+            .unwrap();
+
+        assert_eq!(chemistry.library_types(), &["Gene Expression"]);
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn filter_chemistries_by_library_type(#[future] db_conn: DbConnection) {
+        let query = ChemistryQuery {
+            library_type: Some("Gene Expression".to_string()),
+            ..Default::default()
+        };
+
+        test_query(
+            query,
+            db_conn,
+            1,
+            |c: &Chemistry| c.name().clone(),
+            &[(0, TEST_CHEMISTRY)],
+        )
+        .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn exclude_chemistries_without_library_type(#[future] db_conn: DbConnection) {
+        let query = ChemistryQuery {
+            library_type: Some("Antibody Capture".to_string()),
+            ..Default::default()
+        };
+
+        test_query(
+            query,
+            db_conn,
+            0,
+            |c: &Chemistry| c.name().clone(),
+            &[] as &[(usize, &str)],
+        )
+        .await;
+    }
+}
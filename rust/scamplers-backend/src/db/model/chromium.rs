@@ -1 +1,414 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    chromium::{
+        ChipLoading, ChromiumRun, ChromiumRunQuery, ChromiumRunSummary, Gems, GemsSummary,
+        NewChipLoading, NewChromiumRun, NewGems,
+    },
+};
+use scamplers_schema::{
+    chip_loading,
+    chromium_run::{
+        self, chip as chip_col, id as id_col, readable_id as readable_id_col, run_at as run_at_col,
+        run_by as run_by_col, succeeded as succeeded_col,
+    },
+    gems, suspension,
+};
+use uuid::Uuid;
 
+use crate::{
+    db::{
+        error::Result,
+        model::{self, AsDieselQueryBase, FetchById, FetchRelatives},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewChromiumRun {
+    type Returns = ChromiumRun;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let id = diesel::insert_into(chromium_run::table)
+            .values(&self)
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        for new_gems in &self.gems {
+            write_gems(&id, new_gems, db_conn).await?;
+        }
+
+        ChromiumRun::fetch_by_id(&id, db_conn).await
+    }
+}
+
+async fn write_gems(
+    chromium_run_id: &Uuid,
+    new_gems: &NewGems,
+    db_conn: &mut AsyncPgConnection,
+) -> Result<()> {
+    let n_samples = count_samples(&new_gems.loading, db_conn).await?;
+
+    let gems_id: Uuid = diesel::insert_into(gems::table)
+        .values((
+            new_gems,
+            gems::chromium_run_id.eq(chromium_run_id),
+            gems::n_samples.eq(n_samples),
+        ))
+        .returning(gems::id)
+        .get_result(db_conn)
+        .await?;
+
+    let loading: Vec<_> = new_gems
+        .loading
+        .iter()
+        .map(|l| (l, chip_loading::gems_id.eq(gems_id)))
+        .collect();
+
+    diesel::insert_into(chip_loading::table)
+        .values(loading)
+        .execute(db_conn)
+        .await?;
+
+    Ok(())
+}
+
+// A suspension is one sample, while a multiplexed suspension contributes every suspension that was pooled into it
+async fn count_samples(loading: &[NewChipLoading], db_conn: &mut AsyncPgConnection) -> Result<i32> {
+    let mut n_samples = 0;
+
+    for NewChipLoading {
+        multiplexed_suspension_id,
+        ..
+    } in loading
+    {
+        let Some(multiplexed_suspension_id) = multiplexed_suspension_id else {
+            n_samples += 1;
+            continue;
+        };
+
+        let n_pooled: i64 = suspension::table
+            .filter(suspension::pooled_into_id.eq(multiplexed_suspension_id))
+            .count()
+            .get_result(db_conn)
+            .await?;
+
+        n_samples += n_pooled;
+    }
+
+    Ok(i32::try_from(n_samples).unwrap_or(i32::MAX))
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for ChromiumRunQuery
+where
+    id_col: SelectableExpression<QuerySource>,
+    readable_id_col: SelectableExpression<QuerySource>,
+    chip_col: SelectableExpression<QuerySource>,
+    run_by_col: SelectableExpression<QuerySource>,
+    succeeded_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            ids,
+            readable_id,
+            chip,
+            run_by,
+            succeeded,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(readable_id) = readable_id {
+            query = query.and_condition(readable_id_col.ilike(readable_id.as_ilike()));
+        }
+
+        if let Some(chip) = chip {
+            query = query.and_condition(chip_col.eq(chip));
+        }
+
+        if let Some(run_by) = run_by {
+            query = query.and_condition(run_by_col.eq(run_by));
+        }
+
+        if let Some(succeeded) = succeeded {
+            query = query.and_condition(succeeded_col.eq(succeeded));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for ChromiumRunSummary {
+    type QueryBase = chromium_run::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        chromium_run::table
+    }
+}
+
+impl model::FetchByQuery for ChromiumRunSummary {
+    type QueryParams = ChromiumRunQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::chromium::ChromiumRunOrdinalColumn::{ReadableId, RunAt};
+
+        fetch_by_query!(
+            query,
+            [(RunAt, run_at_col), (ReadableId, readable_id_col)],
+            id_col,
+            db_conn
+        )
+    }
+}
+
+impl model::FetchRelatives<ChipLoading> for gems::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        gems_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<ChipLoading>> {
+        let loading = chip_loading::table
+            .filter(chip_loading::gems_id.eq(gems_id))
+            .select(ChipLoading::as_select())
+            .load(db_conn)
+            .await?;
+
+        Ok(loading)
+    }
+}
+
+impl model::FetchById for Gems {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let summary = gems::table
+            .find(id)
+            .select(GemsSummary::as_select())
+            .first(db_conn)
+            .await?;
+        let loading =
+            <gems::table as FetchRelatives<ChipLoading>>::fetch_relatives(id, db_conn).await?;
+
+        Ok(Self::new(summary, loading))
+    }
+}
+
+impl model::FetchById for ChromiumRun {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let summary = ChromiumRunSummary::as_diesel_query_base()
+            .find(id)
+            .select(ChromiumRunSummary::as_select())
+            .first(db_conn)
+            .await?;
+
+        let gems_ids: Vec<Uuid> = gems::table
+            .filter(gems::chromium_run_id.eq(id))
+            .select(gems::id)
+            .order_by(gems::readable_id)
+            .load(db_conn)
+            .await?;
+
+        let mut gems = Vec::with_capacity(gems_ids.len());
+        for gems_id in &gems_ids {
+            gems.push(Gems::fetch_by_id(gems_id, db_conn).await?);
+        }
+
+        Ok(Self::new(summary, gems))
+    }
+}
+
+impl model::FetchByReadableId for ChromiumRun {
+    async fn fetch_by_readable_id(
+        readable_id: &str,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Self> {
+        let id = chromium_run::table
+            .select(id_col)
+            .filter(readable_id_col.eq(readable_id))
+            .get_result(db_conn)
+            .await?;
+
+        Self::fetch_by_id(&id, db_conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        chromium::{ChipType, NewChipLoading, NewChromiumRun, NewGems},
+        multiplexed_suspension::{
+            MultiplexingTag, MultiplexingTagQuery, NewMultiplexedSuspension, PooledSuspension,
+        },
+        person::{PersonQuery, PersonSummary},
+        suspension::{SuspensionQuery, SuspensionSummary},
+        units::{Quantity, Unit},
+    };
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, Write},
+        test_util::{DbConnection, db_conn},
+    };
+
+    fn loading(
+        suspension_id: Option<Uuid>,
+        multiplexed_suspension_id: Option<Uuid>,
+    ) -> NewChipLoading {
+        NewChipLoading {
+            suspension_id,
+            multiplexed_suspension_id,
+            suspension_volume_loaded: Quantity::new(10.0, Unit::Microliter),
+            buffer_volume_loaded: Quantity::new(30.0, Unit::Microliter),
+            notes: None,
+        }
+    }
+
+    fn new_run(chip: ChipType, gems: Vec<NewGems>, run_by: Uuid) -> NewChromiumRun {
+        NewChromiumRun {
+            readable_id: "RUN-new".to_string(),
+            chip,
+            run_at: OffsetDateTime::now_utc(),
+            run_by,
+            succeeded: true,
+            notes: None,
+            gems,
+        }
+    }
+
+    fn new_gems(i: usize, loading: Vec<NewChipLoading>) -> NewGems {
+        NewGems {
+            readable_id: format!("GEMS-new{i}"),
+            chemistry: None,
+            loading,
+        }
+    }
+
+    #[test]
+    fn chip_capacity() {
+        let run = |n_gems| {
+            let gems = (0..n_gems)
+                .map(|i| new_gems(i, vec![loading(Some(Uuid::default()), None)]))
+                .collect();
+
+            new_run(ChipType::ChipK, gems, Uuid::default())
+        };
+
+        run(ChipType::MAX_WELLS).validate().unwrap();
+        run(ChipType::MAX_WELLS + 1).validate().unwrap_err();
+        run(0).validate().unwrap_err();
+    }
+
+    #[test]
+    fn loading_has_exactly_one_suspension() {
+        loading(Some(Uuid::default()), None).validate().unwrap();
+        loading(None, Some(Uuid::default())).validate().unwrap();
+        loading(Some(Uuid::default()), Some(Uuid::default()))
+            .validate()
+            .unwrap_err();
+        loading(None, None).validate().unwrap_err();
+    }
+
+    #[test]
+    fn loading_requires_volumes() {
+        let mut invalid = loading(Some(Uuid::default()), None);
+        invalid.buffer_volume_loaded = Quantity::new(30.0, Unit::Nanogram);
+
+        invalid.validate().unwrap_err();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn n_samples_is_derived(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let suspensions =
+                        SuspensionSummary::fetch_by_query(&SuspensionQuery::default(), tx)
+                            .await
+                            .unwrap()
+                            .results;
+
+                    let tags =
+                        MultiplexingTag::fetch_by_query(&MultiplexingTagQuery::default(), tx)
+                            .await
+                            .unwrap()
+                            .results;
+
+                    let person = PersonSummary::fetch_by_query(&PersonQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    let pooled: Vec<_> = suspensions[..3]
+                        .iter()
+                        .zip(&tags)
+                        .map(|(s, t)| PooledSuspension {
+                            suspension_id: *s.id(),
+                            multiplexing_tag_id: *t.id(),
+                        })
+                        .collect();
+
+                    let pool = NewMultiplexedSuspension {
+                        name: "pool".to_string(),
+                        readable_id: "POOL-chromium".to_string(),
+                        pooled_at: OffsetDateTime::now_utc(),
+                        notes: None,
+                        suspensions: pooled,
+                        preparer_ids: vec![*person.id()],
+                        measurements: vec![],
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap();
+
+                    let gems = vec![
+                        new_gems(0, vec![loading(None, Some(*pool.id()))]),
+                        new_gems(
+                            1,
+                            vec![
+                                loading(Some(*suspensions[3].id()), None),
+                                loading(Some(*suspensions[4].id()), None),
+                            ],
+                        ),
+                    ];
+
+                    let run = new_run(ChipType::ChipK, gems, *person.id())
+                        .write(tx)
+                        .await
+                        .unwrap();
+
+                    let n_samples: Vec<_> = run.gems().iter().map(|g| *g.n_samples()).collect();
+                    assert_eq!(n_samples, [3, 2]);
+                    assert_eq!(run.gems()[1].loading().len(), 2);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
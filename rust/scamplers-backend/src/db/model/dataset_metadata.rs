@@ -1 +1,325 @@
+use diesel::{dsl::AssumeNotNull, prelude::*};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    dataset_metadata::{
+        CellrangerMetrics, ChromiumDataset, ChromiumDatasetQuery, ChromiumDatasetSummary,
+        NewChromiumDataset,
+    },
+};
+use scamplers_schema::{
+    chromium_dataset::{
+        self, estimated_number_of_cells as estimated_number_of_cells_col, gems_id as gems_id_col,
+        median_genes_per_cell as median_genes_per_cell_col,
+        sequencing_saturation as sequencing_saturation_col,
+    },
+    dataset_metadata::{
+        self, delivered_at as delivered_at_col, id as id_col, lab_id as lab_id_col,
+        name as name_col,
+    },
+};
+use uuid::Uuid;
 
+use crate::{
+    db::{
+        error::{Error, Result},
+        model::{self, AsDieselQueryBase, FetchById},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewChromiumDataset {
+    type Returns = ChromiumDataset;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        // This has already been validated, so an error here is unexpected
+        let metrics =
+            CellrangerMetrics::from_metrics_summary(&self.metrics_summary).map_err(|e| {
+                Error::Other {
+                    message: e.to_string(),
+                }
+            })?;
+
+        let id = diesel::insert_into(dataset_metadata::table)
+            .values(&self)
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        diesel::insert_into(chromium_dataset::table)
+            .values((
+                chromium_dataset::id.eq(id),
+                gems_id_col.eq(self.gems_id),
+                chromium_dataset::metrics.eq(vec![metrics]),
+                chromium_dataset::web_summary.eq(&self.web_summary),
+            ))
+            .execute(db_conn)
+            .await?;
+
+        ChromiumDataset::fetch_by_id(&id, db_conn).await
+    }
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for ChromiumDatasetQuery
+where
+    id_col: SelectableExpression<QuerySource>,
+    name_col: SelectableExpression<QuerySource>,
+    lab_id_col: SelectableExpression<QuerySource>,
+    gems_id_col: SelectableExpression<QuerySource>,
+    estimated_number_of_cells_col: SelectableExpression<QuerySource>,
+    median_genes_per_cell_col: SelectableExpression<QuerySource>,
+    sequencing_saturation_col: SelectableExpression<QuerySource>,
+    AssumeNotNull<estimated_number_of_cells_col>: SelectableExpression<QuerySource>,
+    AssumeNotNull<median_genes_per_cell_col>: SelectableExpression<QuerySource>,
+    AssumeNotNull<sequencing_saturation_col>: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            ids,
+            name,
+            lab_ids,
+            gems_ids,
+            min_estimated_number_of_cells,
+            max_estimated_number_of_cells,
+            min_median_genes_per_cell,
+            max_median_genes_per_cell,
+            min_sequencing_saturation,
+            max_sequencing_saturation,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(name) = name {
+            query = query.and_condition(name_col.ilike(name.as_ilike()));
+        }
+
+        if !lab_ids.is_empty() {
+            query = query.and_condition(lab_id_col.eq_any(lab_ids));
+        }
+
+        if !gems_ids.is_empty() {
+            query = query.and_condition(gems_id_col.eq_any(gems_ids));
+        }
+
+        if let Some(min) = min_estimated_number_of_cells {
+            query = query.and_condition(estimated_number_of_cells_col.assume_not_null().ge(min));
+        }
+
+        if let Some(max) = max_estimated_number_of_cells {
+            query = query.and_condition(estimated_number_of_cells_col.assume_not_null().le(max));
+        }
+
+        if let Some(min) = min_median_genes_per_cell {
+            query = query.and_condition(median_genes_per_cell_col.assume_not_null().ge(min));
+        }
+
+        if let Some(max) = max_median_genes_per_cell {
+            query = query.and_condition(median_genes_per_cell_col.assume_not_null().le(max));
+        }
+
+        if let Some(min) = min_sequencing_saturation {
+            query = query.and_condition(sequencing_saturation_col.assume_not_null().ge(min));
+        }
+
+        if let Some(max) = max_sequencing_saturation {
+            query = query.and_condition(sequencing_saturation_col.assume_not_null().le(max));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for ChromiumDatasetSummary {
+    type QueryBase = diesel::dsl::InnerJoin<dataset_metadata::table, chromium_dataset::table>;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        dataset_metadata::table.inner_join(chromium_dataset::table)
+    }
+}
+
+impl model::FetchByQuery for ChromiumDatasetSummary {
+    type QueryParams = ChromiumDatasetQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::dataset_metadata::ChromiumDatasetOrdinalColumn::{
+            DeliveredAt, EstimatedNumberOfCells, MedianGenesPerCell, Name, SequencingSaturation,
+        };
+
+        fetch_by_query!(
+            query,
+            [
+                (DeliveredAt, delivered_at_col),
+                (Name, name_col),
+                (EstimatedNumberOfCells, estimated_number_of_cells_col),
+                (MedianGenesPerCell, median_genes_per_cell_col),
+                (SequencingSaturation, sequencing_saturation_col)
+            ],
+            id_col,
+            db_conn
+        )
+    }
+}
+
+impl model::FetchById for ChromiumDataset {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        Ok(ChromiumDatasetSummary::as_diesel_query_base()
+            .filter(id_col.eq(id))
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::prelude::*;
+    use diesel_async::{
+        AsyncConnection, AsyncPgConnection, RunQueryDsl, scoped_futures::ScopedFutureExt,
+    };
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        dataset_metadata::{ChromiumDatasetQuery, ChromiumDatasetSummary, NewChromiumDataset},
+        lab::{LabQuery, LabSummary},
+    };
+    use scamplers_schema::gems;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, Write},
+        test_util::{DbConnection, db_conn},
+    };
+
+    const WEB_SUMMARY: &str = "<!DOCTYPE html><html><head><title>Web Summary</title></head><body><div \
+                               id=\"summary\"></div></body></html>";
+
+    fn metrics_summary(n_cells: &str, saturation: &str) -> String {
+        format!(
+            "Estimated Number of Cells,Mean Reads per Cell,Median Genes per Cell,Sequencing \
+             Saturation\n\"{n_cells}\",\"45,678\",\"2,345\",{saturation}\n"
+        )
+    }
+
+    fn new_dataset(
+        name: &str,
+        lab_id: Uuid,
+        gems_id: Uuid,
+        metrics_summary: String,
+    ) -> NewChromiumDataset {
+        NewChromiumDataset {
+            name: name.to_string(),
+            lab_id,
+            data_path: format!("/datasets/{name}"),
+            delivered_at: OffsetDateTime::now_utc(),
+            gems_id,
+            web_summary: WEB_SUMMARY.to_string(),
+            metrics_summary,
+        }
+    }
+
+    async fn lab_and_gems(db_conn: &mut AsyncPgConnection) -> (Uuid, Uuid) {
+        let lab = LabSummary::fetch_by_query(&LabQuery::default(), db_conn)
+            .await
+            .unwrap()
+            .results
+            .remove(0);
+
+        let gems_id = gems::table.select(gems::id).first(db_conn).await.unwrap();
+
+        (*lab.id(), gems_id)
+    }
+
+    #[test]
+    fn web_summary_must_be_html() {
+        let valid = new_dataset(
+            "valid",
+            Uuid::default(),
+            Uuid::default(),
+            metrics_summary("5,123", "62.5%"),
+        );
+        valid.validate().unwrap();
+
+        for web_summary in [
+            "Estimated Number of Cells: 5,123",
+            "<!DOCTYPE html><html><body></body></html>",
+        ] {
+            let mut invalid = new_dataset(
+                "invalid",
+                Uuid::default(),
+                Uuid::default(),
+                metrics_summary("5,123", "62.5%"),
+            );
+            invalid.web_summary = web_summary.to_string();
+            invalid.validate().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn metrics_summary_must_be_parseable() {
+        let invalid = new_dataset(
+            "invalid",
+            Uuid::default(),
+            Uuid::default(),
+            metrics_summary("5,123", "most"),
+        );
+        invalid.validate().unwrap_err();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn filter_by_metrics(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let (lab_id, gems_id) = lab_and_gems(tx).await;
+
+                    let small =
+                        new_dataset("small", lab_id, gems_id, metrics_summary("1,000", "40.0%"))
+                            .write(tx)
+                            .await
+                            .unwrap();
+                    assert_eq!(small.metrics()[0].estimated_number_of_cells, 1000);
+
+                    new_dataset("large", lab_id, gems_id, metrics_summary("10,000", "90.0%"))
+                        .write(tx)
+                        .await
+                        .unwrap();
+
+                    let query = ChromiumDatasetQuery {
+                        min_estimated_number_of_cells: Some(5000),
+                        min_sequencing_saturation: Some(0.5),
+                        ..Default::default()
+                    };
+                    let datasets = ChromiumDatasetSummary::fetch_by_query(&query, tx)
+                        .await
+                        .unwrap()
+                        .results;
+
+                    let names: Vec<_> = datasets.iter().map(|d| d.name().as_str()).collect();
+                    assert_eq!(names, ["large"]);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
@@ -0,0 +1,212 @@
+use diesel::{
+    IntoSql,
+    dsl::{self, not},
+    pg::Pg,
+    prelude::*,
+    sql_types::{self, Bool},
+};
+use scamplers_core::model::filter::Filter;
+use uuid::Uuid;
+
+use crate::db::{
+    model::AsDieselFilter,
+    util::{BoxedDieselExpression, NewBoxedDieselExpression},
+};
+
+/// Compiles the conditions of a filter that apply to a single column, as opposed to those that combine other conditions
+pub(super) trait AsDieselCondition<QuerySource>: Sized {
+    fn as_diesel_condition<'a>(filter: &'a Filter<Self>) -> BoxedDieselExpression<'a, QuerySource>
+    where
+        QuerySource: 'a;
+}
+
+// A condition that can't be applied to its column matches nothing. Requests are validated before they get here, so this
+// only happens if a filter is constructed directly.
+pub(super) fn matches_nothing<'a, QuerySource>() -> BoxedDieselExpression<'a, QuerySource> {
+    Box::new(false.into_sql::<Bool>())
+}
+
+impl<C, QuerySource> AsDieselFilter<QuerySource> for Filter<C>
+where
+    C: AsDieselCondition<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        // `None` means that the filter matches everything
+        match self {
+            Self::And(filters) => {
+                let mut query = BoxedDieselExpression::new_expression();
+
+                for filter in filters {
+                    if let Some(condition) = filter.as_diesel_filter() {
+                        query = query.and_condition(condition);
+                    }
+                }
+
+                query.build()
+            }
+            Self::Or(filters) => {
+                let mut query = BoxedDieselExpression::new_expression();
+
+                for filter in filters {
+                    query = query.or_condition(filter.as_diesel_filter()?);
+                }
+
+                Some(query.build().unwrap_or_else(matches_nothing))
+            }
+            Self::Not(filter) => Some(match filter.as_diesel_filter() {
+                Some(condition) => Box::new(not(condition)),
+                None => matches_nothing(),
+            }),
+            _ => Some(C::as_diesel_condition(self)),
+        }
+    }
+}
+
+/// Compiles the conditions of a filter that can be applied to a column of this SQL type. The column's type is read
+/// from the schema, as it is for validation in `scamplers_core`, so the two can't disagree.
+pub(super) trait AsColumnCondition<Column, QuerySource> {
+    fn as_column_condition<'a, C>(
+        column: Column,
+        filter: &'a Filter<C>,
+    ) -> BoxedDieselExpression<'a, QuerySource>
+    where
+        QuerySource: 'a;
+}
+
+impl<Column, QuerySource> AsColumnCondition<Column, QuerySource> for sql_types::Uuid
+where
+    Column: Expression<SqlType = sql_types::Uuid> + 'static,
+    dsl::Eq<Column, Uuid>: BoxableExpression<QuerySource, Pg, SqlType = Bool>,
+    dsl::EqAny<Column, Vec<Uuid>>: BoxableExpression<QuerySource, Pg, SqlType = Bool>,
+{
+    fn as_column_condition<'a, C>(
+        column: Column,
+        filter: &'a Filter<C>,
+    ) -> BoxedDieselExpression<'a, QuerySource>
+    where
+        QuerySource: 'a,
+    {
+        match filter {
+            Filter::Eq { value, .. } => match value.parse::<Uuid>() {
+                Ok(value) => Box::new(column.eq(value)),
+                Err(_) => matches_nothing(),
+            },
+            Filter::In { values, .. } => {
+                let values: Vec<Uuid> = values.iter().filter_map(|v| v.parse().ok()).collect();
+                Box::new(column.eq_any(values))
+            }
+            _ => matches_nothing(),
+        }
+    }
+}
+
+impl<Column, QuerySource> AsColumnCondition<Column, QuerySource> for sql_types::Text
+where
+    Column: Expression<SqlType = sql_types::Text> + Copy + 'static,
+    dsl::Eq<Column, String>: BoxableExpression<QuerySource, Pg, SqlType = Bool>,
+    dsl::EqAny<Column, Vec<String>>: BoxableExpression<QuerySource, Pg, SqlType = Bool>,
+    dsl::ILike<Column, String>: BoxableExpression<QuerySource, Pg, SqlType = Bool>,
+    dsl::GtEq<Column, String>: BoxableExpression<QuerySource, Pg, SqlType = Bool>,
+    dsl::LtEq<Column, String>: BoxableExpression<QuerySource, Pg, SqlType = Bool>,
+{
+    fn as_column_condition<'a, C>(
+        column: Column,
+        filter: &'a Filter<C>,
+    ) -> BoxedDieselExpression<'a, QuerySource>
+    where
+        QuerySource: 'a,
+    {
+        match filter {
+            Filter::Eq { value, .. } => Box::new(column.eq(value.clone())),
+            Filter::In { values, .. } => Box::new(column.eq_any(values.clone())),
+            Filter::Ilike { pattern, .. } => Box::new(column.ilike(pattern.clone())),
+            Filter::Range { min, max, .. } => {
+                let mut query = BoxedDieselExpression::new_expression();
+
+                if let Some(min) = min {
+                    query = query.and_condition(column.ge(min.clone()));
+                }
+
+                if let Some(max) = max {
+                    query = query.and_condition(column.le(max.clone()));
+                }
+
+                query
+                    .build()
+                    .unwrap_or_else(|| Box::new(true.into_sql::<Bool>()))
+            }
+            _ => matches_nothing(),
+        }
+    }
+}
+
+impl<Column, QuerySource> AsColumnCondition<Column, QuerySource>
+    for sql_types::Nullable<sql_types::Text>
+where
+    Column: Expression<SqlType = sql_types::Nullable<sql_types::Text>> + 'static,
+    dsl::IsNull<Column>: BoxableExpression<QuerySource, Pg, SqlType = Bool>,
+    sql_types::Text: AsColumnCondition<dsl::AssumeNotNull<Column>, QuerySource>,
+{
+    fn as_column_condition<'a, C>(
+        column: Column,
+        filter: &'a Filter<C>,
+    ) -> BoxedDieselExpression<'a, QuerySource>
+    where
+        QuerySource: 'a,
+    {
+        match filter {
+            Filter::IsNull { .. } => Box::new(column.is_null()),
+            _ => sql_types::Text::as_column_condition(column.assume_not_null(), filter),
+        }
+    }
+}
+
+pub(super) fn column_condition<'a, Column, QuerySource, C>(
+    column: Column,
+    filter: &'a Filter<C>,
+) -> BoxedDieselExpression<'a, QuerySource>
+where
+    Column: Expression,
+    Column::SqlType: AsColumnCondition<Column, QuerySource>,
+    QuerySource: 'a,
+{
+    Column::SqlType::as_column_condition(column, filter)
+}
+
+/// Implements `AsDieselCondition` for a resource's enum of filterable columns, given the database column that each
+/// variant corresponds to
+#[macro_export]
+macro_rules! impl_as_diesel_condition {
+    ($column_enum:ident, $query_source:ty, [$(($variant:ident, $db_col:expr)),*]) => {
+        impl $crate::db::model::filter::AsDieselCondition<$query_source> for $column_enum {
+            fn as_diesel_condition<'a>(
+                filter: &'a scamplers_core::model::filter::Filter<Self>,
+            ) -> $crate::db::util::BoxedDieselExpression<'a, $query_source>
+            where
+                $query_source: 'a,
+            {
+                use scamplers_core::model::filter::Filter;
+
+                let (Filter::Eq { column, .. }
+                | Filter::Ilike { column, .. }
+                | Filter::In { column, .. }
+                | Filter::Range { column, .. }
+                | Filter::IsNull { column }) = filter
+                else {
+                    return $crate::db::model::filter::matches_nothing();
+                };
+
+                match column {
+                    $(
+                        $column_enum::$variant => {
+                            $crate::db::model::filter::column_condition($db_col, filter)
+                        }
+                    )*
+                }
+            }
+        }
+    };
+}
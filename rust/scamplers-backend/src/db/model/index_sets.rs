@@ -0,0 +1,313 @@
+use diesel::{dsl::sql, prelude::*, sql_types};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    index_sets::{
+        DualIndexSetSummary, IndexSet, IndexSetOrdinalColumn, IndexSetQuery, SingleIndexSetSummary,
+    },
+};
+use scamplers_schema::{dual_index_set, single_index_set};
+
+use crate::db::{
+    error::Result,
+    model::{self, AsDieselFilter},
+    util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+};
+
+// The `index_set` view combines the two index set tables so that they can be searched, ordered and paginated together.
+// Diesel doesn't generate declarations for views, so we declare it here.
+diesel::table! {
+    index_set (name) {
+        name -> Text,
+        kit -> Text,
+        well -> Text,
+        sequences -> Array<Text>,
+        index_i7 -> Nullable<Text>,
+        index2_workflow_a_i5 -> Nullable<Text>,
+        index2_workflow_b_i5 -> Nullable<Text>,
+    }
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = index_set, check_for_backend(diesel::pg::Pg))]
+struct IndexSetRow {
+    name: String,
+    kit: String,
+    well: String,
+    sequences: Vec<String>,
+    index_i7: Option<String>,
+    index2_workflow_a_i5: Option<String>,
+    index2_workflow_b_i5: Option<String>,
+}
+
+impl From<IndexSetRow> for IndexSet {
+    // The bindings are named after the columns they come from
+    #[allow(clippy::similar_names)]
+    fn from(row: IndexSetRow) -> Self {
+        let IndexSetRow {
+            name,
+            kit,
+            well,
+            sequences,
+            index_i7,
+            index2_workflow_a_i5,
+            index2_workflow_b_i5,
+        } = row;
+
+        match (index_i7, index2_workflow_a_i5, index2_workflow_b_i5) {
+            (Some(index_i7), Some(index2_workflow_a_i5), Some(index2_workflow_b_i5)) => {
+                Self::Dual(DualIndexSetSummary {
+                    name,
+                    kit,
+                    well,
+                    index_i7,
+                    index2_workflow_a_i5,
+                    index2_workflow_b_i5,
+                })
+            }
+            _ => Self::Single(SingleIndexSetSummary {
+                name,
+                kit,
+                well,
+                sequences,
+            }),
+        }
+    }
+}
+
+impl AsDieselFilter<index_set::table> for IndexSetQuery {
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, index_set::table>>
+    where
+        index_set::table: 'a,
+    {
+        use index_set::{kit, well};
+
+        let Self {
+            kits,
+            wells,
+            sequence,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !kits.is_empty() {
+            query = query.and_condition(kit.eq_any(kits));
+        }
+
+        if !wells.is_empty() {
+            query = query.and_condition(well.eq_any(wells));
+        }
+
+        if let Some(sequence) = sequence {
+            query = query.and_condition(
+                sql::<sql_types::Text>("array_to_string(sequences, ',')")
+                    .ilike(sequence.as_ilike()),
+            );
+        }
+
+        query.build()
+    }
+}
+
+impl model::FetchByQuery for IndexSet {
+    type QueryParams = IndexSetQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use IndexSetOrdinalColumn::{Kit, Name, Well};
+        use index_set::{kit, name, well};
+
+        let IndexSetQuery {
+            order_by,
+            pagination:
+                Pagination {
+                    limit,
+                    offset,
+                    count_total,
+                    ..
+                },
+            ..
+        } = query;
+
+        let mut statement = index_set::table
+            .select(IndexSetRow::as_select())
+            .limit(*limit)
+            .offset(*offset)
+            .into_boxed();
+
+        if let Some(filter) = query.as_diesel_filter() {
+            statement = statement.filter(filter);
+        }
+
+        for ordering in order_by {
+            statement = match (ordering.column, ordering.descending) {
+                (Name, false) => statement.then_order_by(name.asc()),
+                (Name, true) => statement.then_order_by(name.desc()),
+                (Kit, false) => statement.then_order_by(kit.asc()),
+                (Kit, true) => statement.then_order_by(kit.desc()),
+                (Well, false) => statement.then_order_by(well.asc()),
+                (Well, true) => statement.then_order_by(well.desc()),
+            };
+        }
+
+        let total_count = if *count_total {
+            let mut statement = index_set::table.count().into_boxed();
+
+            if let Some(filter) = query.as_diesel_filter() {
+                statement = statement.filter(filter);
+            }
+
+            Some(statement.get_result(db_conn).await?)
+        } else {
+            None
+        };
+
+        let rows: Vec<IndexSetRow> = statement.load(db_conn).await?;
+
+        Ok(Page {
+            results: rows.into_iter().map(Self::from).collect(),
+            next_cursor: None,
+            total_count,
+        })
+    }
+}
+
+impl model::FetchById for IndexSet {
+    type Id = String;
+
+    // Index set names are unique across kits, so a name identifies either a single or a dual index set
+    async fn fetch_by_id(name: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let single_index_set = single_index_set::table
+            .find(name)
+            .select(SingleIndexSetSummary::as_select())
+            .first(db_conn)
+            .await
+            .optional()?;
+
+        if let Some(index_set) = single_index_set {
+            return Ok(Self::Single(index_set));
+        }
+
+        let dual_index_set = dual_index_set::table
+            .find(name)
+            .select(DualIndexSetSummary::as_select())
+            .first(db_conn)
+            .await?;
+
+        Ok(Self::Dual(dual_index_set))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        Pagination,
+        index_sets::{IndexSet, IndexSetOrdering, IndexSetOrdinalColumn, IndexSetQuery},
+    };
+
+    use crate::db::{
+        error::Error,
+        model::{FetchById, FetchByQuery},
+        test_util::{
+            DbConnection, TEST_DUAL_INDEX_SET, TEST_SINGLE_INDEX_SET, db_conn, test_query,
+        },
+    };
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn fetch_index_sets_by_name(#[future] mut db_conn: DbConnection) {
+        let single = IndexSet::fetch_by_id(&TEST_SINGLE_INDEX_SET.to_string(), &mut db_conn)
+            .await
+            .unwrap();
+        let IndexSet::Single(single) = single else {
+            panic!("expected single index set, found {single:?}");
+        };
+        assert_eq!(single.sequences.len(), 4);
+
+        let dual = IndexSet::fetch_by_id(&TEST_DUAL_INDEX_SET.to_string(), &mut db_conn)
+            .await
+            .unwrap();
+        assert!(matches!(dual, IndexSet::Dual(_)));
+
+        let missing = IndexSet::fetch_by_id(&"SI-TT-H12".to_string(), &mut db_conn).await;
+        assert!(matches!(missing, Err(Error::RecordNotFound)));
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn search_index_sets_by_well(#[future] db_conn: DbConnection) {
+        let query = IndexSetQuery {
+            wells: vec!["A1".to_string()],
+            ..Default::default()
+        };
+
+        test_query(
+            query,
+            db_conn,
+            2,
+            |s: &IndexSet| s.name().to_string(),
+            &[(0, TEST_SINGLE_INDEX_SET), (1, TEST_DUAL_INDEX_SET)],
+        )
+        .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn paginate_index_sets_across_kinds(#[future] mut db_conn: DbConnection) {
+        let query = IndexSetQuery {
+            wells: vec!["A1".to_string()],
+            order_by: vec![IndexSetOrdering {
+                column: IndexSetOrdinalColumn::Name,
+                descending: true,
+            }],
+            pagination: Pagination {
+                limit: 1,
+                offset: 1,
+                count_total: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let page = IndexSet::fetch_by_query(&query, &mut db_conn)
+            .await
+            .unwrap();
+
+        assert_eq!(page.total_count, Some(2));
+        assert_eq!(page.results.len(), 1);
+        assert!(matches!(&page.results[0], IndexSet::Single(s) if s.name == TEST_SINGLE_INDEX_SET));
+    }
+
+    #[rstest]
+    #[case("ctaaac", TEST_SINGLE_INDEX_SET)]
+    #[case("GTAACACT", TEST_DUAL_INDEX_SET)]
+    #[awt]
+    #[tokio::test]
+    async fn search_index_sets_by_sequence(
+        #[future] db_conn: DbConnection,
+        #[case] sequence: &str,
+        #[case] expected_name: &str,
+    ) {
+        let query = IndexSetQuery {
+            sequence: Some(sequence.to_string()),
+            ..Default::default()
+        };
+
+        test_query(
+            query,
+            db_conn,
+            1,
+            |s: &IndexSet| s.name().to_string(),
+            &[(0, expected_name)],
+        )
+        .await;
+    }
+}
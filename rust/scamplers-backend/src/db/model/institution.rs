@@ -1,18 +1,31 @@
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use scamplers_core::model::{
-    Pagination,
-    institution::{Institution, InstitutionQuery, InstitutionSummary, NewInstitution},
+    Page, Pagination, SimilaritySearch,
+    filter::Filter,
+    institution::{
+        Institution, InstitutionFilterColumn, InstitutionQuery, InstitutionSummary,
+        InstitutionUpdate, NewInstitution,
+    },
+    lab::LabSummary,
+    person::PersonSummary,
+};
+use scamplers_schema::{
+    institution::dsl::{
+        country as country_col, id as id_col, institution, name as name_col, ror_id as ror_id_col,
+    },
+    lab, person,
 };
-use scamplers_schema::institution::dsl::{id as id_col, institution, name as name_col};
 use uuid::Uuid;
 
 use crate::{
     db::{
-        model::{self, AsDieselQueryBase},
-        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+        model::{self, AsDieselQueryBase, FetchById},
+        util::{
+            AsIlike, BoxedDieselExpression, NewBoxedDieselExpression, SimilarityExpressionMethods,
+        },
     },
-    fetch_by_query,
+    fetch_by_query, impl_as_diesel_condition,
 };
 
 impl model::Write for NewInstitution {
@@ -31,6 +44,55 @@ impl model::Write for NewInstitution {
     }
 }
 
+impl model::Write for InstitutionUpdate {
+    type Returns = Institution;
+
+    async fn write(
+        self,
+        db_conn: &mut diesel_async::AsyncPgConnection,
+    ) -> super::error::Result<Self::Returns> {
+        if let Self {
+            name: None,
+            ror_id: None,
+            country: None,
+            remove_ror_id: false,
+            remove_country: false,
+            ..
+        } = &self
+        {
+            return Institution::fetch_by_id(&self.id, db_conn).await;
+        }
+
+        let ror_id = if self.remove_ror_id {
+            Some(ror_id_col.eq(None))
+        } else {
+            self.ror_id
+                .as_ref()
+                .map(|ror_id| ror_id_col.eq(Some(ror_id)))
+        };
+
+        let country = if self.remove_country {
+            Some(country_col.eq(None))
+        } else {
+            self.country
+                .as_ref()
+                .map(|country| country_col.eq(Some(country)))
+        };
+
+        let updated = diesel::update(&self)
+            .set((
+                self.name.as_ref().map(|name| name_col.eq(name)),
+                ror_id,
+                country,
+            ))
+            .returning(Institution::as_returning())
+            .get_result(db_conn)
+            .await?;
+
+        Ok(updated)
+    }
+}
+
 impl model::AsDieselQueryBase for InstitutionSummary {
     type QueryBase = institution;
 
@@ -67,12 +129,19 @@ impl<QuerySource> model::AsDieselFilter<QuerySource> for InstitutionQuery
 where
     id_col: SelectableExpression<QuerySource>,
     name_col: SelectableExpression<QuerySource>,
+    Filter<InstitutionFilterColumn>: model::AsDieselFilter<QuerySource>,
 {
     fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
     where
         QuerySource: 'a,
     {
-        let Self { ids, name, .. } = self;
+        let Self {
+            ids,
+            name,
+            similar_name,
+            filter,
+            ..
+        } = self;
 
         let mut query = BoxedDieselExpression::new_expression();
 
@@ -84,33 +153,103 @@ where
             query = query.and_condition(name_col.ilike(name.as_ilike()));
         }
 
+        if let Some(SimilaritySearch { text, .. }) = similar_name {
+            query = query.and_condition(name_col.trigram_similar_to(text));
+        }
+
+        if let Some(filter) = filter.as_diesel_filter() {
+            query = query.and_condition(filter);
+        }
+
         query.build()
     }
 }
 
+impl_as_diesel_condition!(
+    InstitutionFilterColumn,
+    institution,
+    [
+        (Id, id_col),
+        (Name, name_col),
+        (RorId, ror_id_col),
+        (Country, country_col)
+    ]
+);
+
 impl model::FetchByQuery for InstitutionSummary {
     type QueryParams = InstitutionQuery;
 
     async fn fetch_by_query(
         query: &Self::QueryParams,
         db_conn: &mut diesel_async::AsyncPgConnection,
-    ) -> super::error::Result<Vec<Self>> {
+    ) -> super::error::Result<Page<Self>> {
         use scamplers_core::model::institution::InstitutionOrdinalColumn::Name;
 
-        fetch_by_query!(query, [(Name, name_col)], db_conn)
+        fetch_by_query!(
+            query,
+            [(Name, name_col)],
+            id_col,
+            db_conn,
+            ranked_by_similarity(similar_name, name_col)
+        )
+    }
+}
+
+impl model::FetchRelatives<PersonSummary> for institution {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        institution_id: &Self::Id,
+        db_conn: &mut diesel_async::AsyncPgConnection,
+    ) -> super::error::Result<Vec<PersonSummary>> {
+        let people = PersonSummary::as_diesel_query_base()
+            .filter(person::institution_id.eq(institution_id))
+            .select(PersonSummary::as_select())
+            .order_by(person::name)
+            .load(db_conn)
+            .await?;
+
+        Ok(people)
+    }
+}
+
+// Labs don't belong to an institution directly, so we consider a lab to be at the institution of its PI
+impl model::FetchRelatives<LabSummary> for institution {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        institution_id: &Self::Id,
+        db_conn: &mut diesel_async::AsyncPgConnection,
+    ) -> super::error::Result<Vec<LabSummary>> {
+        let labs = LabSummary::as_diesel_query_base()
+            .inner_join(person::table)
+            .filter(person::institution_id.eq(institution_id))
+            .select(LabSummary::as_select())
+            .order_by(lab::name)
+            .load(db_conn)
+            .await?;
+
+        Ok(labs)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
     use rstest::rstest;
     use scamplers_core::model::institution::*;
 
-    use crate::db::test_util::{DbConnection, N_INSTITUTIONS, db_conn, test_query};
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, FetchRelatives, Write},
+        test_util::{DbConnection, N_INSTITUTIONS, N_LABS, N_PEOPLE, db_conn, test_query},
+    };
 
     fn comparison_fn(i: &InstitutionSummary) -> String {
-        i.name().to_string()
+        i.name().clone()
     }
 
     #[rstest]
@@ -144,4 +283,144 @@ mod tests {
 
         test_query(query, db_conn, 11, comparison_fn, &expected).await;
     }
+
+    #[rstest]
+    #[case(Some("05x2bcf33"), Some("US"), true)]
+    #[case(Some("https://ror.org/05x2bcf33"), None, false)]
+    #[case(None, Some("USA"), false)]
+    #[case(None, Some("us"), false)]
+    fn validate_ror_id_and_country(
+        #[case] ror_id: Option<&str>,
+        #[case] country: Option<&str>,
+        #[case] is_valid: bool,
+    ) {
+        let new_institution = NewInstitution {
+            id: Uuid::now_v7(),
+            name: "Krusty Krab".to_string(),
+            ror_id: ror_id.map(str::to_string),
+            country: country.map(str::to_string),
+        };
+
+        assert_eq!(new_institution.validate().is_ok(), is_valid);
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn update_institution(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let original =
+                        InstitutionSummary::fetch_by_query(&InstitutionQuery::default(), tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .remove(0);
+
+                    let update = InstitutionUpdate {
+                        id: *original.id(),
+                        name: Some("Chum Bucket".to_string()),
+                        ror_id: Some("05x2bcf33".to_string()),
+                        ..Default::default()
+                    };
+                    let updated = update.write(tx).await.unwrap();
+
+                    assert_eq!(updated.name(), "Chum Bucket");
+                    assert_eq!(updated.ror_id(), Some("05x2bcf33"));
+                    assert_eq!(updated.country(), None);
+
+                    let unchanged = InstitutionUpdate {
+                        id: *original.id(),
+                        ..Default::default()
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap();
+
+                    assert_eq!(unchanged.name(), "Chum Bucket");
+
+                    let cleared = InstitutionUpdate {
+                        id: *original.id(),
+                        country: Some("US".to_string()),
+                        remove_ror_id: true,
+                        ..Default::default()
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap();
+
+                    assert_eq!(cleared.ror_id(), None);
+                    assert_eq!(cleared.country(), Some("US"));
+
+                    let cleared = InstitutionUpdate {
+                        id: *original.id(),
+                        remove_country: true,
+                        ..Default::default()
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap();
+
+                    assert_eq!(cleared.name(), "Chum Bucket");
+                    assert_eq!(cleared.country(), None);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[case(false, false, true)]
+    #[case(false, true, true)]
+    #[case(true, false, false)]
+    fn validate_removals(
+        #[case] remove_ror_id: bool,
+        #[case] remove_country: bool,
+        #[case] is_valid: bool,
+    ) {
+        let update = InstitutionUpdate {
+            id: Uuid::now_v7(),
+            ror_id: Some("05x2bcf33".to_string()),
+            remove_ror_id,
+            remove_country,
+            ..Default::default()
+        };
+
+        assert_eq!(update.validate().is_ok(), is_valid);
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn institution_people_and_labs(#[future] mut db_conn: DbConnection) {
+        let institutions =
+            InstitutionSummary::fetch_by_query(&InstitutionQuery::default(), &mut db_conn)
+                .await
+                .unwrap()
+                .results;
+
+        let (mut n_people, mut n_labs) = (0, 0);
+        for i in &institutions {
+            let people = <institution as FetchRelatives<PersonSummary>>::fetch_relatives(
+                i.id(),
+                &mut db_conn,
+            )
+            .await
+            .unwrap();
+            let labs =
+                <institution as FetchRelatives<LabSummary>>::fetch_relatives(i.id(), &mut db_conn)
+                    .await
+                    .unwrap();
+
+            n_people += people.len();
+            n_labs += labs.len();
+        }
+
+        // Every person belongs to exactly one institution, and so does every lab through its PI
+        assert_eq!(n_people, N_PEOPLE);
+        assert_eq!(n_labs, N_LABS);
+    }
 }
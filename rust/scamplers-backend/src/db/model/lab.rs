@@ -1,19 +1,27 @@
 use crate::{
     db::{
         model::{self, AsDieselQueryBase, FetchById, FetchRelatives},
-        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+        util::{
+            AsIlike, BoxedDieselExpression, NewBoxedDieselExpression, SimilarityExpressionMethods,
+        },
     },
-    fetch_by_query,
+    fetch_by_query, impl_as_diesel_condition,
 };
 use diesel::{dsl::InnerJoin, prelude::*};
 use diesel_async::RunQueryDsl;
 use scamplers_core::model::{
-    Pagination,
-    lab::{Lab, LabData, LabQuery, LabSummary, LabUpdate, LabUpdateWithMembers, NewLab},
+    Page, Pagination, SimilaritySearch,
+    filter::Filter,
+    lab::{
+        Lab, LabData, LabFilterColumn, LabQuery, LabSummary, LabUpdate, LabUpdateWithMembers,
+        NewLab,
+    },
     person::PersonSummary,
 };
 use scamplers_schema::{
-    lab::{self, id as id_col, name as name_col},
+    lab::{
+        self, delivery_dir as delivery_dir_col, id as id_col, name as name_col, pi_id as pi_id_col,
+    },
     lab_membership::{self, lab_id as lab_id_col, member_id as member_id_col},
     person,
 };
@@ -109,12 +117,19 @@ impl<QuerySource> model::AsDieselFilter<QuerySource> for LabQuery
 where
     id_col: SelectableExpression<QuerySource>,
     name_col: SelectableExpression<QuerySource>,
+    Filter<LabFilterColumn>: model::AsDieselFilter<QuerySource>,
 {
     fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
     where
         QuerySource: 'a,
     {
-        let Self { ids, name, .. } = self;
+        let Self {
+            ids,
+            name,
+            similar_name,
+            filter,
+            ..
+        } = self;
 
         let mut query = BoxedDieselExpression::new_expression();
 
@@ -126,10 +141,29 @@ where
             query = query.and_condition(name_col.ilike(name.as_ilike()));
         }
 
+        if let Some(SimilaritySearch { text, .. }) = similar_name {
+            query = query.and_condition(name_col.trigram_similar_to(text));
+        }
+
+        if let Some(filter) = filter.as_diesel_filter() {
+            query = query.and_condition(filter);
+        }
+
         query.build()
     }
 }
 
+impl_as_diesel_condition!(
+    LabFilterColumn,
+    lab::table,
+    [
+        (Id, id_col),
+        (Name, name_col),
+        (PiId, pi_id_col),
+        (DeliveryDir, delivery_dir_col)
+    ]
+);
+
 impl AsDieselQueryBase for LabSummary {
     type QueryBase = lab::table;
 
@@ -144,10 +178,16 @@ impl model::FetchByQuery for LabSummary {
     async fn fetch_by_query(
         query: &Self::QueryParams,
         db_conn: &mut diesel_async::AsyncPgConnection,
-    ) -> crate::db::error::Result<Vec<Self>> {
+    ) -> crate::db::error::Result<Page<Self>> {
         use scamplers_core::model::lab::LabOrdinalColumn::Name;
 
-        fetch_by_query!(query, [(Name, name_col)], db_conn)
+        fetch_by_query!(
+            query,
+            [(Name, name_col)],
+            id_col,
+            db_conn,
+            ranked_by_similarity(similar_name, name_col)
+        )
     }
 }
 
@@ -208,11 +248,13 @@ impl model::FetchById for Lab {
 mod tests {
     use std::collections::HashSet;
 
-    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use diesel::prelude::*;
+    use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
     use pretty_assertions::assert_eq;
     use rstest::rstest;
     use scamplers_core::model::{
-        lab::{LabQuery, LabSummary, LabUpdate, LabUpdateWithMembers, NewLab},
+        filter::Filter,
+        lab::{LabFilterColumn, LabQuery, LabSummary, LabUpdate, LabUpdateWithMembers, NewLab},
         person::{PersonQuery, PersonSummary},
     };
     use scamplers_schema::lab;
@@ -221,9 +263,10 @@ mod tests {
         model::{FetchByQuery, FetchRelatives, Write},
         test_util::{DbConnection, N_LAB_MEMBERS, N_LABS, db_conn, test_query},
     };
+    use uuid::Uuid;
 
     fn comparison_fn(l: &LabSummary) -> String {
-        l.name().to_string()
+        l.name().clone()
     }
 
     #[rstest]
@@ -241,6 +284,48 @@ mod tests {
         .await;
     }
 
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn labs_named_or_led_by(#[future] mut db_conn: DbConnection) {
+        let pi_id: Uuid = lab::table
+            .filter(lab::name.eq("lab5"))
+            .select(lab::pi_id)
+            .first(&mut db_conn)
+            .await
+            .unwrap();
+
+        let query = LabQuery {
+            filter: Filter::Or(vec![
+                Filter::Eq {
+                    column: LabFilterColumn::Name,
+                    value: "lab3".to_string(),
+                },
+                Filter::Eq {
+                    column: LabFilterColumn::PiId,
+                    value: pi_id.to_string(),
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let labs = LabSummary::fetch_by_query(&query, &mut db_conn)
+            .await
+            .unwrap()
+            .results;
+        let names: HashSet<_> = labs.iter().map(|l| l.name().as_str()).collect();
+
+        let expected: Vec<String> = lab::table
+            .filter(lab::name.eq("lab3").or(lab::pi_id.eq(pi_id)))
+            .select(lab::name)
+            .load(&mut db_conn)
+            .await
+            .unwrap();
+
+        assert!(names.contains("lab3") && names.contains("lab5"));
+        assert_eq!(names, expected.iter().map(String::as_str).collect());
+    }
+
     #[rstest]
     #[awt]
     #[tokio::test]
@@ -251,6 +336,7 @@ mod tests {
                     let pi = PersonSummary::fetch_by_query(&PersonQuery::default(), conn)
                         .await
                         .unwrap()
+                        .results
                         .remove(0);
 
                     let new_lab = NewLab {
@@ -283,6 +369,7 @@ mod tests {
                     let lab = LabSummary::fetch_by_query(&LabQuery::default(), conn)
                         .await
                         .unwrap()
+                        .results
                         .swap_remove(1);
 
                     let original_members =
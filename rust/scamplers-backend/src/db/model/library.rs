@@ -0,0 +1,459 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    library::{Library, LibraryQuery, LibrarySummary, NewLibrary},
+    measurements::LibraryMeasurement,
+    person::PersonSummary,
+};
+use scamplers_schema::{
+    cdna,
+    chromium_library::{
+        self, cdna_id as cdna_id_col, dual_index_set_name as dual_index_set_name_col, id as id_col,
+        prepared_at as prepared_at_col, readable_id as readable_id_col,
+        single_index_set_name as single_index_set_name_col,
+    },
+    chromium_library_preparers::{
+        self, library_id as library_id_col, prepared_by as prepared_by_col,
+    },
+    dual_index_set, gems, library_type_specification, single_index_set,
+};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{Error, Result},
+        model::{self, AsDieselQueryBase, FetchById, FetchRelatives, WriteRelatives},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewLibrary {
+    type Returns = Library;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let index_set = IndexSet::fetch(&self.index_set_name, db_conn).await?;
+        check_index_kit(&self.cdna_id, &index_set, db_conn).await?;
+
+        let (single_index_set_name, dual_index_set_name) = index_set.names();
+
+        let id = diesel::insert_into(chromium_library::table)
+            .values((
+                &self,
+                single_index_set_name_col.eq(single_index_set_name),
+                dual_index_set_name_col.eq(dual_index_set_name),
+            ))
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        let preparers: Vec<_> = self
+            .preparer_ids
+            .iter()
+            .map(|p| (library_id_col.eq(id), prepared_by_col.eq(p)))
+            .collect();
+
+        diesel::insert_into(chromium_library_preparers::table)
+            .values(preparers)
+            .execute(db_conn)
+            .await?;
+
+        chromium_library::table::write_relatives(&id, self.measurements, db_conn).await?;
+
+        Library::fetch_by_id(&id, db_conn).await
+    }
+}
+
+struct IndexSet<'a> {
+    name: &'a str,
+    kit: String,
+    is_dual: bool,
+}
+
+impl<'a> IndexSet<'a> {
+    // Index set names are unique across kits, so a name identifies either a single or a dual index set
+    async fn fetch(name: &'a str, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let single_kit: Option<String> = single_index_set::table
+            .find(name)
+            .select(single_index_set::kit)
+            .first(db_conn)
+            .await
+            .optional()?;
+
+        if let Some(kit) = single_kit {
+            return Ok(Self {
+                name,
+                kit,
+                is_dual: false,
+            });
+        }
+
+        let dual_kit: Option<String> = dual_index_set::table
+            .find(name)
+            .select(dual_index_set::kit)
+            .first(db_conn)
+            .await
+            .optional()?;
+
+        let Some(kit) = dual_kit else {
+            return Err(Error::ReferenceNotFound {
+                entity: "chromium_library".to_string(),
+                referenced_entity: "index_set".to_string(),
+                value: Some(name.to_string()),
+            });
+        };
+
+        Ok(Self {
+            name,
+            kit,
+            is_dual: true,
+        })
+    }
+
+    fn names(&self) -> (Option<&str>, Option<&str>) {
+        if self.is_dual {
+            (None, Some(self.name))
+        } else {
+            (Some(self.name), None)
+        }
+    }
+}
+
+async fn check_index_kit(
+    cdna_id: &Uuid,
+    index_set: &IndexSet<'_>,
+    db_conn: &mut AsyncPgConnection,
+) -> Result<()> {
+    let violation = |message| Error::ConstraintViolation {
+        entity: "chromium_library".to_string(),
+        constraint: "index_kit_matches_library_type".to_string(),
+        message,
+    };
+
+    let cdna: Option<(Option<String>, String)> = cdna::table
+        .inner_join(gems::table)
+        .filter(cdna::id.eq(cdna_id))
+        .select((gems::chemistry, cdna::library_type))
+        .first(db_conn)
+        .await
+        .optional()?;
+
+    let Some((chemistry, library_type)) = cdna else {
+        return Err(Error::ReferenceNotFound {
+            entity: "chromium_library".to_string(),
+            referenced_entity: "cdna".to_string(),
+            value: Some(cdna_id.to_string()),
+        });
+    };
+
+    let Some(chemistry) = chemistry else {
+        return Err(violation(format!(
+            "the GEMs from which cDNA {cdna_id} was made has no chemistry, so index set {} cannot be \
+             validated",
+            index_set.name
+        )));
+    };
+
+    let index_kit: Option<String> = library_type_specification::table
+        .find((&chemistry, &library_type))
+        .select(library_type_specification::index_kit)
+        .first(db_conn)
+        .await
+        .optional()?;
+
+    let Some(index_kit) = index_kit else {
+        return Err(violation(format!(
+            "there is no library type specification for chemistry {chemistry} and library type \
+             {library_type}"
+        )));
+    };
+
+    if index_kit != index_set.kit {
+        return Err(violation(format!(
+            "index set {} belongs to kit {}, but {library_type} libraries made with chemistry \
+             {chemistry} use kit {index_kit}",
+            index_set.name, index_set.kit
+        )));
+    }
+
+    Ok(())
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for LibraryQuery
+where
+    id_col: SelectableExpression<QuerySource>,
+    readable_id_col: SelectableExpression<QuerySource>,
+    cdna_id_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            ids,
+            readable_id,
+            cdna_ids,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(readable_id) = readable_id {
+            query = query.and_condition(readable_id_col.ilike(readable_id.as_ilike()));
+        }
+
+        if !cdna_ids.is_empty() {
+            query = query.and_condition(cdna_id_col.eq_any(cdna_ids));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for LibrarySummary {
+    type QueryBase = chromium_library::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        chromium_library::table
+    }
+}
+
+impl model::FetchById for LibrarySummary {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        Ok(Self::as_diesel_query_base()
+            .find(id)
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+impl model::FetchByQuery for LibrarySummary {
+    type QueryParams = LibraryQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::library::LibraryOrdinalColumn::{PreparedAt, ReadableId};
+
+        fetch_by_query!(
+            query,
+            [(ReadableId, readable_id_col), (PreparedAt, prepared_at_col)],
+            id_col,
+            db_conn
+        )
+    }
+}
+
+impl model::FetchRelatives<PersonSummary> for chromium_library::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        library_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<PersonSummary>> {
+        let preparers = chromium_library_preparers::table
+            .filter(library_id_col.eq(library_id))
+            .inner_join(PersonSummary::as_diesel_query_base())
+            .select(PersonSummary::as_select())
+            .load(db_conn)
+            .await?;
+
+        Ok(preparers)
+    }
+}
+
+impl model::FetchById for Library {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let summary = LibrarySummary::fetch_by_id(id, db_conn).await?;
+        let preparers =
+            <chromium_library::table as FetchRelatives<PersonSummary>>::fetch_relatives(
+                id, db_conn,
+            )
+            .await?;
+        let measurements =
+            <chromium_library::table as FetchRelatives<LibraryMeasurement>>::fetch_relatives(
+                id, db_conn,
+            )
+            .await?;
+
+        Ok(Self::new(summary, preparers, measurements))
+    }
+}
+
+impl model::FetchByReadableId for Library {
+    async fn fetch_by_readable_id(
+        readable_id: &str,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Self> {
+        let id = chromium_library::table
+            .select(id_col)
+            .filter(readable_id_col.eq(readable_id))
+            .get_result(db_conn)
+            .await?;
+
+        Self::fetch_by_id(&id, db_conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::prelude::*;
+    use diesel_async::{
+        AsyncConnection, AsyncPgConnection, RunQueryDsl, scoped_futures::ScopedFutureExt,
+    };
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        library::{LibraryQuery, LibrarySummary, NewLibrary},
+        person::{PersonQuery, PersonSummary},
+    };
+    use scamplers_schema::cdna;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, Write},
+        test_util::{
+            DbConnection, N_LIBRARIES, TEST_DUAL_INDEX_SET, TEST_SINGLE_INDEX_SET, db_conn,
+            test_query,
+        },
+    };
+
+    fn comparison_fn(l: &LibrarySummary) -> String {
+        l.readable_id().clone()
+    }
+
+    async fn new_library(index_set_name: &str, db_conn: &mut AsyncPgConnection) -> NewLibrary {
+        let cdna_id: Uuid = cdna::table
+            .filter(cdna::readable_id.eq("cdna0"))
+            .select(cdna::id)
+            .first(db_conn)
+            .await
+            .unwrap();
+
+        let person = PersonSummary::fetch_by_query(&PersonQuery::default(), db_conn)
+            .await
+            .unwrap()
+            .results
+            .remove(0);
+
+        NewLibrary {
+            readable_id: "LIB-new".to_string(),
+            cdna_id,
+            index_set_name: index_set_name.to_string(),
+            number_of_sample_index_pcr_cycles: 12,
+            target_reads_per_cell: 50_000,
+            prepared_at: OffsetDateTime::now_utc(),
+            notes: None,
+            preparer_ids: vec![*person.id()],
+            measurements: vec![],
+        }
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn default_library_query(#[future] db_conn: DbConnection) {
+        let expected = [(0, "library0")];
+        test_query(
+            LibraryQuery::default(),
+            db_conn,
+            N_LIBRARIES,
+            comparison_fn,
+            &expected,
+        )
+        .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn index_set_is_resolved(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let library = new_library(TEST_DUAL_INDEX_SET, tx)
+                        .await
+                        .write(tx)
+                        .await
+                        .unwrap();
+
+                    assert_eq!(
+                        library.dual_index_set_name().as_deref(),
+                        Some(TEST_DUAL_INDEX_SET)
+                    );
+                    assert_eq!(*library.single_index_set_name(), None);
+                    assert_eq!(library.preparers().len(), 1);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn index_kit_must_match_library_type(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let err = new_library(TEST_SINGLE_INDEX_SET, tx)
+                        .await
+                        .write(tx)
+                        .await
+                        .unwrap_err();
+
+                    let Error::ConstraintViolation { constraint, .. } = err else {
+                        panic!("expected constraint violation, found {err:?}");
+                    };
+                    assert_eq!(constraint, "index_kit_matches_library_type");
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn index_set_must_exist(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let err = new_library("SI-TT-H12", tx)
+                        .await
+                        .write(tx)
+                        .await
+                        .unwrap_err();
+
+                    let Error::ReferenceNotFound {
+                        referenced_entity, ..
+                    } = err
+                    else {
+                        panic!("expected missing reference, found {err:?}");
+                    };
+                    assert_eq!(referenced_entity, "index_set");
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
@@ -0,0 +1,243 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    library_type_specification::{
+        LibraryTypeSpecification, LibraryTypeSpecificationQuery, LibraryTypeSpecificationUpdate,
+        NewLibraryTypeSpecification,
+    },
+};
+use scamplers_schema::library_type_specification::{
+    self, chemistry as chemistry_col, index_kit as index_kit_col, library_type as library_type_col,
+};
+
+use crate::{
+    db::{
+        error::Result,
+        model::{self, AsDieselQueryBase, FetchById},
+        util::{BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+// Only app admins are granted write access to this table, so the database rejects writes from anyone else
+impl model::Write for NewLibraryTypeSpecification {
+    type Returns = LibraryTypeSpecification;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let specification = diesel::insert_into(library_type_specification::table)
+            .values(self)
+            .returning(LibraryTypeSpecification::as_returning())
+            .get_result(db_conn)
+            .await?;
+
+        Ok(specification)
+    }
+}
+
+impl model::Write for LibraryTypeSpecificationUpdate {
+    type Returns = LibraryTypeSpecification;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let id = (self.chemistry.clone(), self.library_type.clone());
+
+        if let Self {
+            index_kit: None,
+            cdna_volume_ul: None,
+            library_volume_ul: None,
+            ..
+        } = &self
+        {
+            return LibraryTypeSpecification::fetch_by_id(&id, db_conn).await;
+        }
+
+        let specification = diesel::update(&self)
+            .set(&self)
+            .returning(LibraryTypeSpecification::as_returning())
+            .get_result(db_conn)
+            .await?;
+
+        Ok(specification)
+    }
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for LibraryTypeSpecificationQuery
+where
+    chemistry_col: SelectableExpression<QuerySource>,
+    library_type_col: SelectableExpression<QuerySource>,
+    index_kit_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            chemistries,
+            library_types,
+            index_kits,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !chemistries.is_empty() {
+            query = query.and_condition(chemistry_col.eq_any(chemistries));
+        }
+
+        if !library_types.is_empty() {
+            query = query.and_condition(library_type_col.eq_any(library_types));
+        }
+
+        if !index_kits.is_empty() {
+            query = query.and_condition(index_kit_col.eq_any(index_kits));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for LibraryTypeSpecification {
+    type QueryBase = library_type_specification::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        library_type_specification::table
+    }
+}
+
+impl model::FetchByQuery for LibraryTypeSpecification {
+    type QueryParams = LibraryTypeSpecificationQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::library_type_specification::LibraryTypeSpecificationOrdinalColumn::{
+            Chemistry, IndexKit, LibraryType,
+        };
+
+        fetch_by_query!(
+            query,
+            [
+                (Chemistry, chemistry_col),
+                (LibraryType, library_type_col),
+                (IndexKit, index_kit_col)
+            ],
+            db_conn
+        )
+    }
+}
+
+/// Looks up a specification by `(chemistry, library_type)`
+impl model::FetchById for LibraryTypeSpecification {
+    type Id = (String, String);
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let (chemistry, library_type) = id;
+
+        Ok(Self::as_diesel_query_base()
+            .find((chemistry, library_type))
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::sql_query;
+    use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::library_type_specification::{
+        LibraryTypeSpecification, LibraryTypeSpecificationUpdate, NewLibraryTypeSpecification,
+    };
+
+    use crate::db::{
+        error::Error,
+        model::{FetchById, Write},
+        test_util::{DbConnection, TEST_CHEMISTRY, db_conn},
+    };
+
+    fn new_specification() -> NewLibraryTypeSpecification {
+        NewLibraryTypeSpecification {
+            chemistry: TEST_CHEMISTRY.to_string(),
+            library_type: "Antibody Capture".to_string(),
+            index_kit: "GA".to_string(),
+            cdna_volume_ul: 5.0,
+            library_volume_ul: 35.0,
+        }
+    }
+
+    #[test]
+    fn volumes_cannot_be_negative() {
+        let mut specification = new_specification();
+        specification.validate().unwrap();
+
+        specification.cdna_volume_ul = -1.0;
+        specification.validate().unwrap_err();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn look_up_specification(#[future] mut db_conn: DbConnection) {
+        let id = (TEST_CHEMISTRY.to_string(), "Gene Expression".to_string());
+
+        let specification = LibraryTypeSpecification::fetch_by_id(&id, &mut db_conn)
+            .await
+            .unwrap();
+
+        assert_eq!(specification.index_kit(), "TT");
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn admin_can_manage_specifications(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    sql_query("set local role app_admin").execute(tx).await?;
+
+                    new_specification().write(tx).await.unwrap();
+
+                    let update = LibraryTypeSpecificationUpdate {
+                        chemistry: TEST_CHEMISTRY.to_string(),
+                        library_type: "Antibody Capture".to_string(),
+                        library_volume_ul: Some(40.0),
+                        ..Default::default()
+                    };
+                    let updated = update.write(tx).await.unwrap();
+
+                    assert_eq!(updated.index_kit(), "GA");
+                    assert!((updated.library_volume_ul() - 40.0).abs() < f32::EPSILON);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn only_admin_can_create_specifications(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    sql_query("set local role biology_staff")
+                        .execute(tx)
+                        .await?;
+
+                    let error = new_specification().write(tx).await.unwrap_err();
+                    assert!(matches!(error, Error::PermissionDenied { .. }));
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
@@ -0,0 +1,280 @@
+use diesel::{prelude::*, sql_types};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    lineage::{DatasetProvenance, LineageEdge, LineageNode, LineageNodeType, SampleLineage},
+    sample_metadata::CommitteeApproval,
+};
+use scamplers_schema::committee_approval;
+use uuid::Uuid;
+
+use crate::db::{
+    error::{Error, Result},
+    model::{self, AsDieselQueryBase},
+};
+
+// The `lineage_edge` view lists every step from a sample to the things made from it, so the whole graph on either side
+// of a record can be walked in one recursive query
+const DOWNSTREAM_EDGES: &str = "
+    with recursive lineage as (
+        select parent_type, parent_id, child_type, child_id from lineage_edge
+        where parent_type = $1 and parent_id = $2
+        union
+        select e.parent_type, e.parent_id, e.child_type, e.child_id from lineage_edge e
+        inner join lineage l on e.parent_type = l.child_type and e.parent_id = l.child_id
+    )
+    select parent_id, child_id from lineage";
+
+const UPSTREAM_EDGES: &str = "
+    with recursive lineage as (
+        select parent_type, parent_id, child_type, child_id from lineage_edge
+        where child_type = $1 and child_id = $2
+        union
+        select e.parent_type, e.parent_id, e.child_type, e.child_id from lineage_edge e
+        inner join lineage l on e.child_type = l.parent_type and e.child_id = l.parent_id
+    )
+    select parent_id, child_id from lineage";
+
+async fn fetch_lineage(
+    edges_query: &str,
+    root_type: LineageNodeType,
+    root_id: &Uuid,
+    db_conn: &mut AsyncPgConnection,
+) -> Result<(Vec<LineageNode>, Vec<LineageEdge>, Vec<CommitteeApproval>)> {
+    let edges: Vec<LineageEdge> = diesel::sql_query(edges_query)
+        .bind::<sql_types::Text, _>(root_type)
+        .bind::<sql_types::Uuid, _>(root_id)
+        .load(db_conn)
+        .await?;
+
+    let mut ids: Vec<_> = edges
+        .iter()
+        .flat_map(|e| [*e.parent_id(), *e.child_id()])
+        .chain([*root_id])
+        .collect();
+    ids.sort();
+    ids.dedup();
+
+    let nodes: Vec<LineageNode> = diesel::sql_query(
+        "select type as type_, id, label from lineage_node where id = any($1) order by id",
+    )
+    .bind::<sql_types::Array<sql_types::Uuid>, _>(&ids)
+    .load(db_conn)
+    .await?;
+
+    if !nodes
+        .iter()
+        .any(|n| *n.type_() == root_type && n.id() == root_id)
+    {
+        return Err(Error::RecordNotFound);
+    }
+
+    let sample_ids: Vec<_> = nodes
+        .iter()
+        .filter(|n| *n.type_() == LineageNodeType::Sample)
+        .map(|n| *n.id())
+        .collect();
+
+    let committee_approvals = CommitteeApproval::as_diesel_query_base()
+        .filter(committee_approval::sample_id.eq_any(&sample_ids))
+        .select(CommitteeApproval::as_select())
+        .load(db_conn)
+        .await?;
+
+    Ok((nodes, edges, committee_approvals))
+}
+
+impl model::FetchById for SampleLineage {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let (nodes, edges, committee_approvals) =
+            fetch_lineage(DOWNSTREAM_EDGES, LineageNodeType::Sample, id, db_conn).await?;
+
+        Ok(Self::new(nodes, edges, committee_approvals))
+    }
+}
+
+impl model::FetchById for DatasetProvenance {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let (nodes, edges, committee_approvals) =
+            fetch_lineage(UPSTREAM_EDGES, LineageNodeType::Dataset, id, db_conn).await?;
+
+        Ok(Self::new(nodes, edges, committee_approvals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::prelude::*;
+    use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        dataset_metadata::NewChromiumDataset,
+        institution::{InstitutionQuery, InstitutionSummary},
+        lab::{LabQuery, LabSummary},
+        lineage::{DatasetProvenance, LineageNode, LineageNodeType, SampleLineage},
+        sample_metadata::{
+            ComplianceCommitteeType, NewCommitteeApproval, SampleQuery, SampleSummary,
+        },
+    };
+    use scamplers_schema::{gems, sample_metadata};
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchById, FetchByQuery, Write, WriteRelatives},
+        test_util::{DbConnection, db_conn},
+    };
+
+    fn labels(nodes: &[LineageNode]) -> Vec<&str> {
+        let mut labels: Vec<_> = nodes.iter().map(|n| n.label().as_str()).collect();
+        labels.sort_unstable();
+
+        labels
+    }
+
+    // sample0's cells were loaded into gems0, from which cdna0 and cdna2 were made. Only cdna0 was made into a library.
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn sample_lineage(#[future] mut db_conn: DbConnection) {
+        let query = SampleQuery {
+            name: Some("sample0".to_string()),
+            ..Default::default()
+        };
+        let sample = SampleSummary::fetch_by_query(&query, &mut db_conn)
+            .await
+            .unwrap()
+            .results
+            .remove(0);
+
+        let lineage = SampleLineage::fetch_by_id(sample.id(), &mut db_conn)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            labels(lineage.nodes()),
+            [
+                "cdna0",
+                "cdna2",
+                "gems0",
+                "library0",
+                "sample0",
+                "sequencing_run0",
+                "specimen0",
+                "suspension0"
+            ]
+        );
+        assert_eq!(lineage.edges().len(), 7);
+        assert!(lineage.committee_approvals().is_empty());
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn dataset_provenance(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let sample_id: Uuid = sample_metadata::table
+                        .filter(sample_metadata::name.eq("sample1"))
+                        .select(sample_metadata::id)
+                        .first(tx)
+                        .await
+                        .unwrap();
+
+                    let institution =
+                        InstitutionSummary::fetch_by_query(&InstitutionQuery::default(), tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .remove(0);
+
+                    sample_metadata::table::write_relatives(
+                        &sample_id,
+                        vec![NewCommitteeApproval {
+                            sample_id: None,
+                            institution_id: *institution.id(),
+                            committee_type: ComplianceCommitteeType::Irb,
+                            compliance_identifier: "irb-1".to_string(),
+                            approved_at: None,
+                            expires_at: None,
+                        }],
+                        tx,
+                    )
+                    .await
+                    .unwrap();
+
+                    let lab = LabSummary::fetch_by_query(&LabQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+                    let gems_id = gems::table
+                        .filter(gems::readable_id.eq("gems1"))
+                        .select(gems::id)
+                        .first(tx)
+                        .await
+                        .unwrap();
+
+                    let dataset = NewChromiumDataset {
+                        name: "dataset0".to_string(),
+                        lab_id: *lab.id(),
+                        data_path: "/datasets/dataset0".to_string(),
+                        delivered_at: OffsetDateTime::now_utc(),
+                        gems_id,
+                        web_summary: "<!DOCTYPE html><html><head><title>Web Summary</title></head>\
+                                      <body></body></html>"
+                            .to_string(),
+                        metrics_summary: "Estimated Number of Cells,Mean Reads per Cell,Median \
+                                          Genes per Cell,Sequencing \
+                                          Saturation\n\"5,123\",\"45,678\",\"2,345\",62.5%\n"
+                            .to_string(),
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap();
+
+                    let provenance = DatasetProvenance::fetch_by_id(dataset.id(), tx)
+                        .await
+                        .unwrap();
+
+                    assert_eq!(
+                        labels(provenance.nodes()),
+                        ["dataset0", "gems1", "sample1", "specimen1", "suspension1"]
+                    );
+                    assert_eq!(provenance.edges().len(), 4);
+
+                    let sample = provenance
+                        .nodes()
+                        .iter()
+                        .find(|n| *n.type_() == LineageNodeType::Sample)
+                        .unwrap();
+                    assert_eq!(*sample.id(), sample_id);
+
+                    let approvals = provenance.committee_approvals();
+                    assert_eq!(approvals.len(), 1);
+                    assert_eq!(approvals[0].compliance_identifier(), "irb-1");
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn lineage_of_missing_sample(#[future] mut db_conn: DbConnection) {
+        let error = SampleLineage::fetch_by_id(&Uuid::now_v7(), &mut db_conn)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::RecordNotFound));
+    }
+}
@@ -1 +1,253 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::measurements::{
+    CdnaMeasurement, LibraryMeasurement, MultiplexedSuspensionMeasurement, NewCdnaMeasurement,
+    NewLibraryMeasurement, NewMultiplexedSuspensionMeasurement, NewSpecimenMeasurement,
+    NewSuspensionMeasurement, SpecimenMeasurement, SuspensionMeasurement,
+};
+use scamplers_schema::{
+    cdna, cdna_measurement, chromium_library, chromium_library_measurement, multiplexed_suspension,
+    multiplexed_suspension_measurement, specimen, specimen_measurement, suspension,
+    suspension_measurement,
+};
+use uuid::Uuid;
 
+use crate::db::{error::Result, model};
+
+// Every `*_measurement` table has the same shape, differing only in the name of the column referencing its parent
+macro_rules! impl_measurements {
+    ($parent_table:ident, $measurement_table:ident, $parent_id_col:ident, $new:ident, $read:ident) => {
+        impl model::WriteRelatives<$new> for $parent_table::table {
+            type Id = Uuid;
+            type Returns = $read;
+
+            async fn write_relatives(
+                parent_id: &Self::Id,
+                mut measurements: Vec<$new>,
+                db_conn: &mut AsyncPgConnection,
+            ) -> Result<Vec<Self::Returns>> {
+                for measurement in &mut measurements {
+                    measurement.$parent_id_col = Some(*parent_id);
+                }
+
+                let measurements = diesel::insert_into($measurement_table::table)
+                    .values(&measurements)
+                    .returning($read::as_returning())
+                    .get_results(db_conn)
+                    .await?;
+
+                Ok(measurements)
+            }
+        }
+
+        impl model::FetchRelatives<$read> for $parent_table::table {
+            type Id = Uuid;
+
+            async fn fetch_relatives(
+                parent_id: &Self::Id,
+                db_conn: &mut AsyncPgConnection,
+            ) -> Result<Vec<$read>> {
+                let measurements = $measurement_table::table
+                    .filter($measurement_table::$parent_id_col.eq(parent_id))
+                    .select($read::as_select())
+                    .order_by($measurement_table::id)
+                    .load(db_conn)
+                    .await?;
+
+                Ok(measurements)
+            }
+        }
+    };
+}
+
+impl_measurements!(
+    specimen,
+    specimen_measurement,
+    specimen_id,
+    NewSpecimenMeasurement,
+    SpecimenMeasurement
+);
+impl_measurements!(
+    suspension,
+    suspension_measurement,
+    suspension_id,
+    NewSuspensionMeasurement,
+    SuspensionMeasurement
+);
+impl_measurements!(
+    multiplexed_suspension,
+    multiplexed_suspension_measurement,
+    suspension_id,
+    NewMultiplexedSuspensionMeasurement,
+    MultiplexedSuspensionMeasurement
+);
+impl_measurements!(
+    cdna,
+    cdna_measurement,
+    cdna_id,
+    NewCdnaMeasurement,
+    CdnaMeasurement
+);
+impl_measurements!(
+    chromium_library,
+    chromium_library_measurement,
+    library_id,
+    NewLibraryMeasurement,
+    LibraryMeasurement
+);
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        measurements::{Measurement, MeasurementData, NewSpecimenMeasurement},
+        person::{PersonQuery, PersonSummary},
+        specimen::{SpecimenQuery, SpecimenSummary},
+        units::{Quantity, Unit},
+    };
+    use scamplers_schema::specimen;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, FetchRelatives, WriteRelatives},
+        test_util::{DbConnection, db_conn},
+    };
+
+    fn measurement_data(measurement: Measurement) -> MeasurementData {
+        MeasurementData {
+            measured_at: "2025-06-17T12:30:00Z".parse().unwrap(),
+            instrument_name: Some("TapeStation".to_string()),
+            measurement,
+        }
+    }
+
+    #[test]
+    fn quantity_is_flattened_into_measurement() {
+        let json = serde_json::json!({
+            "measured_at": "2025-06-17T12:30:00Z",
+            "quantity": "volume",
+            "value": 0.5,
+            "unit": "ml"
+        });
+
+        let data: MeasurementData = serde_json::from_value(json.clone()).unwrap();
+        let Measurement::Volume { volume } = data.measurement else {
+            panic!("expected volume, found {:?}", data.measurement);
+        };
+        assert_eq!(
+            volume.convert_to(Unit::Microliter).unwrap(),
+            Quantity::new(500.0, Unit::Microliter)
+        );
+
+        assert_eq!(
+            serde_json::to_value(&data).unwrap(),
+            serde_json::json!({
+                "measured_at": "2025-06-17T12:30:00Z",
+                "instrument_name": null,
+                "quantity": "volume",
+                "value": 0.5,
+                "unit": "ml"
+            })
+        );
+    }
+
+    #[test]
+    fn measurement_json_is_tagged() {
+        let data = measurement_data(Measurement::Rin { value: 8.5 });
+        let json = serde_json::to_value(&data).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "measured_at": "2025-06-17T12:30:00Z",
+                "instrument_name": "TapeStation",
+                "quantity": "rin",
+                "value": 8.5
+            })
+        );
+
+        let round_tripped: MeasurementData = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            serde_json::to_value(round_tripped).unwrap(),
+            serde_json::to_value(data).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(Measurement::Viability { value: 101.0 })]
+    #[case(Measurement::Rin { value: 0.5 })]
+    #[case(Measurement::CellConcentration { concentration: Quantity::new(-1.0, Unit::CellsPerMicroliter) })]
+    #[case(Measurement::Volume { volume: Quantity::new(10.0, Unit::Nanomolar) })]
+    fn invalid_measurement(#[case] measurement: Measurement) {
+        let new_measurement = NewSpecimenMeasurement {
+            specimen_id: None,
+            measured_by: Uuid::default(),
+            data: measurement_data(measurement),
+        };
+
+        new_measurement.validate().unwrap_err();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn specimen_measurements(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let specimen = SpecimenSummary::fetch_by_query(&SpecimenQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    let measurer = PersonSummary::fetch_by_query(&PersonQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    let new_measurements = [
+                        Measurement::Rin { value: 8.5 },
+                        Measurement::Dv200 { value: 72.0 },
+                    ]
+                    .map(|m| NewSpecimenMeasurement {
+                        specimen_id: None,
+                        measured_by: *measurer.id(),
+                        data: measurement_data(m),
+                    });
+
+                    let written = specimen::table::write_relatives(
+                        specimen.id(),
+                        new_measurements.into(),
+                        tx,
+                    )
+                    .await
+                    .unwrap();
+                    assert_eq!(written.len(), 2);
+
+                    let fetched = specimen::table::fetch_relatives(specimen.id(), tx)
+                        .await
+                        .unwrap();
+                    assert_eq!(fetched.len(), 2);
+
+                    for measurement in &fetched {
+                        assert_eq!(measurement.specimen_id(), specimen.id());
+                    }
+
+                    assert!(fetched.iter().any(|m| matches!(
+                        m.data().measurement,
+                        Measurement::Rin { value } if (value - 8.5).abs() < f32::EPSILON
+                    )));
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
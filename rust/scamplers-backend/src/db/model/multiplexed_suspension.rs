@@ -0,0 +1,499 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    measurements::MultiplexedSuspensionMeasurement,
+    multiplexed_suspension::{
+        MultiplexedSuspension, MultiplexedSuspensionQuery, MultiplexedSuspensionSummary,
+        MultiplexingTag, MultiplexingTagQuery, NewMultiplexedSuspension, PooledSuspension,
+    },
+    person::PersonSummary,
+    suspension::SuspensionSummary,
+};
+use scamplers_schema::{
+    multiplexed_suspension::{
+        self, id as id_col, name as name_col, pooled_at as pooled_at_col,
+        readable_id as readable_id_col,
+    },
+    multiplexed_suspension_preparers::{
+        self, prepared_by as prepared_by_col, suspension_id as suspension_id_col,
+    },
+    multiplexing_tag::{
+        self, id as multiplexing_tag_id_col, tag_id as tag_id_col, type_ as tag_type_col,
+    },
+    suspension,
+};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::{Error, Result},
+        model::{self, AsDieselQueryBase, FetchById, FetchRelatives, WriteRelatives},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewMultiplexedSuspension {
+    type Returns = MultiplexedSuspension;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let id = diesel::insert_into(multiplexed_suspension::table)
+            .values(&self)
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        let preparers: Vec<_> = self
+            .preparer_ids
+            .iter()
+            .map(|p| (suspension_id_col.eq(id), prepared_by_col.eq(p)))
+            .collect();
+
+        diesel::insert_into(multiplexed_suspension_preparers::table)
+            .values(preparers)
+            .execute(db_conn)
+            .await?;
+
+        pool_suspensions(&id, &self.suspensions, db_conn).await?;
+
+        multiplexed_suspension::table::write_relatives(&id, self.measurements, db_conn).await?;
+
+        MultiplexedSuspension::fetch_by_id(&id, db_conn).await
+    }
+}
+
+async fn pool_suspensions(
+    pool_id: &Uuid,
+    suspensions: &[PooledSuspension],
+    db_conn: &mut AsyncPgConnection,
+) -> Result<()> {
+    let suspension_ids: Vec<_> = suspensions.iter().map(|s| s.suspension_id).collect();
+
+    let existing: Vec<(Uuid, Option<Uuid>)> = suspension::table
+        .filter(suspension::id.eq_any(&suspension_ids))
+        .select((suspension::id, suspension::pooled_into_id))
+        .load(db_conn)
+        .await?;
+
+    // Check everything up front so that the client learns exactly which suspension is the problem, rather than getting
+    // a foreign key error or silently moving a suspension from one pool to another
+    for suspension_id in &suspension_ids {
+        let Some((_, pooled_into_id)) = existing.iter().find(|(id, _)| id == suspension_id) else {
+            return Err(Error::ReferenceNotFound {
+                entity: "multiplexed_suspension".to_string(),
+                referenced_entity: "suspension".to_string(),
+                value: Some(suspension_id.to_string()),
+            });
+        };
+
+        if let Some(pooled_into_id) = pooled_into_id {
+            return Err(already_pooled(suspension_id, Some(pooled_into_id)));
+        }
+    }
+
+    for PooledSuspension {
+        suspension_id,
+        multiplexing_tag_id,
+    } in suspensions
+    {
+        // The check above doesn't lock anything, so a concurrent request may have pooled this suspension since
+        let n_updated = diesel::update(suspension::table.find(suspension_id))
+            .filter(suspension::pooled_into_id.is_null())
+            .set((
+                suspension::pooled_into_id.eq(pool_id),
+                suspension::multiplexing_tag_id.eq(multiplexing_tag_id),
+            ))
+            .execute(db_conn)
+            .await?;
+
+        if n_updated == 0 {
+            return Err(already_pooled(suspension_id, None));
+        }
+    }
+
+    Ok(())
+}
+
+fn already_pooled(suspension_id: &Uuid, pooled_into_id: Option<&Uuid>) -> Error {
+    let message = match pooled_into_id {
+        Some(pooled_into_id) => format!(
+            "suspension {suspension_id} has already been pooled into multiplexed suspension \
+             {pooled_into_id}"
+        ),
+        None => format!("suspension {suspension_id} has already been pooled"),
+    };
+
+    Error::ConstraintViolation {
+        entity: "suspension".to_string(),
+        constraint: "pooled_once".to_string(),
+        message,
+    }
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for MultiplexedSuspensionQuery
+where
+    id_col: SelectableExpression<QuerySource>,
+    name_col: SelectableExpression<QuerySource>,
+    readable_id_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            ids,
+            name,
+            readable_id,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(name) = name {
+            query = query.and_condition(name_col.ilike(name.as_ilike()));
+        }
+
+        if let Some(readable_id) = readable_id {
+            query = query.and_condition(readable_id_col.ilike(readable_id.as_ilike()));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for MultiplexedSuspensionSummary {
+    type QueryBase = multiplexed_suspension::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        multiplexed_suspension::table
+    }
+}
+
+impl model::FetchById for MultiplexedSuspensionSummary {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        Ok(Self::as_diesel_query_base()
+            .find(id)
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+impl model::FetchByQuery for MultiplexedSuspensionSummary {
+    type QueryParams = MultiplexedSuspensionQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::multiplexed_suspension::MultiplexedSuspensionOrdinalColumn::{
+            Name, PooledAt, ReadableId,
+        };
+
+        fetch_by_query!(
+            query,
+            [
+                (ReadableId, readable_id_col),
+                (Name, name_col),
+                (PooledAt, pooled_at_col)
+            ],
+            id_col,
+            db_conn
+        )
+    }
+}
+
+impl model::FetchRelatives<SuspensionSummary> for multiplexed_suspension::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        pool_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<SuspensionSummary>> {
+        let suspensions = SuspensionSummary::as_diesel_query_base()
+            .filter(suspension::pooled_into_id.eq(pool_id))
+            .select(SuspensionSummary::as_select())
+            .order_by(suspension::readable_id)
+            .load(db_conn)
+            .await?;
+
+        Ok(suspensions)
+    }
+}
+
+impl model::FetchRelatives<PersonSummary> for multiplexed_suspension::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        pool_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<PersonSummary>> {
+        let preparers = multiplexed_suspension_preparers::table
+            .filter(suspension_id_col.eq(pool_id))
+            .inner_join(PersonSummary::as_diesel_query_base())
+            .select(PersonSummary::as_select())
+            .load(db_conn)
+            .await?;
+
+        Ok(preparers)
+    }
+}
+
+impl model::FetchById for MultiplexedSuspension {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let summary = MultiplexedSuspensionSummary::fetch_by_id(id, db_conn).await?;
+        let suspensions =
+            <multiplexed_suspension::table as FetchRelatives<SuspensionSummary>>::fetch_relatives(
+                id, db_conn,
+            )
+            .await?;
+        let preparers =
+            <multiplexed_suspension::table as FetchRelatives<PersonSummary>>::fetch_relatives(
+                id, db_conn,
+            )
+            .await?;
+        let measurements = <multiplexed_suspension::table as FetchRelatives<
+            MultiplexedSuspensionMeasurement,
+        >>::fetch_relatives(id, db_conn)
+        .await?;
+
+        Ok(Self::new(summary, suspensions, preparers, measurements))
+    }
+}
+
+impl model::FetchByReadableId for MultiplexedSuspension {
+    async fn fetch_by_readable_id(
+        readable_id: &str,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Self> {
+        let id = multiplexed_suspension::table
+            .select(id_col)
+            .filter(readable_id_col.eq(readable_id))
+            .get_result(db_conn)
+            .await?;
+
+        Self::fetch_by_id(&id, db_conn).await
+    }
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for MultiplexingTagQuery
+where
+    tag_id_col: SelectableExpression<QuerySource>,
+    tag_type_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self { tag_id, type_, .. } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if let Some(tag_id) = tag_id {
+            query = query.and_condition(tag_id_col.ilike(tag_id.as_ilike()));
+        }
+
+        if let Some(type_) = type_ {
+            query = query.and_condition(tag_type_col.eq(type_));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for MultiplexingTag {
+    type QueryBase = multiplexing_tag::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        multiplexing_tag::table
+    }
+}
+
+impl model::FetchByQuery for MultiplexingTag {
+    type QueryParams = MultiplexingTagQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::multiplexed_suspension::MultiplexingTagOrdinalColumn::TagId;
+
+        fetch_by_query!(
+            query,
+            [(TagId, tag_id_col)],
+            multiplexing_tag_id_col,
+            db_conn
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        multiplexed_suspension::{
+            MultiplexingTag, MultiplexingTagQuery, MultiplexingTagType, NewMultiplexedSuspension,
+            PooledSuspension,
+        },
+        person::{PersonQuery, PersonSummary},
+        suspension::{SuspensionQuery, SuspensionSummary},
+    };
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, Write},
+        test_util::{DbConnection, db_conn, test_query},
+    };
+
+    fn new_pool(suspensions: Vec<PooledSuspension>, preparer_id: Uuid) -> NewMultiplexedSuspension {
+        NewMultiplexedSuspension {
+            name: "pool".to_string(),
+            readable_id: "POOL-new".to_string(),
+            pooled_at: OffsetDateTime::now_utc(),
+            notes: None,
+            suspensions,
+            preparer_ids: vec![preparer_id],
+            measurements: vec![],
+        }
+    }
+
+    #[test]
+    fn reject_ambiguous_pool() {
+        let (suspension1, suspension2) = (Uuid::now_v7(), Uuid::now_v7());
+        let (tag1, tag2) = (Uuid::now_v7(), Uuid::now_v7());
+
+        let pooled = |suspension_id, multiplexing_tag_id| PooledSuspension {
+            suspension_id,
+            multiplexing_tag_id,
+        };
+
+        new_pool(
+            vec![pooled(suspension1, tag1), pooled(suspension2, tag2)],
+            Uuid::default(),
+        )
+        .validate()
+        .unwrap();
+
+        new_pool(
+            vec![pooled(suspension1, tag1), pooled(suspension2, tag1)],
+            Uuid::default(),
+        )
+        .validate()
+        .unwrap_err();
+
+        new_pool(
+            vec![pooled(suspension1, tag1), pooled(suspension1, tag2)],
+            Uuid::default(),
+        )
+        .validate()
+        .unwrap_err();
+
+        new_pool(vec![], Uuid::default()).validate().unwrap_err();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn multiplexing_tag_catalog(#[future] db_conn: DbConnection) {
+        let query = MultiplexingTagQuery {
+            type_: Some(MultiplexingTagType::CellPlex),
+            ..Default::default()
+        };
+
+        let expected = [(0, "CMO301"), (11, "CMO312")];
+        test_query(
+            query,
+            db_conn,
+            12,
+            |t: &MultiplexingTag| t.tag_id().clone(),
+            &expected,
+        )
+        .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn pool_suspensions(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let suspensions =
+                        SuspensionSummary::fetch_by_query(&SuspensionQuery::default(), tx)
+                            .await
+                            .unwrap()
+                            .results;
+
+                    let tags = MultiplexingTag::fetch_by_query(
+                        &MultiplexingTagQuery {
+                            type_: Some(MultiplexingTagType::TotalSeqA),
+                            ..Default::default()
+                        },
+                        tx,
+                    )
+                    .await
+                    .unwrap()
+                    .results;
+
+                    let preparer = PersonSummary::fetch_by_query(&PersonQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    let to_pool: Vec<_> = suspensions
+                        .iter()
+                        .zip(&tags)
+                        .take(3)
+                        .map(|(s, t)| PooledSuspension {
+                            suspension_id: *s.id(),
+                            multiplexing_tag_id: *t.id(),
+                        })
+                        .collect();
+
+                    let pool = new_pool(to_pool, *preparer.id()).write(tx).await.unwrap();
+
+                    assert_eq!(pool.suspensions().len(), 3);
+                    assert!(
+                        pool.suspensions()
+                            .iter()
+                            .all(|s| s.pooled_into_id() == &Some(*pool.id()))
+                    );
+
+                    // The first suspension is already in the pool we just made
+                    let repooled = new_pool(
+                        vec![PooledSuspension {
+                            suspension_id: *suspensions[0].id(),
+                            multiplexing_tag_id: *tags[0].id(),
+                        }],
+                        *preparer.id(),
+                    );
+                    let repooled = NewMultiplexedSuspension {
+                        readable_id: "POOL-repooled".to_string(),
+                        ..repooled
+                    };
+
+                    let err = repooled.write(tx).await.unwrap_err();
+                    assert!(matches!(err, Error::ConstraintViolation { .. }));
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
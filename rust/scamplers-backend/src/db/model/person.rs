@@ -1,29 +1,37 @@
 use crate::{
+    config::LOGIN_USER,
     db::{
-        error::Result,
+        DbTransaction,
+        error::{Error, Result},
         model::{self, AsDieselQueryBase, FetchById},
-        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+        util::{
+            AsIlike, BoxedDieselExpression, NewBoxedDieselExpression, SimilarityExpressionMethods,
+        },
     },
-    fetch_by_query,
+    fetch_by_query, impl_as_diesel_condition,
     server::auth::{ApiKey, HashedApiKey},
 };
 use diesel::{
-    dsl::{AssumeNotNull, InnerJoin},
+    dsl::{AssumeNotNull, InnerJoin, sql},
     prelude::*,
 };
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use scamplers_core::model::{
-    Pagination,
+    Page, Pagination, SimilaritySearch,
+    filter::Filter,
     person::{
-        CreatedUser, NewPerson, Person, PersonData, PersonDataUpdate, PersonQuery, PersonSummary,
-        PersonUpdate, UserRole,
+        CreatedUser, NewPerson, Person, PersonData, PersonDataUpdate, PersonFilterColumn,
+        PersonQuery, PersonSummary, PersonUpdate, UserRole,
     },
 };
 use scamplers_schema::{
     institution,
     person::{
         self,
-        dsl::{email as email_col, id as id_col, ms_user_id as ms_user_id_col, name as name_col},
+        dsl::{
+            email as email_col, id as id_col, institution_id as institution_id_col,
+            ms_user_id as ms_user_id_col, name as name_col, orcid as orcid_col,
+        },
     },
 };
 use uuid::Uuid;
@@ -37,19 +45,26 @@ define_sql_function! {fn grant_roles_to_user(user_id: Text, roles: Array<Text>)}
 define_sql_function! {fn revoke_roles_from_user(user_id: Text, roles: Array<Text>)}
 define_sql_function! {fn create_user_if_not_exists(user_id: Text, roles: Array<Text>)}
 define_sql_function! {fn get_user_roles(user_id: Text) -> Array<Text>}
+define_sql_function! {fn pg_has_role(role: Text, privilege: Text) -> Bool}
 
 impl<QuerySource> model::AsDieselFilter<QuerySource> for PersonQuery
 where
     id_col: SelectableExpression<QuerySource>,
     name_col: SelectableExpression<QuerySource>,
     AssumeNotNull<email_col>: SelectableExpression<QuerySource>,
+    Filter<PersonFilterColumn>: model::AsDieselFilter<QuerySource>,
 {
     fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
     where
         QuerySource: 'a,
     {
         let Self {
-            ids, name, email, ..
+            ids,
+            name,
+            similar_name,
+            email,
+            filter,
+            ..
         } = self;
 
         let mut query = BoxedDieselExpression::new_expression();
@@ -62,14 +77,34 @@ where
             query = query.and_condition(name_col.ilike(name.as_ilike()));
         }
 
+        if let Some(SimilaritySearch { text, .. }) = similar_name {
+            query = query.and_condition(name_col.trigram_similar_to(text));
+        }
+
         if let Some(email) = email {
             query = query.and_condition(email_col.assume_not_null().ilike(email.as_ilike()));
         }
 
+        if let Some(filter) = filter.as_diesel_filter() {
+            query = query.and_condition(filter);
+        }
+
         query.build()
     }
 }
 
+impl_as_diesel_condition!(
+    PersonFilterColumn,
+    person::table,
+    [
+        (Id, id_col),
+        (Name, name_col),
+        (Email, email_col),
+        (InstitutionId, institution_id_col),
+        (Orcid, orcid_col)
+    ]
+);
+
 impl AsDieselQueryBase for PersonSummary {
     type QueryBase = person::table;
 
@@ -96,10 +131,16 @@ impl model::FetchByQuery for PersonSummary {
     async fn fetch_by_query(
         query: &Self::QueryParams,
         db_conn: &mut AsyncPgConnection,
-    ) -> Result<Vec<Self>> {
+    ) -> Result<Page<Self>> {
         use scamplers_core::model::person::PersonOrdinalColumn::{Email, Name};
 
-        fetch_by_query!(query, [(Name, name_col), (Email, email_col)], db_conn)
+        fetch_by_query!(
+            query,
+            [(Name, name_col), (Email, email_col)],
+            id_col,
+            db_conn,
+            ranked_by_similarity(similar_name, name_col)
+        )
     }
 }
 
@@ -167,6 +208,27 @@ impl model::Write for PersonUpdate {
                 .await?;
         }
 
+        if add_roles.is_empty() && remove_roles.is_empty() {
+            return Person::fetch_by_id(&update.id, db_conn).await;
+        }
+
+        // Granting or revoking a role requires the admin option on it, which only `login_user` holds. Once we know the
+        // requester is an app admin, we act as `login_user` for the role changes and then switch back
+        let (requester, requester_is_admin): (String, bool) = diesel::select((
+            sql::<Text>("current_user"),
+            pg_has_role("app_admin", "member"),
+        ))
+        .get_result(db_conn)
+        .await?;
+
+        if !requester_is_admin {
+            return Err(Error::PermissionDenied {
+                message: "only app admins can change a person's roles".to_string(),
+            });
+        }
+
+        db_conn.set_transaction_user(LOGIN_USER).await?;
+
         let user_id = update.id.to_string();
 
         diesel::select(grant_roles_to_user(&user_id, add_roles))
@@ -177,6 +239,8 @@ impl model::Write for PersonUpdate {
             .execute(db_conn)
             .await?;
 
+        db_conn.set_transaction_user(&requester).await?;
+
         Person::fetch_by_id(&update.id, db_conn).await
     }
 }
@@ -257,10 +321,12 @@ mod tests {
     use pretty_assertions::assert_eq;
     use rstest::rstest;
     use scamplers_core::model::{
+        Pagination, SimilaritySearch,
+        filter::Filter,
         institution::{InstitutionQuery, InstitutionSummary},
         person::{
-            NewPerson, PersonDataUpdate, PersonOrdering, PersonOrdinalColumn, PersonQuery,
-            PersonSummary, PersonUpdate, UserRole,
+            NewPerson, PersonDataUpdate, PersonFilterColumn, PersonOrdering, PersonOrdinalColumn,
+            PersonQuery, PersonSummary, PersonUpdate, UserRole,
         },
     };
     use uuid::Uuid;
@@ -276,7 +342,7 @@ mod tests {
     };
 
     fn comparison_fn(p: &PersonSummary) -> String {
-        p.name().to_string()
+        p.name().clone()
     }
 
     #[rstest]
@@ -311,6 +377,81 @@ mod tests {
         test_query(query, db_conn, 11, comparison_fn, &expected).await;
     }
 
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn filtered_person_query(#[future] db_conn: DbConnection) {
+        let query = PersonQuery {
+            filter: Filter::And(vec![
+                Filter::Ilike {
+                    column: PersonFilterColumn::Name,
+                    pattern: "PERSON1%".to_string(),
+                },
+                Filter::Not(Box::new(Filter::Eq {
+                    column: PersonFilterColumn::Name,
+                    value: "person1".to_string(),
+                })),
+                Filter::IsNull {
+                    column: PersonFilterColumn::Orcid,
+                },
+            ]),
+            order_by: vec![PersonOrdering {
+                column: PersonOrdinalColumn::Name,
+                descending: true,
+            }],
+            ..Default::default()
+        };
+
+        let expected = [(0, "person19"), (9, "person10")];
+        test_query(query, db_conn, 10, comparison_fn, &expected).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn empty_or_matches_nothing(#[future] db_conn: DbConnection) {
+        let query = PersonQuery {
+            filter: Filter::Or(vec![]),
+            ..Default::default()
+        };
+
+        test_query::<_, _, &str>(query, db_conn, 0, comparison_fn, &[]).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn similar_person_name_query(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let query = PersonQuery {
+                        similar_name: Some(SimilaritySearch {
+                            text: "persn42".to_string(),
+                            threshold: 0.4,
+                        }),
+                        pagination: Pagination {
+                            limit: 3,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    };
+
+                    let page = PersonSummary::fetch_by_query(&query, tx).await.unwrap();
+                    let closest = page.results.first().unwrap();
+
+                    assert_eq!(closest.name(), "person42");
+                    assert!(page.results.len() <= 3);
+                    // Ranked results can't be paginated by cursor
+                    assert_eq!(page.next_cursor, None);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
     #[rstest]
     #[awt]
     #[tokio::test]
@@ -320,7 +461,8 @@ mod tests {
                 async move {
                     let people = PersonSummary::fetch_by_query(&PersonQuery::default(), tx)
                         .await
-                        .unwrap();
+                        .unwrap()
+                        .results;
 
                     let id = people.get(0).unwrap().id();
 
@@ -365,6 +507,7 @@ mod tests {
                         *InstitutionSummary::fetch_by_query(&InstitutionQuery::default(), tx)
                             .await
                             .unwrap()
+                            .results
                             .get(0)
                             .unwrap()
                             .id();
@@ -430,4 +573,58 @@ mod tests {
             })
             .await;
     }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn only_admin_can_change_roles(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    tx.set_transaction_user(LOGIN_USER).await.unwrap();
+
+                    let institution_id =
+                        *InstitutionSummary::fetch_by_query(&InstitutionQuery::default(), tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .get(0)
+                            .unwrap()
+                            .id();
+
+                    let created_user = NewPerson {
+                        name: "Miles Morales".to_string(),
+                        email: "miles.morales@example.com".to_string(),
+                        ms_user_id: Some(Uuid::now_v7()),
+                        orcid: None,
+                        institution_id,
+                        roles: vec![],
+                    }
+                    .write_ms_login(tx)
+                    .await
+                    .unwrap();
+
+                    let role_update = || PersonUpdate {
+                        data_update: PersonDataUpdate {
+                            id: *created_user.id(),
+                            ..Default::default()
+                        },
+                        add_roles: vec![UserRole::BiologyStaff],
+                        ..Default::default()
+                    };
+
+                    tx.set_transaction_user("biology_staff").await.unwrap();
+                    let error = role_update().write(tx).await.unwrap_err();
+                    assert!(matches!(error, Error::PermissionDenied { .. }));
+
+                    tx.set_transaction_user("app_admin").await.unwrap();
+                    let updated = role_update().write(tx).await.unwrap();
+                    assert_eq!(updated.roles(), &[UserRole::BiologyStaff]);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
 }
@@ -1 +1,1080 @@
+use diesel::{dsl::now, helper_types::InnerJoin, prelude::*};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination, SimilaritySearch,
+    institution::InstitutionSummary,
+    sample_metadata::{
+        CommitteeApproval, CommitteeApprovalQuery, CommitteeApprovalUpdate,
+        ComplianceCommitteeType, NewCommitteeApproval, NewSampleMetadata,
+        NewSampleStatusTransition, Sample, SampleQuery, SampleStatus, SampleStatusTransition,
+        SampleSummary,
+    },
+};
+use scamplers_schema::{
+    committee_approval::{
+        self, approved_at as approved_at_col, committee_type as committee_type_col,
+        compliance_identifier as compliance_identifier_col, expires_at as expires_at_col,
+        institution_id as approval_institution_id_col, sample_id as approval_sample_id_col,
+    },
+    institution,
+    sample_metadata::{
+        self, id as id_col, lab_id as lab_id_col, name as name_col, received_at as received_at_col,
+        returned_at as returned_at_col, returned_by as returned_by_col, species as species_col,
+        status as status_col, submitted_by as submitted_by_col, tissue as tissue_col,
+    },
+    sample_status_transition::{
+        self, from_status as from_status_col, sample_id as transition_sample_id_col,
+        transitioned_at as transitioned_at_col,
+    },
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
 
+use crate::{
+    db::{
+        error::{Error, Result},
+        model::{self, AsDieselQueryBase, FetchById, FetchRelatives},
+        util::{
+            AsIlike, BoxedDieselExpression, NewBoxedDieselExpression, SimilarityExpressionMethods,
+        },
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewSampleMetadata {
+    type Returns = Sample;
+
+    async fn write(mut self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        // Samples are sometimes recorded after they've already been returned
+        let status = if self.returned_at.is_some() {
+            SampleStatus::Returned
+        } else {
+            SampleStatus::Received
+        };
+
+        let id = diesel::insert_into(sample_metadata::table)
+            .values((&self, status_col.eq(status)))
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        for approval in &mut self.committee_approvals {
+            approval.sample_id = Some(id);
+        }
+
+        diesel::insert_into(committee_approval::table)
+            .values(&self.committee_approvals)
+            .execute(db_conn)
+            .await?;
+
+        Sample::fetch_by_id(&id, db_conn).await
+    }
+}
+
+// The approval subquery is only valid when selecting from `sample_metadata` itself, so this can't be generic over the
+// query source like the other filters
+impl model::AsDieselFilter<sample_metadata::table> for SampleQuery {
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, sample_metadata::table>>
+    where
+        sample_metadata::table: 'a,
+    {
+        let Self {
+            ids,
+            name,
+            similar_name,
+            tissue,
+            lab_ids,
+            submitter_ids,
+            any_species,
+            all_species,
+            received_after,
+            received_before,
+            returned_after,
+            returned_before,
+            missing_committee_approval,
+            statuses,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(name) = name {
+            query = query.and_condition(name_col.ilike(name.as_ilike()));
+        }
+
+        if let Some(SimilaritySearch { text, .. }) = similar_name {
+            query = query.and_condition(name_col.trigram_similar_to(text));
+        }
+
+        if let Some(tissue) = tissue {
+            query = query.and_condition(tissue_col.ilike(tissue.as_ilike()));
+        }
+
+        if !lab_ids.is_empty() {
+            query = query.and_condition(lab_id_col.eq_any(lab_ids));
+        }
+
+        if !submitter_ids.is_empty() {
+            query = query.and_condition(submitted_by_col.eq_any(submitter_ids));
+        }
+
+        if !any_species.is_empty() {
+            query = query.and_condition(species_col.overlaps_with(any_species));
+        }
+
+        if !all_species.is_empty() {
+            query = query.and_condition(species_col.contains(all_species));
+        }
+
+        if let Some(received_after) = received_after {
+            query = query.and_condition(received_at_col.ge(received_after));
+        }
+
+        if let Some(received_before) = received_before {
+            query = query.and_condition(received_at_col.le(received_before));
+        }
+
+        if let Some(returned_after) = returned_after {
+            query = query.and_condition(returned_at_col.assume_not_null().ge(returned_after));
+        }
+
+        if let Some(returned_before) = returned_before {
+            query = query.and_condition(returned_at_col.assume_not_null().le(returned_before));
+        }
+
+        if let Some(committee_type) = missing_committee_approval {
+            let approved_samples = committee_approval::table
+                .filter(committee_type_col.eq(committee_type))
+                .filter(expires_at_col.gt(now).is_distinct_from(false))
+                .select(approval_sample_id_col);
+
+            query = query.and_condition(id_col.ne_all(approved_samples));
+        }
+
+        if !statuses.is_empty() {
+            query = query.and_condition(status_col.eq_any(statuses));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for SampleSummary {
+    type QueryBase = sample_metadata::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        sample_metadata::table
+    }
+}
+
+impl model::FetchById for SampleSummary {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        Ok(Self::as_diesel_query_base()
+            .find(id)
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+impl model::FetchByQuery for SampleSummary {
+    type QueryParams = SampleQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::sample_metadata::SampleOrdinalColumn::{Name, ReceivedAt};
+
+        fetch_by_query!(
+            query,
+            [(Name, name_col), (ReceivedAt, received_at_col)],
+            id_col,
+            db_conn,
+            ranked_by_similarity(similar_name, name_col)
+        )
+    }
+}
+
+impl model::FetchRelatives<CommitteeApproval> for sample_metadata::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        sample_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<CommitteeApproval>> {
+        let approvals = CommitteeApproval::as_diesel_query_base()
+            .filter(approval_sample_id_col.eq(sample_id))
+            .select(CommitteeApproval::as_select())
+            .load(db_conn)
+            .await?;
+
+        Ok(approvals)
+    }
+}
+
+impl model::WriteRelatives<NewCommitteeApproval> for sample_metadata::table {
+    type Id = Uuid;
+    type Returns = CommitteeApproval;
+
+    async fn write_relatives(
+        sample_id: &Self::Id,
+        mut approvals: Vec<NewCommitteeApproval>,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<Self::Returns>> {
+        for approval in &mut approvals {
+            approval.sample_id = Some(*sample_id);
+        }
+
+        diesel::insert_into(committee_approval::table)
+            .values(&approvals)
+            .execute(db_conn)
+            .await?;
+
+        <Self as FetchRelatives<CommitteeApproval>>::fetch_relatives(sample_id, db_conn).await
+    }
+}
+
+impl model::FetchRelatives<SampleStatusTransition> for sample_metadata::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        sample_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<SampleStatusTransition>> {
+        let transitions = sample_status_transition::table
+            .filter(transition_sample_id_col.eq(sample_id))
+            .order_by(transitioned_at_col)
+            .select(SampleStatusTransition::as_select())
+            .load(db_conn)
+            .await?;
+
+        Ok(transitions)
+    }
+}
+
+impl model::WriteRelatives<NewSampleStatusTransition> for sample_metadata::table {
+    type Id = Uuid;
+    type Returns = SampleStatusTransition;
+
+    // Transitions are applied in the order given, so a client can move a sample through several statuses at once.
+    // Locking the sample's row prevents two concurrent transitions from both starting from the same status, or from
+    // being recorded out of order.
+    async fn write_relatives(
+        sample_id: &Self::Id,
+        transitions: Vec<NewSampleStatusTransition>,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<Self::Returns>> {
+        for mut transition in transitions {
+            transition.sample_id = Some(*sample_id);
+
+            let (current_status, received_at): (SampleStatus, OffsetDateTime) =
+                sample_metadata::table
+                    .find(sample_id)
+                    .select((status_col, received_at_col))
+                    .for_update()
+                    .first(db_conn)
+                    .await?;
+
+            if !current_status.can_transition_to(transition.to_status) {
+                let (from, to): (&str, &str) = (current_status.into(), transition.to_status.into());
+
+                return Err(Error::ConstraintViolation {
+                    entity: "sample_metadata".to_string(),
+                    constraint: "allowed_status_transition".to_string(),
+                    message: format!("sample {sample_id} cannot go from {from} to {to}"),
+                });
+            }
+
+            // A transition that isn't given a time is recorded now, so only a client-supplied time can be out of order
+            if let Some(transitioned_at) = transition.transitioned_at {
+                let last_transitioned_at: Option<OffsetDateTime> = sample_status_transition::table
+                    .filter(transition_sample_id_col.eq(sample_id))
+                    .select(transitioned_at_col)
+                    .order_by(transitioned_at_col.desc())
+                    .first(db_conn)
+                    .await
+                    .optional()?;
+
+                let earliest_allowed =
+                    last_transitioned_at.map_or(received_at, |last| last.max(received_at));
+
+                if transitioned_at < earliest_allowed {
+                    return Err(Error::ConstraintViolation {
+                        entity: "sample_status_transition".to_string(),
+                        constraint: "transitioned_in_order".to_string(),
+                        message: format!(
+                            "sample {sample_id} cannot transition at {transitioned_at}, before it was received or \
+                             last transitioned at {earliest_allowed}"
+                        ),
+                    });
+                }
+            }
+
+            let transitioned_at: OffsetDateTime =
+                diesel::insert_into(sample_status_transition::table)
+                    .values((&transition, from_status_col.eq(current_status)))
+                    .returning(transitioned_at_col)
+                    .get_result(db_conn)
+                    .await?;
+
+            let to_status = status_col.eq(transition.to_status);
+            let sample = sample_metadata::table.find(sample_id);
+
+            if transition.to_status == SampleStatus::Returned {
+                diesel::update(sample)
+                    .set((
+                        to_status,
+                        returned_at_col.eq(transitioned_at),
+                        returned_by_col.eq(transition.transitioned_by),
+                    ))
+                    .execute(db_conn)
+                    .await?;
+            } else {
+                diesel::update(sample)
+                    .set(to_status)
+                    .execute(db_conn)
+                    .await?;
+            }
+        }
+
+        <Self as FetchRelatives<SampleStatusTransition>>::fetch_relatives(sample_id, db_conn).await
+    }
+}
+
+impl model::Write for CommitteeApprovalUpdate {
+    type Returns = CommitteeApproval;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let id = (self.institution_id, self.committee_type, self.sample_id);
+
+        if let Self {
+            compliance_identifier: None,
+            approved_at: None,
+            expires_at: None,
+            remove_expiry: false,
+            ..
+        } = &self
+        {
+            return CommitteeApproval::fetch_by_id(&id, db_conn).await;
+        }
+
+        let expires_at = if self.remove_expiry {
+            Some(expires_at_col.eq(None))
+        } else {
+            self.expires_at
+                .map(|expires_at| expires_at_col.eq(Some(expires_at)))
+        };
+
+        diesel::update(&self)
+            .set((
+                self.compliance_identifier
+                    .as_ref()
+                    .map(|identifier| compliance_identifier_col.eq(identifier)),
+                self.approved_at
+                    .map(|approved_at| approved_at_col.eq(approved_at)),
+                expires_at,
+            ))
+            .execute(db_conn)
+            .await?;
+
+        CommitteeApproval::fetch_by_id(&id, db_conn).await
+    }
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for CommitteeApprovalQuery
+where
+    approval_sample_id_col: SelectableExpression<QuerySource>,
+    approval_institution_id_col: SelectableExpression<QuerySource>,
+    committee_type_col: SelectableExpression<QuerySource>,
+    expires_at_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            sample_ids,
+            institution_ids,
+            committee_types,
+            expired,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !sample_ids.is_empty() {
+            query = query.and_condition(approval_sample_id_col.eq_any(sample_ids));
+        }
+
+        if !institution_ids.is_empty() {
+            query = query.and_condition(approval_institution_id_col.eq_any(institution_ids));
+        }
+
+        if !committee_types.is_empty() {
+            query = query.and_condition(committee_type_col.eq_any(committee_types));
+        }
+
+        // An approval without an expiry date never expires, so comparing its expiry date to the present evaluates to NULL
+        match expired {
+            Some(true) => {
+                query = query.and_condition(expires_at_col.le(now).is_not_distinct_from(true));
+            }
+            Some(false) => {
+                query = query.and_condition(expires_at_col.gt(now).is_distinct_from(false));
+            }
+            None => {}
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for CommitteeApproval {
+    type QueryBase = InnerJoin<committee_approval::table, institution::table>;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        committee_approval::table.inner_join(InstitutionSummary::as_diesel_query_base())
+    }
+}
+
+/// Looks up an approval by `(institution_id, committee_type, sample_id)`
+impl model::FetchById for CommitteeApproval {
+    type Id = (Uuid, ComplianceCommitteeType, Uuid);
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let (institution_id, committee_type, sample_id) = id;
+
+        Ok(Self::as_diesel_query_base()
+            .filter(approval_institution_id_col.eq(institution_id))
+            .filter(committee_type_col.eq(committee_type))
+            .filter(approval_sample_id_col.eq(sample_id))
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+impl model::FetchByQuery for CommitteeApproval {
+    type QueryParams = CommitteeApprovalQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::sample_metadata::CommitteeApprovalOrdinalColumn::{
+            ApprovedAt, ComplianceIdentifier, ExpiresAt,
+        };
+
+        fetch_by_query!(
+            query,
+            [
+                (ExpiresAt, expires_at_col),
+                (ApprovedAt, approved_at_col),
+                (ComplianceIdentifier, compliance_identifier_col)
+            ],
+            db_conn
+        )
+    }
+}
+
+impl model::FetchById for Sample {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let summary = SampleSummary::fetch_by_id(id, db_conn).await?;
+        let committee_approvals =
+            <sample_metadata::table as FetchRelatives<CommitteeApproval>>::fetch_relatives(
+                id, db_conn,
+            )
+            .await?;
+
+        Ok(Self::new(summary, committee_approvals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::{AsyncConnection, AsyncPgConnection, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        Pagination,
+        institution::{InstitutionQuery, InstitutionSummary},
+        lab::{LabQuery, LabSummary},
+        person::{PersonQuery, PersonSummary},
+        sample_metadata::{
+            CommitteeApproval, CommitteeApprovalQuery, CommitteeApprovalUpdate,
+            ComplianceCommitteeType, NewCommitteeApproval, NewSampleMetadata,
+            NewSampleStatusTransition, SampleOrdering, SampleOrdinalColumn, SampleQuery,
+            SampleStatus, SampleSummary, Species,
+        },
+    };
+    use scamplers_schema::sample_metadata;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchById, FetchByQuery, Write, WriteRelatives},
+        test_util::{DbConnection, N_SAMPLES, db_conn, test_query},
+    };
+
+    fn comparison_fn(s: &SampleSummary) -> String {
+        s.name().clone()
+    }
+
+    fn new_human_sample(
+        institution_id: Uuid,
+        lab_id: Uuid,
+        submitted_by: Uuid,
+    ) -> NewSampleMetadata {
+        NewSampleMetadata {
+            name: "Krabby Patty".to_string(),
+            submitted_by,
+            lab_id,
+            received_at: "2025-06-17T12:30:00Z".parse().unwrap(),
+            species: vec![Species::HomoSapiens],
+            tissue: "brain".to_string(),
+            committee_approvals: vec![NewCommitteeApproval {
+                sample_id: None,
+                institution_id,
+                committee_type: ComplianceCommitteeType::Irb,
+                compliance_identifier: "irb-1".to_string(),
+                approved_at: Some("2025-01-01T00:00:00Z".parse().unwrap()),
+                expires_at: None,
+            }],
+            notes: None,
+            returned_at: None,
+            returned_by: None,
+        }
+    }
+
+    async fn new_human_sample_from_db(db_conn: &mut AsyncPgConnection) -> NewSampleMetadata {
+        let institution = InstitutionSummary::fetch_by_query(&InstitutionQuery::default(), db_conn)
+            .await
+            .unwrap()
+            .results
+            .remove(0);
+
+        let lab = LabSummary::fetch_by_query(&LabQuery::default(), db_conn)
+            .await
+            .unwrap()
+            .results
+            .remove(0);
+
+        let submitter = PersonSummary::fetch_by_query(&PersonQuery::default(), db_conn)
+            .await
+            .unwrap()
+            .results
+            .remove(0);
+
+        new_human_sample(*institution.id(), *lab.id(), *submitter.id())
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn default_sample_query(#[future] db_conn: DbConnection) {
+        let expected = [(0, "sample0"), (N_SAMPLES - 1, "sample9")];
+        test_query(
+            SampleQuery::default(),
+            db_conn,
+            N_SAMPLES,
+            comparison_fn,
+            &expected,
+        )
+        .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn specific_sample_query(#[future] db_conn: DbConnection) {
+        let query = SampleQuery {
+            name: Some("sample1".to_string()),
+            order_by: vec![SampleOrdering {
+                column: SampleOrdinalColumn::Name,
+                descending: true,
+            }],
+            ..Default::default()
+        };
+
+        let expected = [(0, "sample19"), (10, "sample1")];
+        test_query(query, db_conn, 11, comparison_fn, &expected).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn new_sample_with_committee_approvals(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let new_sample = new_human_sample_from_db(tx).await;
+                    let institution_id = new_sample.committee_approvals[0].institution_id;
+                    let received_at = new_sample.received_at;
+
+                    let sample = new_sample.write(tx).await.unwrap();
+
+                    assert_eq!(*sample.received_at(), received_at);
+                    assert_eq!(sample.species(), &[Species::HomoSapiens]);
+
+                    let approvals = sample.committee_approvals();
+                    assert_eq!(approvals.len(), 1);
+                    assert_eq!(approvals[0].sample_id(), sample.id());
+                    assert_eq!(*approvals[0].institution().id(), institution_id);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn search_samples_by_species_and_dates(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let brain = new_human_sample_from_db(tx).await;
+                    let (lab_id, submitter_id) = (brain.lab_id, brain.submitted_by);
+
+                    let xenograft = NewSampleMetadata {
+                        name: "Xenograft".to_string(),
+                        received_at: "2025-07-01T00:00:00Z".parse().unwrap(),
+                        species: vec![Species::HomoSapiens, Species::MusMusculus],
+                        returned_at: Some("2025-07-15T00:00:00Z".parse().unwrap()),
+                        returned_by: Some(submitter_id),
+                        ..new_human_sample_from_db(tx).await
+                    };
+
+                    brain.write(tx).await.unwrap();
+                    xenograft.write(tx).await.unwrap();
+
+                    let mut search = async |query: SampleQuery| {
+                        SampleSummary::fetch_by_query(&query, tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .into_iter()
+                            .map(|s| s.name().clone())
+                            .collect::<Vec<_>>()
+                    };
+
+                    // The samples in the test database are all mouse samples received at the start of 2025
+                    let human = search(SampleQuery {
+                        any_species: vec![Species::HomoSapiens, Species::RattusNorvegicus],
+                        ..Default::default()
+                    })
+                    .await;
+                    assert_eq!(human, ["Krabby Patty", "Xenograft"]);
+
+                    let human_and_mouse = search(SampleQuery {
+                        all_species: vec![Species::MusMusculus, Species::HomoSapiens],
+                        ..Default::default()
+                    })
+                    .await;
+                    assert_eq!(human_and_mouse, ["Xenograft"]);
+
+                    let received_in_june = search(SampleQuery {
+                        received_after: Some("2025-06-01T00:00:00Z".parse().unwrap()),
+                        received_before: Some("2025-06-30T00:00:00Z".parse().unwrap()),
+                        ..Default::default()
+                    })
+                    .await;
+                    assert_eq!(received_in_june, ["Krabby Patty"]);
+
+                    let returned = search(SampleQuery {
+                        returned_after: Some("2025-07-01T00:00:00Z".parse().unwrap()),
+                        ..Default::default()
+                    })
+                    .await;
+                    assert_eq!(returned, ["Xenograft"]);
+
+                    let latest_from_submitter = search(SampleQuery {
+                        lab_ids: vec![lab_id],
+                        submitter_ids: vec![submitter_id],
+                        order_by: vec![SampleOrdering {
+                            column: SampleOrdinalColumn::ReceivedAt,
+                            descending: true,
+                        }],
+                        ..Default::default()
+                    })
+                    .await;
+                    assert_eq!(latest_from_submitter[..2], ["Xenograft", "Krabby Patty"]);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[test]
+    fn human_sample_requires_irb_approval() {
+        let mut sample = new_human_sample(Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        sample.validate().unwrap();
+
+        sample.committee_approvals[0].expires_at = Some("2025-06-01T00:00:00Z".parse().unwrap());
+        sample.validate().unwrap_err();
+
+        sample.committee_approvals[0].expires_at = None;
+        sample.committee_approvals[0].approved_at = Some("2025-07-01T00:00:00Z".parse().unwrap());
+        sample.validate().unwrap_err();
+
+        sample.committee_approvals[0].approved_at = None;
+        sample.validate().unwrap();
+
+        sample.committee_approvals[0].committee_type = ComplianceCommitteeType::Iacuc;
+        sample.committee_approvals[0].expires_at = None;
+        sample.validate().unwrap_err();
+
+        sample.species = vec![Species::MusMusculus];
+        sample.validate().unwrap();
+    }
+
+    #[test]
+    fn approval_cannot_expire_before_it_was_granted() {
+        let mut sample = new_human_sample(Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+
+        let approval = &mut sample.committee_approvals[0];
+        approval.expires_at = Some("2024-12-31T00:00:00Z".parse().unwrap());
+        approval.validate().unwrap_err();
+    }
+
+    #[test]
+    fn expiry_cannot_be_set_and_removed() {
+        let mut update = CommitteeApprovalUpdate {
+            expires_at: Some("2025-06-01T00:00:00Z".parse().unwrap()),
+            remove_expiry: true,
+            ..Default::default()
+        };
+        update.validate().unwrap_err();
+
+        update.expires_at = None;
+        update.validate().unwrap();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn search_samples_missing_committee_approval(#[future] db_conn: DbConnection) {
+        let query = SampleQuery {
+            missing_committee_approval: Some(ComplianceCommitteeType::Iacuc),
+            ..Default::default()
+        };
+
+        test_query(query, db_conn, N_SAMPLES, comparison_fn, &[(0, "sample0")]).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn expire_committee_approval(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let sample = new_human_sample_from_db(tx).await.write(tx).await.unwrap();
+                    let approval = &sample.committee_approvals()[0];
+
+                    let missing_irb = SampleQuery {
+                        ids: vec![*sample.id()],
+                        missing_committee_approval: Some(ComplianceCommitteeType::Irb),
+                        ..Default::default()
+                    };
+                    let expired_approvals = CommitteeApprovalQuery {
+                        sample_ids: vec![*sample.id()],
+                        expired: Some(true),
+                        ..Default::default()
+                    };
+
+                    assert!(
+                        SampleSummary::fetch_by_query(&missing_irb, tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .is_empty()
+                    );
+                    assert!(
+                        CommitteeApproval::fetch_by_query(&expired_approvals, tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .is_empty()
+                    );
+
+                    let expires_at: OffsetDateTime = "2025-06-01T00:00:00Z".parse().unwrap();
+                    let update = CommitteeApprovalUpdate {
+                        institution_id: *approval.institution().id(),
+                        committee_type: ComplianceCommitteeType::Irb,
+                        sample_id: *sample.id(),
+                        expires_at: Some(expires_at),
+                        ..Default::default()
+                    };
+                    let expired = update.write(tx).await.unwrap();
+
+                    assert_eq!(*expired.expires_at(), Some(expires_at));
+                    assert_eq!(expired.compliance_identifier(), "irb-1");
+
+                    assert_eq!(
+                        SampleSummary::fetch_by_query(&missing_irb, tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .len(),
+                        1
+                    );
+                    assert_eq!(
+                        CommitteeApproval::fetch_by_query(&expired_approvals, tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .len(),
+                        1
+                    );
+
+                    let renewed = CommitteeApprovalUpdate {
+                        institution_id: *approval.institution().id(),
+                        committee_type: ComplianceCommitteeType::Irb,
+                        sample_id: *sample.id(),
+                        remove_expiry: true,
+                        ..Default::default()
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap();
+
+                    assert_eq!(*renewed.expires_at(), None);
+                    assert!(
+                        SampleSummary::fetch_by_query(&missing_irb, tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .is_empty()
+                    );
+
+                    // Only the database knows when the approval was granted, so it's the one to reject this
+                    let err = CommitteeApprovalUpdate {
+                        institution_id: *approval.institution().id(),
+                        committee_type: ComplianceCommitteeType::Irb,
+                        sample_id: *sample.id(),
+                        expires_at: Some("2024-12-01T00:00:00Z".parse().unwrap()),
+                        ..Default::default()
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap_err();
+
+                    let Error::ConstraintViolation { message, .. } = err else {
+                        panic!("expected constraint violation, found {err:?}");
+                    };
+                    assert_eq!(
+                        message,
+                        "a committee approval must expire after it was approved"
+                    );
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn search_samples_by_status(#[future] db_conn: DbConnection) {
+        let query = SampleQuery {
+            statuses: vec![SampleStatus::Received],
+            ..Default::default()
+        };
+
+        test_query(query, db_conn, N_SAMPLES, comparison_fn, &[(0, "sample0")]).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn sample_status_transitions(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let sample = new_human_sample_from_db(tx).await.write(tx).await.unwrap();
+                    let transitioned_by = *sample.submitted_by();
+
+                    let transition = |to_status| NewSampleStatusTransition {
+                        sample_id: None,
+                        to_status,
+                        transitioned_by,
+                        transitioned_at: None,
+                    };
+
+                    let transitions = sample_metadata::table::write_relatives(
+                        sample.id(),
+                        vec![
+                            transition(SampleStatus::InProcessing),
+                            transition(SampleStatus::Returned),
+                        ],
+                        tx,
+                    )
+                    .await
+                    .unwrap();
+
+                    assert_eq!(transitions.len(), 2);
+                    assert_eq!(*transitions[1].from_status(), SampleStatus::InProcessing);
+
+                    let sample = SampleSummary::fetch_by_id(sample.id(), tx).await.unwrap();
+                    assert_eq!(*sample.status(), SampleStatus::Returned);
+                    assert_eq!(
+                        *sample.returned_at(),
+                        Some(*transitions[1].transitioned_at())
+                    );
+                    assert_eq!(*sample.returned_by(), Some(transitioned_by));
+
+                    let err = sample_metadata::table::write_relatives(
+                        sample.id(),
+                        vec![transition(SampleStatus::Disposed)],
+                        tx,
+                    )
+                    .await
+                    .unwrap_err();
+
+                    let Error::ConstraintViolation { constraint, .. } = err else {
+                        panic!("expected constraint violation, found {err:?}");
+                    };
+                    assert_eq!(constraint, "allowed_status_transition");
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn transitions_cannot_go_back_in_time(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let sample = new_human_sample_from_db(tx).await.write(tx).await.unwrap();
+
+                    let transition = |to_status, transitioned_at: &str| NewSampleStatusTransition {
+                        sample_id: None,
+                        to_status,
+                        transitioned_by: *sample.submitted_by(),
+                        transitioned_at: Some(transitioned_at.parse().unwrap()),
+                    };
+
+                    // Before the sample was received
+                    let err = sample_metadata::table::write_relatives(
+                        sample.id(),
+                        vec![transition(SampleStatus::Returned, "2025-06-01T00:00:00Z")],
+                        tx,
+                    )
+                    .await
+                    .unwrap_err();
+                    let Error::ConstraintViolation { constraint, .. } = err else {
+                        panic!("expected constraint violation, found {err:?}");
+                    };
+                    assert_eq!(constraint, "transitioned_in_order");
+
+                    sample_metadata::table::write_relatives(
+                        sample.id(),
+                        vec![transition(
+                            SampleStatus::InProcessing,
+                            "2025-07-01T00:00:00Z",
+                        )],
+                        tx,
+                    )
+                    .await
+                    .unwrap();
+
+                    // Before the previous transition
+                    let err = sample_metadata::table::write_relatives(
+                        sample.id(),
+                        vec![transition(SampleStatus::Returned, "2025-06-20T00:00:00Z")],
+                        tx,
+                    )
+                    .await
+                    .unwrap_err();
+                    let Error::ConstraintViolation { constraint, .. } = err else {
+                        panic!("expected constraint violation, found {err:?}");
+                    };
+                    assert_eq!(constraint, "transitioned_in_order");
+
+                    let transitions = sample_metadata::table::write_relatives(
+                        sample.id(),
+                        vec![transition(SampleStatus::Returned, "2025-07-02T00:00:00Z")],
+                        tx,
+                    )
+                    .await
+                    .unwrap();
+                    assert_eq!(transitions.len(), 2);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    // Every test sample was received at the same time, so ordering by receipt leaves only the id to break ties
+    #[rstest]
+    #[case(SampleOrdinalColumn::Name)]
+    #[case(SampleOrdinalColumn::ReceivedAt)]
+    #[awt]
+    #[tokio::test]
+    async fn paginate_samples_by_cursor(
+        #[future] mut db_conn: DbConnection,
+        #[case] column: SampleOrdinalColumn,
+    ) {
+        let mut query = SampleQuery {
+            order_by: vec![SampleOrdering {
+                column,
+                descending: true,
+            }],
+            ..Default::default()
+        };
+
+        let all_samples = SampleSummary::fetch_by_query(&query, &mut db_conn)
+            .await
+            .unwrap()
+            .results;
+
+        query.pagination = Pagination {
+            limit: 3,
+            count_total: true,
+            ..Default::default()
+        };
+
+        let mut paged_samples = Vec::new();
+        loop {
+            let page = SampleSummary::fetch_by_query(&query, &mut db_conn)
+                .await
+                .unwrap();
+
+            assert_eq!(page.total_count, Some(i64::try_from(N_SAMPLES).unwrap()));
+            paged_samples.extend(page.results);
+
+            let Some(cursor) = page.next_cursor else {
+                break;
+            };
+            query.pagination.cursor = Some(cursor);
+        }
+
+        let ids = |samples: &[SampleSummary]| samples.iter().map(|s| *s.id()).collect::<Vec<_>>();
+        assert_eq!(ids(&paged_samples), ids(&all_samples));
+    }
+}
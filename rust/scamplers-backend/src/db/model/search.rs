@@ -0,0 +1,142 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page,
+    institution::InstitutionSummary,
+    lab::LabSummary,
+    person::PersonSummary,
+    sample_metadata::SampleSummary,
+    search::{GlobalSearchQuery, SearchHit, SearchRecord},
+};
+use scamplers_schema::{institution, lab, person, sample_metadata};
+
+use crate::db::{
+    error::Result,
+    model::{self, AsDieselQueryBase},
+    util::{SimilarityExpressionMethods, set_similarity_threshold, similarity},
+};
+
+// Fetches the records of one kind whose names are similar to the search text, most similar first. No kind can
+// contribute more than `limit` hits, so that's all we need from each.
+macro_rules! search_names {
+    ($summary:ident, $name_col:expr, $variant:ident, $query:ident, $db_conn:ident) => {{
+        let GlobalSearchQuery { q, limit, .. } = $query;
+        let similarity = similarity($name_col, q);
+
+        $summary::as_diesel_query_base()
+            .filter($name_col.trigram_similar_to(q))
+            .select(($summary::as_select(), similarity))
+            .order_by(similarity.desc())
+            .limit(*limit)
+            .load::<($summary, f32)>($db_conn)
+            .await?
+            .into_iter()
+            .map(|(record, similarity)| SearchHit {
+                similarity,
+                record: SearchRecord::$variant(record),
+            })
+    }};
+}
+
+impl model::FetchByQuery for SearchHit {
+    type QueryParams = GlobalSearchQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        set_similarity_threshold(query.threshold, db_conn).await?;
+
+        let mut results: Vec<_> =
+            search_names!(PersonSummary, person::name, Person, query, db_conn).collect();
+        results.extend(search_names!(LabSummary, lab::name, Lab, query, db_conn));
+        results.extend(search_names!(
+            InstitutionSummary,
+            institution::name,
+            Institution,
+            query,
+            db_conn
+        ));
+        results.extend(search_names!(
+            SampleSummary,
+            sample_metadata::name,
+            Sample,
+            query,
+            db_conn
+        ));
+
+        results.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        results.truncate(usize::try_from(query.limit).unwrap_or_default());
+
+        Ok(Page {
+            results,
+            next_cursor: None,
+            total_count: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::search::{GlobalSearchQuery, SearchHit, SearchRecord};
+
+    use crate::db::{
+        error::Error,
+        model::FetchByQuery,
+        test_util::{DbConnection, db_conn},
+    };
+
+    fn name(hit: &SearchHit) -> &str {
+        match &hit.record {
+            SearchRecord::Person(p) => p.name(),
+            SearchRecord::Lab(l) => l.name(),
+            SearchRecord::Institution(i) => i.name(),
+            SearchRecord::Sample(s) => s.name(),
+        }
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn global_search(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let query = GlobalSearchQuery {
+                        q: "institutoin3".to_string(),
+                        limit: 5,
+                        ..Default::default()
+                    };
+
+                    let hits = SearchHit::fetch_by_query(&query, tx).await.unwrap().results;
+                    let closest = hits.first().unwrap();
+
+                    assert!(matches!(closest.record, SearchRecord::Institution(_)));
+                    assert_eq!(name(closest), "institution3");
+                    assert!(hits.len() <= 5);
+                    assert!(hits.is_sorted_by(|a, b| a.similarity >= b.similarity));
+
+                    let query = GlobalSearchQuery {
+                        q: "sample1".to_string(),
+                        limit: 100,
+                        threshold: 0.5,
+                    };
+
+                    let hits = SearchHit::fetch_by_query(&query, tx).await.unwrap().results;
+
+                    assert_eq!(name(hits.first().unwrap()), "sample1");
+                    assert!(
+                        hits.iter()
+                            .any(|h| matches!(h.record, SearchRecord::Sample(_)))
+                    );
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
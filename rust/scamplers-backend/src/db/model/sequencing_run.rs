@@ -1 +1,396 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    sequencing_run::{
+        NewSequencingRun, NewSequencingSubmission, SequencingRun, SequencingRunQuery,
+        SequencingRunSummary, SequencingRunUpdate, SequencingSubmission,
+    },
+};
+use scamplers_schema::{
+    chromium_library, chromium_sequencing_submissions,
+    sequencing_run::{
+        self, begun_at as begun_at_col, finished_at as finished_at_col, id as id_col,
+        readable_id as readable_id_col,
+    },
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
 
+use crate::{
+    db::{
+        error::{Error, Result},
+        model::{self, AsDieselQueryBase, FetchById, FetchRelatives, WriteRelatives},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewSequencingRun {
+    type Returns = SequencingRun;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let id = diesel::insert_into(sequencing_run::table)
+            .values(&self)
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        sequencing_run::table::write_relatives(&id, self.libraries, db_conn).await?;
+
+        SequencingRun::fetch_by_id(&id, db_conn).await
+    }
+}
+
+impl model::Write for SequencingRunUpdate {
+    type Returns = SequencingRun;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        if let Self {
+            readable_id: None,
+            begun_at: None,
+            finished_at: None,
+            notes: None,
+            ..
+        } = &self
+        {
+            return SequencingRun::fetch_by_id(&self.id, db_conn).await;
+        }
+
+        // Validation only compares the two times when both are given, so check the result against what's stored
+        let (begun_at, finished_at): (OffsetDateTime, OffsetDateTime) = diesel::update(&self)
+            .set(&self)
+            .returning((begun_at_col, finished_at_col))
+            .get_result(db_conn)
+            .await?;
+
+        if finished_at < begun_at {
+            return Err(Error::ConstraintViolation {
+                entity: "sequencing_run".to_string(),
+                constraint: "finished_after_begun".to_string(),
+                message: format!("sequencing run {} cannot finish before it began", self.id),
+            });
+        }
+
+        SequencingRun::fetch_by_id(&self.id, db_conn).await
+    }
+}
+
+impl model::WriteRelatives<NewSequencingSubmission> for sequencing_run::table {
+    type Id = Uuid;
+    type Returns = SequencingSubmission;
+
+    async fn write_relatives(
+        sequencing_run_id: &Self::Id,
+        submissions: Vec<NewSequencingSubmission>,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<Self::Returns>> {
+        let submissions: Vec<_> = submissions
+            .iter()
+            .map(|submission| {
+                (
+                    submission,
+                    chromium_sequencing_submissions::sequencing_run_id.eq(sequencing_run_id),
+                )
+            })
+            .collect();
+
+        let submissions = diesel::insert_into(chromium_sequencing_submissions::table)
+            .values(submissions)
+            .returning(SequencingSubmission::as_returning())
+            .get_results(db_conn)
+            .await?;
+
+        Ok(submissions)
+    }
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for SequencingRunQuery
+where
+    id_col: SelectableExpression<QuerySource>,
+    readable_id_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            ids, readable_id, ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(readable_id) = readable_id {
+            query = query.and_condition(readable_id_col.ilike(readable_id.as_ilike()));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for SequencingRunSummary {
+    type QueryBase = sequencing_run::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        sequencing_run::table
+    }
+}
+
+impl model::FetchByQuery for SequencingRunSummary {
+    type QueryParams = SequencingRunQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::sequencing_run::SequencingRunOrdinalColumn::{
+            BegunAt, ReadableId,
+        };
+
+        fetch_by_query!(
+            query,
+            [(BegunAt, begun_at_col), (ReadableId, readable_id_col)],
+            id_col,
+            db_conn
+        )
+    }
+}
+
+impl model::FetchRelatives<SequencingSubmission> for sequencing_run::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        sequencing_run_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<SequencingSubmission>> {
+        let submissions = chromium_sequencing_submissions::table
+            .filter(chromium_sequencing_submissions::sequencing_run_id.eq(sequencing_run_id))
+            .select(SequencingSubmission::as_select())
+            .order_by(chromium_sequencing_submissions::submitted_at)
+            .load(db_conn)
+            .await?;
+
+        Ok(submissions)
+    }
+}
+
+impl model::FetchRelatives<SequencingRunSummary> for chromium_library::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        library_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<SequencingRunSummary>> {
+        let runs = chromium_sequencing_submissions::table
+            .filter(chromium_sequencing_submissions::library_id.eq(library_id))
+            .inner_join(SequencingRunSummary::as_diesel_query_base())
+            .select(SequencingRunSummary::as_select())
+            .order_by(begun_at_col)
+            .load(db_conn)
+            .await?;
+
+        Ok(runs)
+    }
+}
+
+impl model::FetchById for SequencingRun {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let summary = SequencingRunSummary::as_diesel_query_base()
+            .find(id)
+            .select(SequencingRunSummary::as_select())
+            .first(db_conn)
+            .await?;
+        let libraries = sequencing_run::table::fetch_relatives(id, db_conn).await?;
+
+        Ok(Self::new(summary, libraries))
+    }
+}
+
+impl model::FetchByReadableId for SequencingRun {
+    async fn fetch_by_readable_id(
+        readable_id: &str,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Self> {
+        let id = sequencing_run::table
+            .select(id_col)
+            .filter(readable_id_col.eq(readable_id))
+            .get_result(db_conn)
+            .await?;
+
+        Self::fetch_by_id(&id, db_conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        library::{LibraryQuery, LibrarySummary},
+        sequencing_run::{
+            NewSequencingRun, NewSequencingSubmission, SequencingRunQuery, SequencingRunSummary,
+            SequencingRunUpdate, SequencingSubmission,
+        },
+    };
+    use scamplers_schema::{chromium_library, sequencing_run};
+    use time::OffsetDateTime;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, FetchRelatives, Write},
+        test_util::{DbConnection, N_LIBRARIES, db_conn},
+    };
+
+    fn new_run(libraries: Vec<NewSequencingSubmission>) -> NewSequencingRun {
+        NewSequencingRun {
+            readable_id: "SEQ-new".to_string(),
+            begun_at: "2025-02-01T00:00:00Z".parse().unwrap(),
+            finished_at: "2025-02-02T00:00:00Z".parse().unwrap(),
+            notes: None,
+            libraries,
+        }
+    }
+
+    #[test]
+    fn run_cannot_finish_before_it_began() {
+        let mut run = new_run(vec![]);
+        run.validate().unwrap();
+
+        run.finished_at = "2025-01-31T23:00:00Z".parse().unwrap();
+        run.validate().unwrap_err();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn update_run(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let original =
+                        SequencingRunSummary::fetch_by_query(&SequencingRunQuery::default(), tx)
+                            .await
+                            .unwrap()
+                            .results
+                            .remove(0);
+
+                    let update = SequencingRunUpdate {
+                        id: *original.id(),
+                        readable_id: Some("SEQ-renamed".to_string()),
+                        notes: Some(vec!["resequenced".to_string()]),
+                        ..Default::default()
+                    };
+                    let updated = update.write(tx).await.unwrap();
+
+                    assert_eq!(updated.readable_id(), "SEQ-renamed");
+                    assert_eq!(updated.begun_at(), original.begun_at());
+                    assert_eq!(updated.libraries().len(), N_LIBRARIES);
+
+                    let unchanged = SequencingRunUpdate {
+                        id: *original.id(),
+                        ..Default::default()
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap();
+
+                    assert_eq!(unchanged.readable_id(), "SEQ-renamed");
+
+                    let err = SequencingRunUpdate {
+                        id: *original.id(),
+                        finished_at: Some("1999-12-31T00:00:00Z".parse().unwrap()),
+                        ..Default::default()
+                    }
+                    .write(tx)
+                    .await
+                    .unwrap_err();
+
+                    let Error::ConstraintViolation { constraint, .. } = err else {
+                        panic!("expected constraint violation, got {err:?}");
+                    };
+                    assert_eq!(constraint, "finished_after_begun");
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[test]
+    fn update_cannot_finish_before_it_began() {
+        let mut update = SequencingRunUpdate {
+            begun_at: Some("2025-02-01T00:00:00Z".parse().unwrap()),
+            finished_at: Some("2025-01-31T23:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        update.validate().unwrap_err();
+
+        update.begun_at = None;
+        update.validate().unwrap();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn libraries_on_run(#[future] mut db_conn: DbConnection) {
+        let run =
+            SequencingRunSummary::fetch_by_query(&SequencingRunQuery::default(), &mut db_conn)
+                .await
+                .unwrap()
+                .results
+                .remove(0);
+
+        let libraries: Vec<SequencingSubmission> =
+            sequencing_run::table::fetch_relatives(run.id(), &mut db_conn)
+                .await
+                .unwrap();
+
+        assert_eq!(libraries.len(), N_LIBRARIES);
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn runs_containing_library(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let library = LibrarySummary::fetch_by_query(&LibraryQuery::default(), tx)
+                        .await
+                        .unwrap()
+.results
+                        .remove(0);
+
+                    let submission = NewSequencingSubmission {
+                        library_id: *library.id(),
+                        fastq_paths: Some(vec!["/fastqs/SEQ-new".to_string()]),
+                        submitted_at: OffsetDateTime::now_utc(),
+                    };
+
+                    let new_run = new_run(vec![submission]).write(tx).await.unwrap();
+                    assert_eq!(new_run.libraries().len(), 1);
+
+                    let runs =
+                        <chromium_library::table as FetchRelatives<SequencingRunSummary>>::fetch_relatives(library.id(), tx)
+                            .await
+                            .unwrap();
+
+                    assert_eq!(runs.len(), 2);
+                    assert!(runs.iter().any(|r| r.id() == new_run.id()));
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
@@ -1 +1,316 @@
+use diesel::{dsl::InnerJoin, prelude::*};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    sample_metadata::SampleSummary,
+    specimen::{NewSpecimen, Specimen, SpecimenQuery, SpecimenSummary},
+};
+use scamplers_schema::{
+    sample_metadata,
+    specimen::{
+        self, embedded_in as embedded_in_col, id as id_col, metadata_id as metadata_id_col,
+        preserved_with as preserved_with_col, readable_id as readable_id_col, type_ as type_col,
+    },
+};
+use uuid::Uuid;
 
+use crate::{
+    db::{
+        error::Result,
+        model::{self, AsDieselQueryBase, FetchById},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewSpecimen {
+    type Returns = Specimen;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let id = diesel::insert_into(specimen::table)
+            .values(&self)
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        Specimen::fetch_by_id(&id, db_conn).await
+    }
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for SpecimenQuery
+where
+    id_col: SelectableExpression<QuerySource>,
+    readable_id_col: SelectableExpression<QuerySource>,
+    metadata_id_col: SelectableExpression<QuerySource>,
+    type_col: SelectableExpression<QuerySource>,
+    embedded_in_col: SelectableExpression<QuerySource>,
+    preserved_with_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            ids,
+            readable_id,
+            metadata_ids,
+            type_,
+            embedded_in,
+            preserved_with,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(readable_id) = readable_id {
+            query = query.and_condition(readable_id_col.ilike(readable_id.as_ilike()));
+        }
+
+        if !metadata_ids.is_empty() {
+            query = query.and_condition(metadata_id_col.eq_any(metadata_ids));
+        }
+
+        if let Some(type_) = type_ {
+            query = query.and_condition(type_col.eq(type_));
+        }
+
+        if let Some(embedded_in) = embedded_in {
+            query = query.and_condition(embedded_in_col.is_not_distinct_from(embedded_in));
+        }
+
+        if let Some(preserved_with) = preserved_with {
+            query = query.and_condition(preserved_with_col.is_not_distinct_from(preserved_with));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for SpecimenSummary {
+    type QueryBase = specimen::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        specimen::table
+    }
+}
+
+impl model::FetchByQuery for SpecimenSummary {
+    type QueryParams = SpecimenQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::specimen::SpecimenOrdinalColumn::ReadableId;
+
+        fetch_by_query!(query, [(ReadableId, readable_id_col)], id_col, db_conn)
+    }
+}
+
+impl AsDieselQueryBase for Specimen {
+    type QueryBase = InnerJoin<specimen::table, sample_metadata::table>;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        SpecimenSummary::as_diesel_query_base().inner_join(SampleSummary::as_diesel_query_base())
+    }
+}
+
+impl model::FetchById for Specimen {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        Ok(Self::as_diesel_query_base()
+            .select(Self::as_select())
+            .filter(id_col.eq(id))
+            .get_result(db_conn)
+            .await?)
+    }
+}
+
+impl model::FetchByReadableId for Specimen {
+    async fn fetch_by_readable_id(
+        readable_id: &str,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Self> {
+        Ok(Self::as_diesel_query_base()
+            .select(Self::as_select())
+            .filter(readable_id_col.eq(readable_id))
+            .get_result(db_conn)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        sample_metadata::{SampleQuery, SampleSummary},
+        specimen::{
+            EmbeddingMatrix, NewSpecimen, PreservationMethod, SpecimenOrdering,
+            SpecimenOrdinalColumn, SpecimenQuery, SpecimenSummary, SpecimenType,
+        },
+    };
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchById, FetchByQuery, FetchByReadableId, Write},
+        test_util::{DbConnection, N_SPECIMENS, db_conn, test_query},
+    };
+
+    use super::Specimen;
+
+    fn comparison_fn(s: &SpecimenSummary) -> String {
+        s.readable_id().clone()
+    }
+
+    fn new_specimen(
+        type_: SpecimenType,
+        embedded_in: Option<EmbeddingMatrix>,
+        preserved_with: Option<PreservationMethod>,
+    ) -> NewSpecimen {
+        NewSpecimen {
+            readable_id: "SP-new".to_string(),
+            metadata_id: Uuid::default(),
+            type_,
+            embedded_in,
+            preserved_with,
+            notes: None,
+        }
+    }
+
+    #[rstest]
+    #[case(SpecimenType::Tissue, None, None)]
+    #[case(SpecimenType::Fluid, None, Some(PreservationMethod::Freezing))]
+    #[case(
+        SpecimenType::Block,
+        Some(EmbeddingMatrix::Paraffin),
+        Some(PreservationMethod::FormaldehydeDerivativeFixation)
+    )]
+    #[case(
+        SpecimenType::Block,
+        Some(EmbeddingMatrix::OptimalCuttingTemperatureCompound),
+        Some(PreservationMethod::Freezing)
+    )]
+    #[case(
+        SpecimenType::Block,
+        Some(EmbeddingMatrix::CarboxymethylCellulose),
+        None
+    )]
+    fn valid_specimen(
+        #[case] type_: SpecimenType,
+        #[case] embedded_in: Option<EmbeddingMatrix>,
+        #[case] preserved_with: Option<PreservationMethod>,
+    ) {
+        new_specimen(type_, embedded_in, preserved_with)
+            .validate()
+            .unwrap();
+    }
+
+    #[rstest]
+    #[case(SpecimenType::Tissue, Some(EmbeddingMatrix::Paraffin), None)]
+    #[case(
+        SpecimenType::Fluid,
+        Some(EmbeddingMatrix::OptimalCuttingTemperatureCompound),
+        Some(PreservationMethod::Freezing)
+    )]
+    #[case(SpecimenType::Block, None, Some(PreservationMethod::Freezing))]
+    #[case(
+        SpecimenType::Block,
+        Some(EmbeddingMatrix::Paraffin),
+        Some(PreservationMethod::Freezing)
+    )]
+    #[case(SpecimenType::Block, Some(EmbeddingMatrix::Paraffin), None)]
+    #[case(
+        SpecimenType::Block,
+        Some(EmbeddingMatrix::CarboxymethylCellulose),
+        Some(PreservationMethod::DspFixation)
+    )]
+    fn invalid_specimen(
+        #[case] type_: SpecimenType,
+        #[case] embedded_in: Option<EmbeddingMatrix>,
+        #[case] preserved_with: Option<PreservationMethod>,
+    ) {
+        new_specimen(type_, embedded_in, preserved_with)
+            .validate()
+            .unwrap_err();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn default_specimen_query(#[future] db_conn: DbConnection) {
+        let expected = [(0, "specimen0"), (N_SPECIMENS - 1, "specimen9")];
+        test_query(
+            SpecimenQuery::default(),
+            db_conn,
+            N_SPECIMENS,
+            comparison_fn,
+            &expected,
+        )
+        .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn specific_specimen_query(#[future] db_conn: DbConnection) {
+        let query = SpecimenQuery {
+            type_: Some(SpecimenType::Block),
+            order_by: vec![SpecimenOrdering {
+                column: SpecimenOrdinalColumn::ReadableId,
+                descending: true,
+            }],
+            ..Default::default()
+        };
+
+        let expected = [(0, "specimen8"), (N_SPECIMENS / 2 - 1, "specimen0")];
+        test_query(query, db_conn, N_SPECIMENS / 2, comparison_fn, &expected).await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn new_specimen_by_readable_id(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let sample = SampleSummary::fetch_by_query(&SampleQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    let new_specimen = NewSpecimen {
+                        metadata_id: *sample.id(),
+                        ..new_specimen(
+                            SpecimenType::Block,
+                            Some(EmbeddingMatrix::Paraffin),
+                            Some(PreservationMethod::FormaldehydeDerivativeFixation),
+                        )
+                    };
+
+                    let created = new_specimen.write(tx).await.unwrap();
+                    assert_eq!(created.metadata().id(), sample.id());
+
+                    let by_readable_id =
+                        Specimen::fetch_by_readable_id("SP-new", tx).await.unwrap();
+                    let by_id = Specimen::fetch_by_id(created.id(), tx).await.unwrap();
+
+                    assert_eq!(by_readable_id.id(), created.id());
+                    assert_eq!(by_id.readable_id(), "SP-new");
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
@@ -0,0 +1,413 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use scamplers_core::model::{
+    Page, Pagination,
+    measurements::SuspensionMeasurement,
+    person::PersonSummary,
+    suspension::{NewSuspension, Suspension, SuspensionQuery, SuspensionSummary},
+};
+use scamplers_schema::{
+    suspension::{
+        self, biological_material as biological_material_col, created_at as created_at_col,
+        id as id_col, metadata_id as metadata_id_col, parent_specimen_id as parent_specimen_id_col,
+        readable_id as readable_id_col,
+    },
+    suspension_preparers::{
+        self, prepared_by as prepared_by_col, suspension_id as suspension_id_col,
+    },
+};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        error::Result,
+        model::{self, AsDieselQueryBase, FetchById, FetchRelatives, WriteRelatives},
+        util::{AsIlike, BoxedDieselExpression, NewBoxedDieselExpression},
+    },
+    fetch_by_query,
+};
+
+impl model::Write for NewSuspension {
+    type Returns = Suspension;
+
+    async fn write(self, db_conn: &mut AsyncPgConnection) -> Result<Self::Returns> {
+        let id = diesel::insert_into(suspension::table)
+            .values(&self)
+            .returning(id_col)
+            .get_result(db_conn)
+            .await?;
+
+        let preparers: Vec<_> = self
+            .preparer_ids
+            .iter()
+            .map(|p| (suspension_id_col.eq(id), prepared_by_col.eq(p)))
+            .collect();
+
+        diesel::insert_into(suspension_preparers::table)
+            .values(preparers)
+            .execute(db_conn)
+            .await?;
+
+        suspension::table::write_relatives(&id, self.measurements, db_conn).await?;
+
+        Suspension::fetch_by_id(&id, db_conn).await
+    }
+}
+
+impl<QuerySource> model::AsDieselFilter<QuerySource> for SuspensionQuery
+where
+    id_col: SelectableExpression<QuerySource>,
+    readable_id_col: SelectableExpression<QuerySource>,
+    metadata_id_col: SelectableExpression<QuerySource>,
+    parent_specimen_id_col: SelectableExpression<QuerySource>,
+    biological_material_col: SelectableExpression<QuerySource>,
+{
+    fn as_diesel_filter<'a>(&'a self) -> Option<BoxedDieselExpression<'a, QuerySource>>
+    where
+        QuerySource: 'a,
+    {
+        let Self {
+            ids,
+            readable_id,
+            metadata_ids,
+            parent_specimen_ids,
+            biological_material,
+            ..
+        } = self;
+
+        let mut query = BoxedDieselExpression::new_expression();
+
+        if !ids.is_empty() {
+            query = query.and_condition(id_col.eq_any(ids));
+        }
+
+        if let Some(readable_id) = readable_id {
+            query = query.and_condition(readable_id_col.ilike(readable_id.as_ilike()));
+        }
+
+        // These columns are nullable, so `IN` can evaluate to NULL, which we treat as a non-match
+        if !metadata_ids.is_empty() {
+            query = query.and_condition(
+                metadata_id_col
+                    .eq_any(metadata_ids)
+                    .is_not_distinct_from(true),
+            );
+        }
+
+        if !parent_specimen_ids.is_empty() {
+            query = query.and_condition(
+                parent_specimen_id_col
+                    .eq_any(parent_specimen_ids)
+                    .is_not_distinct_from(true),
+            );
+        }
+
+        if let Some(biological_material) = biological_material {
+            query = query.and_condition(biological_material_col.eq(biological_material));
+        }
+
+        query.build()
+    }
+}
+
+impl AsDieselQueryBase for SuspensionSummary {
+    type QueryBase = suspension::table;
+
+    fn as_diesel_query_base() -> Self::QueryBase {
+        suspension::table
+    }
+}
+
+impl model::FetchById for SuspensionSummary {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        Ok(Self::as_diesel_query_base()
+            .find(id)
+            .select(Self::as_select())
+            .first(db_conn)
+            .await?)
+    }
+}
+
+impl model::FetchByQuery for SuspensionSummary {
+    type QueryParams = SuspensionQuery;
+
+    async fn fetch_by_query(
+        query: &Self::QueryParams,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Page<Self>> {
+        use scamplers_core::model::suspension::SuspensionOrdinalColumn::{CreatedAt, ReadableId};
+
+        fetch_by_query!(
+            query,
+            [(ReadableId, readable_id_col), (CreatedAt, created_at_col)],
+            id_col,
+            db_conn
+        )
+    }
+}
+
+impl model::FetchRelatives<PersonSummary> for suspension::table {
+    type Id = Uuid;
+
+    async fn fetch_relatives(
+        suspension_id: &Self::Id,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<PersonSummary>> {
+        let preparers = suspension_preparers::table
+            .filter(suspension_id_col.eq(suspension_id))
+            .inner_join(PersonSummary::as_diesel_query_base())
+            .select(PersonSummary::as_select())
+            .load(db_conn)
+            .await?;
+
+        Ok(preparers)
+    }
+}
+
+impl model::FetchById for Suspension {
+    type Id = Uuid;
+
+    async fn fetch_by_id(id: &Self::Id, db_conn: &mut AsyncPgConnection) -> Result<Self> {
+        let summary = SuspensionSummary::fetch_by_id(id, db_conn).await?;
+        let preparers =
+            <suspension::table as FetchRelatives<PersonSummary>>::fetch_relatives(id, db_conn)
+                .await?;
+        let measurements =
+            <suspension::table as FetchRelatives<SuspensionMeasurement>>::fetch_relatives(
+                id, db_conn,
+            )
+            .await?;
+
+        Ok(Self::new(summary, preparers, measurements))
+    }
+}
+
+impl model::FetchByReadableId for Suspension {
+    async fn fetch_by_readable_id(
+        readable_id: &str,
+        db_conn: &mut AsyncPgConnection,
+    ) -> Result<Self> {
+        let id = suspension::table
+            .select(id_col)
+            .filter(readable_id_col.eq(readable_id))
+            .get_result(db_conn)
+            .await?;
+
+        Self::fetch_by_id(&id, db_conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use scamplers_core::model::{
+        measurements::{Measurement, MeasurementData, NewSuspensionMeasurement},
+        person::{PersonQuery, PersonSummary},
+        specimen::{SpecimenQuery, SpecimenSummary},
+        suspension::{
+            BiologicalMaterial, NewSuspension, SuspensionOrdering, SuspensionOrdinalColumn,
+            SuspensionQuery, SuspensionSummary,
+        },
+        units::{Quantity, Unit},
+    };
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::db::{
+        error::Error,
+        model::{FetchByQuery, Write},
+        test_util::{DbConnection, N_SUSPENSIONS, db_conn, test_query},
+    };
+
+    fn comparison_fn(s: &SuspensionSummary) -> String {
+        s.readable_id().clone()
+    }
+
+    fn new_suspension(
+        metadata_id: Option<Uuid>,
+        parent_specimen_id: Option<Uuid>,
+        created_at: Option<OffsetDateTime>,
+    ) -> NewSuspension {
+        NewSuspension {
+            readable_id: "SUS-new".to_string(),
+            metadata_id,
+            parent_specimen_id,
+            biological_material: BiologicalMaterial::Nuclei,
+            created_at,
+            pooled_into_id: None,
+            multiplexing_tag_id: None,
+            lysis_duration_min: Some(5.0),
+            target_cell_recovery: 10_000.0,
+            target_reads_per_cell: 50_000,
+            notes: None,
+            preparer_ids: vec![Uuid::default()],
+            measurements: vec![],
+        }
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn default_suspension_query(#[future] db_conn: DbConnection) {
+        let expected = [(0, "suspension0"), (N_SUSPENSIONS - 1, "suspension9")];
+        test_query(
+            SuspensionQuery::default(),
+            db_conn,
+            N_SUSPENSIONS,
+            comparison_fn,
+            &expected,
+        )
+        .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn specific_suspension_query(#[future] db_conn: DbConnection) {
+        let query = SuspensionQuery {
+            biological_material: Some(BiologicalMaterial::Nuclei),
+            order_by: vec![SuspensionOrdering {
+                column: SuspensionOrdinalColumn::ReadableId,
+                descending: true,
+            }],
+            ..Default::default()
+        };
+
+        let expected = [(0, "suspension9"), (N_SUSPENSIONS / 2 - 1, "suspension1")];
+        test_query(query, db_conn, N_SUSPENSIONS / 2, comparison_fn, &expected).await;
+    }
+
+    #[test]
+    fn suspension_rules_validated_before_insert() {
+        let created_at = Some(OffsetDateTime::now_utc());
+
+        new_suspension(Some(Uuid::default()), None, None)
+            .validate()
+            .unwrap();
+        new_suspension(None, Some(Uuid::default()), created_at)
+            .validate()
+            .unwrap();
+
+        // has_metadata
+        new_suspension(Some(Uuid::default()), Some(Uuid::default()), created_at)
+            .validate()
+            .unwrap_err();
+        new_suspension(None, None, None).validate().unwrap_err();
+
+        // has_creation_time
+        new_suspension(Some(Uuid::default()), None, created_at)
+            .validate()
+            .unwrap_err();
+        new_suspension(None, Some(Uuid::default()), None)
+            .validate()
+            .unwrap_err();
+
+        // pooling_is_correctly_specified
+        let mut pooled = new_suspension(Some(Uuid::default()), None, None);
+        pooled.pooled_into_id = Some(Uuid::default());
+        pooled.validate().unwrap_err();
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn derived_suspension_with_measurements(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let specimen = SpecimenSummary::fetch_by_query(&SpecimenQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    let preparer = PersonSummary::fetch_by_query(&PersonQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    let measurement = NewSuspensionMeasurement {
+                        suspension_id: None,
+                        measured_by: *preparer.id(),
+                        data: MeasurementData {
+                            measured_at: OffsetDateTime::now_utc(),
+                            instrument_name: None,
+                            measurement: Measurement::CellConcentration {
+                                concentration: Quantity::new(1200.0, Unit::CellsPerMicroliter),
+                            },
+                        },
+                    };
+
+                    let new_suspension = NewSuspension {
+                        preparer_ids: vec![*preparer.id()],
+                        measurements: vec![measurement],
+                        ..new_suspension(
+                            None,
+                            Some(*specimen.id()),
+                            Some(OffsetDateTime::now_utc()),
+                        )
+                    };
+
+                    let suspension = new_suspension.write(tx).await.unwrap();
+
+                    assert_eq!(suspension.parent_specimen_id(), &Some(*specimen.id()));
+                    assert_eq!(suspension.preparers().len(), 1);
+                    assert_eq!(suspension.preparers()[0].id(), preparer.id());
+                    assert_eq!(suspension.measurements().len(), 1);
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+
+    #[rstest]
+    #[awt]
+    #[tokio::test]
+    async fn check_violation_is_structured(#[future] mut db_conn: DbConnection) {
+        db_conn
+            .test_transaction::<_, Error, _>(|tx| {
+                async move {
+                    let specimen = SpecimenSummary::fetch_by_query(&SpecimenQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    let preparer = PersonSummary::fetch_by_query(&PersonQuery::default(), tx)
+                        .await
+                        .unwrap()
+                        .results
+                        .remove(0);
+
+                    // Skip validation so that the database is the one to reject this
+                    let invalid = NewSuspension {
+                        preparer_ids: vec![*preparer.id()],
+                        ..new_suspension(None, Some(*specimen.id()), None)
+                    };
+
+                    let err = invalid.write(tx).await.unwrap_err();
+                    let Error::ConstraintViolation {
+                        entity, constraint, ..
+                    } = err
+                    else {
+                        panic!("expected constraint violation, found {err:?}");
+                    };
+
+                    assert_eq!(entity, "suspension");
+                    assert_eq!(constraint, "has_creation_time");
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await;
+    }
+}
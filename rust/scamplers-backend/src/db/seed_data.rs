@@ -1,11 +1,14 @@
 use admin::NewAdmin;
 use anyhow::Context;
+use camino::{Utf8Path, Utf8PathBuf};
+use chemistry::NewChemistry;
 use diesel_async::AsyncPgConnection;
 use garde::Validate;
-use index_set::IndexSetFileUrl;
+use index_set::IndexSetFile;
 use scamplers_core::model::institution::NewInstitution;
 use serde::Deserialize;
 mod admin;
+mod chemistry;
 mod index_set;
 
 use super::model::Write;
@@ -14,7 +17,13 @@ use super::model::Write;
 pub struct SeedData {
     institution: NewInstitution,
     app_admin: NewAdmin,
-    index_set_urls: Vec<IndexSetFileUrl>,
+    #[serde(alias = "index_set_urls")]
+    index_set_files: Vec<IndexSetFile>,
+    /// Where downloaded index set files are kept, so that later startups don't need to download them again
+    #[serde(default)]
+    index_set_cache_dir: Option<Utf8PathBuf>,
+    #[serde(default)]
+    chemistries: Vec<NewChemistry>,
 }
 
 impl SeedData {
@@ -27,7 +36,9 @@ impl SeedData {
         let Self {
             institution,
             app_admin,
-            index_set_urls,
+            index_set_files,
+            index_set_cache_dir,
+            chemistries,
         } = self;
 
         let institutions_result = institution.write(db_conn).await;
@@ -41,7 +52,20 @@ impl SeedData {
         app_admin.validate()?;
         app_admin.write(db_conn).await?;
 
-        download_and_insert_index_sets(db_conn, http_client, &index_set_urls).await?;
+        index_set_files.validate()?;
+        load_and_insert_index_sets(
+            db_conn,
+            http_client,
+            index_set_files,
+            index_set_cache_dir.as_deref(),
+        )
+        .await?;
+
+        chemistries.validate()?;
+        chemistries
+            .write(db_conn)
+            .await
+            .context("failed to insert chemistries into database")?;
 
         Ok(())
     }
@@ -49,21 +73,22 @@ impl SeedData {
 
 // We use anyhow::Result here because we just want to know what went wrong, we
 // don't care about serializing structured errors to a client
-async fn download_and_insert_index_sets(
+async fn load_and_insert_index_sets(
     db_conn: &mut AsyncPgConnection,
     http_client: reqwest::Client,
-    file_urls: &[IndexSetFileUrl],
+    files: Vec<IndexSetFile>,
+    cache_dir: Option<&Utf8Path>,
 ) -> anyhow::Result<()> {
-    let downloads = file_urls
-        .iter()
-        .map(|url| url.clone().download(http_client.clone()));
-    let index_sets = futures::future::try_join_all(downloads)
+    let loads = files
+        .into_iter()
+        .map(|file| file.load(http_client.clone(), cache_dir));
+    let index_sets = futures::future::try_join_all(loads)
         .await
-        .context("failed to download index set files")?;
+        .context("failed to load index set files")?;
 
-    // A for-loop is fine because this is like 10 URLs max, and each of these is a
+    // A for-loop is fine because this is like 10 files max, and each of these is a
     // bulk insert
-    for sets in index_sets {
+    for sets in index_sets.into_iter().flatten() {
         sets.write(db_conn)
             .await
             .context("failed to insert index sets into database")?;
@@ -0,0 +1,63 @@
+use diesel::{prelude::*, upsert::excluded};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use garde::Validate;
+use scamplers_schema::chemistry;
+use serde::Deserialize;
+
+use super::Write;
+
+/// One entry of Cell Ranger's `chemistry_defs.json`, along with the library types it can produce and the argument
+/// Cell Ranger expects for it. `definition` is stored verbatim.
+#[derive(Deserialize, Validate, Insertable, Clone)]
+#[diesel(table_name = chemistry, check_for_backend(Pg))]
+#[garde(allow_unvalidated)]
+pub(super) struct NewChemistry {
+    #[garde(length(min = 1))]
+    name: String,
+    #[garde(length(min = 1))]
+    description: String,
+    #[garde(custom(is_json_object))]
+    definition: serde_json::Value,
+    #[garde(length(min = 1), inner(length(min = 1)))]
+    library_types: Vec<String>,
+    #[garde(length(min = 1))]
+    cmdline: String,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_json_object(definition: &serde_json::Value, _: &()) -> garde::Result {
+    if definition.is_object() {
+        Ok(())
+    } else {
+        Err(garde::Error::new(
+            "chemistry definition must be a JSON object",
+        ))
+    }
+}
+
+impl Write for Vec<NewChemistry> {
+    type Returns = ();
+
+    // Chemistries are keyed by name, so re-seeding replaces a definition rather than failing on it
+    async fn write(
+        self,
+        db_conn: &mut AsyncPgConnection,
+    ) -> super::super::error::Result<Self::Returns> {
+        use chemistry::{cmdline, definition, description, library_types, name};
+
+        diesel::insert_into(chemistry::table)
+            .values(self)
+            .on_conflict(name)
+            .do_update()
+            .set((
+                description.eq(excluded(description)),
+                definition.eq(excluded(definition)),
+                library_types.eq(excluded(library_types)),
+                cmdline.eq(excluded(cmdline)),
+            ))
+            .execute(db_conn)
+            .await?;
+
+        Ok(())
+    }
+}
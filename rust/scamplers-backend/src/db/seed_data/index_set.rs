@@ -1,15 +1,29 @@
 use std::{collections::HashMap, fmt::Display, hash::Hash, sync::LazyLock};
 
+use anyhow::Context;
+use camino::{Utf8Path, Utf8PathBuf};
+
 use diesel::{expression::AsExpression, prelude::*, sql_types};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use garde::{Validate, error::PathComponentKind};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use tokio::fs;
 use url::Url;
+use uuid::Uuid;
 
 use super::Write;
 use scamplers_schema::{dual_index_set, index_kit, single_index_set};
 
+/// Where to find a 10x Genomics index set file. A string that isn't an absolute URL is treated as a local path, which
+/// may point either to a single file or to a directory of them (such as a copy bundled with the deployment).
+#[derive(Deserialize, Validate, Clone, Serialize, Debug)]
+#[serde(untagged)]
+pub(super) enum IndexSetFile {
+    Url(#[garde(dive)] IndexSetFileUrl),
+    Path(#[garde(skip)] Utf8PathBuf),
+}
+
 #[derive(Deserialize, Validate, Clone, Serialize, Debug)]
 #[serde(transparent)]
 pub(super) struct IndexSetFileUrl(#[garde(custom(is_10x_genomics_url))] Url);
@@ -30,16 +44,122 @@ fn is_10x_genomics_url(url: &Url, _: &()) -> garde::Result {
 }
 
 // `anyhow::Result` is fine here because this isn't user-facing code
+impl IndexSetFile {
+    pub(super) async fn load(
+        self,
+        http_client: reqwest::Client,
+        cache_dir: Option<&Utf8Path>,
+    ) -> anyhow::Result<Vec<IndexSets>> {
+        match self {
+            Self::Url(url) => Ok(vec![url.download(http_client, cache_dir).await?]),
+            Self::Path(path) if path.is_dir() => {
+                let mut files: Vec<_> = path
+                    .read_dir_utf8()
+                    .with_context(|| format!("failed to read index set directory {path}"))?
+                    .map(|entry| entry.map(camino::Utf8DirEntry::into_path))
+                    .collect::<Result<_, _>>()?;
+                files.retain(|f| f.extension() == Some("json"));
+                files.sort();
+
+                let mut index_sets = Vec::with_capacity(files.len());
+                for f in &files {
+                    index_sets.push(read_index_set_file(f).await?);
+                }
+
+                Ok(index_sets)
+            }
+            Self::Path(path) => Ok(vec![read_index_set_file(&path).await?]),
+        }
+    }
+}
+
 impl IndexSetFileUrl {
-    pub(super) async fn download(self, http_client: reqwest::Client) -> anyhow::Result<IndexSets> {
+    // A file that was downloaded once is read from the cache from then on, so that restarting doesn't depend on the
+    // CDN being reachable. 10x Genomics versions these URLs, so a cached file never goes stale. One that can't be read
+    // is downloaded again and replaced.
+    async fn download(
+        self,
+        http_client: reqwest::Client,
+        cache_dir: Option<&Utf8Path>,
+    ) -> anyhow::Result<IndexSets> {
         let Self(url) = self;
 
-        let index_set: IndexSets = http_client.get(url.clone()).send().await?.json().await?;
+        let cached_path = cache_dir.map(|dir| dir.join(cache_file_name(&url)));
+        if let Some(cached_path) = &cached_path
+            && fs::metadata(cached_path)
+                .await
+                .is_ok_and(|metadata| metadata.is_file())
+        {
+            match read_index_set_file(cached_path).await {
+                Ok(index_sets) => return Ok(index_sets),
+                Err(error) => tracing::warn!(
+                    cached_path = cached_path.as_str(),
+                    error = format!("{error:#}"),
+                    "discarding unreadable cached index set file"
+                ),
+            }
+        }
+
+        let contents = http_client
+            .get(url.clone())
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let index_sets = parse_index_sets(&contents)
+            .with_context(|| format!("invalid index set file at {url}"))?;
+
+        if let Some(cached_path) = cached_path {
+            write_cache_file(&cached_path, contents)
+                .await
+                .with_context(|| format!("failed to cache index set file at {cached_path}"))?;
+        }
+
+        Ok(index_sets)
+    }
+}
+
+// The file is written next to its final location and then renamed into place, so an interrupted write can't leave a
+// truncated file behind
+async fn write_cache_file(path: &Utf8Path, contents: String) -> anyhow::Result<()> {
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        anyhow::bail!("{path} is not a file path");
+    };
+    fs::create_dir_all(dir).await?;
 
-        index_set.validate()?;
+    let temp_path = dir.join(format!(".{file_name}.{}.tmp", Uuid::now_v7()));
+    fs::write(&temp_path, contents).await?;
 
-        Ok(index_set)
+    if let Err(error) = fs::rename(&temp_path, path).await {
+        fs::remove_file(&temp_path).await.ok();
+        return Err(error.into());
     }
+
+    Ok(())
+}
+
+// The last path segment is the file name 10x Genomics gave the file (e.g. `Dual_Index_Kit_TT_Set_A.json`), but we keep
+// the whole path so that two versions of a file can't collide
+fn cache_file_name(url: &Url) -> String {
+    url.path()
+        .trim_start_matches('/')
+        .replace(['/', '%', ' '], "_")
+}
+
+async fn read_index_set_file(path: &Utf8Path) -> anyhow::Result<IndexSets> {
+    let contents = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("failed to read index set file {path}"))?;
+
+    parse_index_sets(&contents).with_context(|| format!("invalid index set file {path}"))
+}
+
+fn parse_index_sets(contents: &str) -> anyhow::Result<IndexSets> {
+    let index_sets: IndexSets = serde_json::from_str(contents)?;
+    index_sets.validate()?;
+
+    Ok(index_sets)
 }
 
 #[derive(Deserialize, Validate)]
@@ -266,3 +386,107 @@ impl Write for HashMap<IndexSetName, NewDualIndexSet> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use camino::Utf8PathBuf;
+    use serde_json::json;
+    use url::Url;
+    use uuid::Uuid;
+
+    use super::{IndexSetFile, IndexSets, cache_file_name};
+
+    const SINGLE_INDEX_SETS: &str =
+        r#"[["SI-GA-A1", ["GGTTTACT", "CTAAACGG", "TCGGCGTC", "AACCGTAA"]]]"#;
+
+    fn temp_dir() -> Utf8PathBuf {
+        let dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("scamplers-index-sets-{}", Uuid::now_v7()));
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn distinguish_urls_from_paths() {
+        let files: Vec<IndexSetFile> = serde_json::from_value(json!([
+            "https://cdn.10xgenomics.com/Single_Index_Kit_N_Set_A.json",
+            "/opt/scamplers/index_sets",
+            "index_sets/Dual_Index_Kit_TT_Set_A.json"
+        ]))
+        .unwrap();
+
+        assert!(matches!(files[0], IndexSetFile::Url(_)));
+        assert!(matches!(files[1], IndexSetFile::Path(_)));
+        assert!(matches!(files[2], IndexSetFile::Path(_)));
+    }
+
+    #[tokio::test]
+    async fn load_index_set_directory() {
+        let dir = temp_dir();
+        fs::write(dir.join("Single_Index_Kit_GA.json"), SINGLE_INDEX_SETS).unwrap();
+        fs::write(dir.join("README.txt"), "not an index set file").unwrap();
+
+        let index_sets = IndexSetFile::Path(dir.clone())
+            .load(reqwest::Client::new(), None)
+            .await
+            .unwrap();
+
+        assert!(matches!(index_sets.as_slice(), [IndexSets::Single(_)]));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn cached_download_skips_network() {
+        let url: Url = "https://cdn.10xgenomics.com/raw/upload/v1/Single_Index_Kit_GA.json"
+            .parse()
+            .unwrap();
+
+        let dir = temp_dir();
+        fs::write(dir.join(cache_file_name(&url)), SINGLE_INDEX_SETS).unwrap();
+
+        // A client that can't make any requests
+        let http_client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all("http://0.0.0.0:0").unwrap())
+            .build()
+            .unwrap();
+
+        let file: IndexSetFile = serde_json::from_value(json!(url)).unwrap();
+        file.load(http_client, Some(&dir)).await.unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn corrupt_cached_download_is_downloaded_again() {
+        let url: Url = "https://cdn.10xgenomics.com/raw/upload/v1/Single_Index_Kit_GA.json"
+            .parse()
+            .unwrap();
+
+        let dir = temp_dir();
+        fs::write(
+            dir.join(cache_file_name(&url)),
+            &SINGLE_INDEX_SETS[..SINGLE_INDEX_SETS.len() / 2],
+        )
+        .unwrap();
+
+        // The same client that can't make any requests, so that a download attempt shows up as a network error
+        let http_client = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all("http://0.0.0.0:0").unwrap())
+            .build()
+            .unwrap();
+
+        let file: IndexSetFile = serde_json::from_value(json!(url)).unwrap();
+        let Err(error) = file.load(http_client, Some(&dir)).await else {
+            panic!("expected the download to fail");
+        };
+
+        assert!(error.downcast_ref::<reqwest::Error>().is_some());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -16,10 +16,19 @@ use pretty_assertions::assert_eq;
 use rand::seq::IndexedRandom;
 use rstest::fixture;
 use scamplers_core::model::{
+    cdna::{Cdna, NewCdna},
+    chromium::{ChipType, NewChipLoading, NewChromiumRun, NewGems},
     institution::NewInstitution,
     lab::NewLab,
+    library::NewLibrary,
     person::{NewPerson, Person},
+    sample_metadata::{NewSampleMetadata, Sample, Species},
+    sequencing_run::{NewSequencingRun, NewSequencingSubmission},
+    specimen::{EmbeddingMatrix, NewSpecimen, PreservationMethod, SpecimenType},
+    suspension::{BiologicalMaterial, NewSuspension, Suspension},
+    units::{Quantity, Unit},
 };
+use time::OffsetDateTime;
 use tokio::sync::OnceCell;
 use uuid::Uuid;
 
@@ -29,6 +38,15 @@ pub const N_INSTITUTIONS: usize = 20;
 pub const N_PEOPLE: usize = 100;
 pub const N_LABS: usize = 25;
 pub const N_LAB_MEMBERS: usize = 5;
+pub const N_SAMPLES: usize = 20;
+pub const N_SPECIMENS: usize = N_SAMPLES;
+pub const N_SUSPENSIONS: usize = 10;
+pub const N_GEMS: usize = 2;
+pub const N_CDNA: usize = 4;
+pub const TEST_CHEMISTRY: &str = "SC3Pv3";
+pub const TEST_SINGLE_INDEX_SET: &str = "SI-GA-A1";
+pub const TEST_DUAL_INDEX_SET: &str = "SI-TT-A1";
+pub const N_LIBRARIES: usize = 2;
 
 struct TestState {
     _container: DevContainer,
@@ -65,6 +83,8 @@ impl TestState {
             let new_institution = NewInstitution {
                 id: Uuid::now_v7(),
                 name: format!("institution{i}"),
+                ror_id: None,
+                country: None,
             }
             .write(db_conn)
             .await
@@ -116,7 +136,285 @@ impl TestState {
 
             labs.push(new_lab);
         }
+
+        let received_at: OffsetDateTime = "2025-01-01T00:00:00Z".parse().unwrap();
+        let mut samples = Vec::with_capacity(N_SAMPLES);
+        for i in 0..N_SAMPLES {
+            let lab = labs.choose(rng).unwrap();
+
+            let new_sample = NewSampleMetadata {
+                name: format!("sample{i}"),
+                submitted_by: *lab.pi().id(),
+                lab_id: *lab.id(),
+                received_at,
+                species: vec![Species::MusMusculus],
+                tissue: "liver".to_string(),
+                committee_approvals: vec![],
+                notes: None,
+                returned_at: None,
+                returned_by: None,
+            }
+            .write(db_conn)
+            .await
+            .unwrap();
+
+            samples.push(new_sample);
+        }
+
+        let suspensions = populate_sample_processing(db_conn, &samples, &people, received_at).await;
+        let cdna = populate_chromium(db_conn, &suspensions, &people, received_at).await;
+        populate_libraries(db_conn, &cdna, &people, received_at).await;
+    }
+}
+
+async fn populate_sample_processing(
+    db_conn: &mut AsyncPgConnection,
+    samples: &[Sample],
+    people: &[Person],
+    created_at: OffsetDateTime,
+) -> Vec<Suspension> {
+    let rng = &mut rand::rng();
+
+    // Half of the specimens are FFPE blocks and the other half are fresh tissues
+    let mut specimens = Vec::with_capacity(N_SPECIMENS);
+    for (i, sample) in samples.iter().enumerate().take(N_SPECIMENS) {
+        let (type_, embedded_in, preserved_with) = if i % 2 == 0 {
+            (
+                SpecimenType::Block,
+                Some(EmbeddingMatrix::Paraffin),
+                Some(PreservationMethod::FormaldehydeDerivativeFixation),
+            )
+        } else {
+            (SpecimenType::Tissue, None, None)
+        };
+
+        let new_specimen = NewSpecimen {
+            readable_id: format!("specimen{i}"),
+            metadata_id: *sample.id(),
+            type_,
+            embedded_in,
+            preserved_with,
+            notes: None,
+        }
+        .write(db_conn)
+        .await
+        .unwrap();
+
+        specimens.push(new_specimen);
+    }
+
+    // Half of the suspensions are cells made directly from a sample and the other half are nuclei derived from a
+    // specimen
+    let mut suspensions = Vec::with_capacity(N_SUSPENSIONS);
+    for i in 0..N_SUSPENSIONS {
+        let (metadata_id, parent_specimen_id, biological_material, created_at) = if i % 2 == 0 {
+            (
+                Some(*samples[i].id()),
+                None,
+                BiologicalMaterial::Cells,
+                None,
+            )
+        } else {
+            (
+                None,
+                Some(*specimens[i].id()),
+                BiologicalMaterial::Nuclei,
+                Some(created_at),
+            )
+        };
+
+        let new_suspension = NewSuspension {
+            readable_id: format!("suspension{i}"),
+            metadata_id,
+            parent_specimen_id,
+            biological_material,
+            created_at,
+            pooled_into_id: None,
+            multiplexing_tag_id: None,
+            lysis_duration_min: None,
+            target_cell_recovery: 10_000.0,
+            target_reads_per_cell: 50_000,
+            notes: None,
+            preparer_ids: vec![*people.choose(rng).unwrap().id()],
+            measurements: vec![],
+        }
+        .write(db_conn)
+        .await
+        .unwrap();
+
+        suspensions.push(new_suspension);
+    }
+
+    suspensions
+}
+
+// One Chromium run whose GEMs each have a single suspension loaded, and cDNA made alternately from each GEMs by the first
+// two people, stored alternately in two freezers
+async fn populate_chromium(
+    db_conn: &mut AsyncPgConnection,
+    suspensions: &[Suspension],
+    people: &[Person],
+    run_at: OffsetDateTime,
+) -> Vec<Cdna> {
+    use diesel::prelude::*;
+    use diesel_async::RunQueryDsl;
+    use scamplers_schema::chemistry;
+
+    diesel::insert_into(chemistry::table)
+        .values((
+            chemistry::name.eq(TEST_CHEMISTRY),
+            chemistry::description.eq("Single Cell 3' v3"),
+            chemistry::definition.eq(serde_json::json!({})),
+            chemistry::library_types.eq(vec!["Gene Expression"]),
+            chemistry::cmdline.eq("--chemistry=SC3Pv3"),
+        ))
+        .execute(db_conn)
+        .await
+        .unwrap();
+
+    let gems = suspensions
+        .iter()
+        .take(N_GEMS)
+        .enumerate()
+        .map(|(i, s)| NewGems {
+            readable_id: format!("gems{i}"),
+            chemistry: Some(TEST_CHEMISTRY.to_string()),
+            loading: vec![NewChipLoading {
+                suspension_id: Some(*s.id()),
+                multiplexed_suspension_id: None,
+                suspension_volume_loaded: Quantity::new(10.0, Unit::Microliter),
+                buffer_volume_loaded: Quantity::new(30.0, Unit::Microliter),
+                notes: None,
+            }],
+        })
+        .collect();
+
+    let run = NewChromiumRun {
+        readable_id: "chromium_run0".to_string(),
+        chip: ChipType::ChipJ,
+        run_at,
+        run_by: *people[0].id(),
+        succeeded: true,
+        notes: None,
+        gems,
+    }
+    .write(db_conn)
+    .await
+    .unwrap();
+
+    let mut cdna = Vec::with_capacity(N_CDNA);
+    for i in 0..N_CDNA {
+        let new_cdna = NewCdna {
+            library_type: "Gene Expression".to_string(),
+            readable_id: format!("cdna{i}"),
+            prepared_at: run_at,
+            gems_id: *run.gems()[i % N_GEMS].id(),
+            n_amplification_cycles: 11,
+            storage_location: Some(format!("freezer {}", i % 2)),
+            notes: None,
+            preparer_ids: vec![*people[i % 2].id()],
+            measurements: vec![],
+        }
+        .write(db_conn)
+        .await
+        .unwrap();
+
+        cdna.push(new_cdna);
+    }
+
+    cdna
+}
+
+// One single and one dual index set, of which only the dual index set's kit is prescribed for the test chemistry's gene
+// expression libraries. Every library is submitted to the same sequencing run
+async fn populate_libraries(
+    db_conn: &mut AsyncPgConnection,
+    cdna: &[Cdna],
+    people: &[Person],
+    prepared_at: OffsetDateTime,
+) {
+    use diesel::prelude::*;
+    use diesel_async::RunQueryDsl;
+    use scamplers_schema::{
+        dual_index_set, index_kit, library_type_specification, single_index_set,
+    };
+
+    diesel::insert_into(index_kit::table)
+        .values(vec![index_kit::name.eq("GA"), index_kit::name.eq("TT")])
+        .execute(db_conn)
+        .await
+        .unwrap();
+
+    diesel::insert_into(single_index_set::table)
+        .values((
+            single_index_set::name.eq(TEST_SINGLE_INDEX_SET),
+            single_index_set::kit.eq("GA"),
+            single_index_set::well.eq("A1"),
+            single_index_set::sequences.eq(vec!["GGTTTACT", "CTAAACGG", "TCGGCGTC", "AACCGTAA"]),
+        ))
+        .execute(db_conn)
+        .await
+        .unwrap();
+
+    diesel::insert_into(dual_index_set::table)
+        .values((
+            dual_index_set::name.eq(TEST_DUAL_INDEX_SET),
+            dual_index_set::kit.eq("TT"),
+            dual_index_set::well.eq("A1"),
+            dual_index_set::index_i7.eq("GTAACATGCG"),
+            dual_index_set::index2_workflow_a_i5.eq("AGTGTTACCT"),
+            dual_index_set::index2_workflow_b_i5.eq("AGGTAACACT"),
+        ))
+        .execute(db_conn)
+        .await
+        .unwrap();
+
+    diesel::insert_into(library_type_specification::table)
+        .values((
+            library_type_specification::chemistry.eq(TEST_CHEMISTRY),
+            library_type_specification::library_type.eq("Gene Expression"),
+            library_type_specification::index_kit.eq("TT"),
+            library_type_specification::cdna_volume_l.eq(10.0),
+            library_type_specification::library_volume_l.eq(35.0),
+        ))
+        .execute(db_conn)
+        .await
+        .unwrap();
+
+    let mut submissions = Vec::with_capacity(N_LIBRARIES);
+    for (i, cdna) in cdna.iter().enumerate().take(N_LIBRARIES) {
+        let library = NewLibrary {
+            readable_id: format!("library{i}"),
+            cdna_id: *cdna.id(),
+            index_set_name: TEST_DUAL_INDEX_SET.to_string(),
+            number_of_sample_index_pcr_cycles: 12,
+            target_reads_per_cell: 50_000,
+            prepared_at,
+            notes: None,
+            preparer_ids: vec![*people[0].id()],
+            measurements: vec![],
+        }
+        .write(db_conn)
+        .await
+        .unwrap();
+
+        submissions.push(NewSequencingSubmission {
+            library_id: *library.id(),
+            fastq_paths: Some(vec![format!("/fastqs/library{i}")]),
+            submitted_at: prepared_at,
+        });
+    }
+
+    NewSequencingRun {
+        readable_id: "sequencing_run0".to_string(),
+        begun_at: prepared_at,
+        finished_at: prepared_at,
+        notes: None,
+        libraries: submissions,
     }
+    .write(db_conn)
+    .await
+    .unwrap();
 }
 
 static TEST_STATE: OnceCell<TestState> = OnceCell::const_new();
@@ -144,7 +442,7 @@ pub async fn test_query<Record, Value1, Value2>(
     db_conn
         .test_transaction::<_, crate::db::error::Error, _>(|conn| {
             async move {
-                let records = Record::fetch_by_query(&query, conn).await.unwrap();
+                let records = Record::fetch_by_query(&query, conn).await.unwrap().results;
                 assert_eq!(records.len(), expected_len);
 
                 for (i, expected_val) in expected {
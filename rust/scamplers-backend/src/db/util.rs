@@ -1,4 +1,4 @@
-use diesel::{pg::Pg, prelude::*, sql_types};
+use diesel::{define_sql_function, expression::AsExpression, pg::Pg, prelude::*, sql_types};
 use diesel_async::{AsyncPgConnection, RunQueryDsl, pooled_connection::deadpool::Object};
 
 pub(super) type BoxedDieselExpression<'a, QuerySource> =
@@ -81,11 +81,46 @@ impl AsIlike for String {
     }
 }
 
+diesel::infix_operator!(IsSimilarTo, " % ", backend: Pg);
+
+define_sql_function! {fn similarity(a: sql_types::Text, b: sql_types::Text) -> sql_types::Float4}
+define_sql_function! {fn set_config(name: sql_types::Text, value: sql_types::Text, is_local: sql_types::Bool) -> sql_types::Text}
+
+/// Trigram similarity from `pg_trgm`. Unlike `similarity`, the `%` operator can use a trigram index.
+pub(super) trait SimilarityExpressionMethods:
+    Expression<SqlType = sql_types::Text> + Sized
+{
+    fn trigram_similar_to<T>(self, other: T) -> IsSimilarTo<Self, T::Expression>
+    where
+        T: AsExpression<sql_types::Text>,
+    {
+        IsSimilarTo::new(self, other.as_expression())
+    }
+}
+
+impl<T: Expression<SqlType = sql_types::Text>> SimilarityExpressionMethods for T {}
+
+/// Sets how similar two strings must be for `%` to match them, for the rest of the current transaction
+pub(super) async fn set_similarity_threshold(
+    threshold: f32,
+    db_conn: &mut AsyncPgConnection,
+) -> super::error::Result<()> {
+    diesel::select(set_config(
+        "pg_trgm.similarity_threshold",
+        threshold.to_string(),
+        true,
+    ))
+    .execute(db_conn)
+    .await?;
+
+    Ok(())
+}
+
 pub trait DbTransaction {
     async fn set_transaction_user(&mut self, user_id: &str) -> super::error::Result<()>;
 }
 
-impl DbTransaction for Object<AsyncPgConnection> {
+impl DbTransaction for AsyncPgConnection {
     /// # Errors
     async fn set_transaction_user(&mut self, user: &str) -> super::error::Result<()> {
         diesel::sql_query(format!(r#"set local role "{user}""#))
@@ -95,3 +130,12 @@ impl DbTransaction for Object<AsyncPgConnection> {
         Ok(())
     }
 }
+
+impl DbTransaction for Object<AsyncPgConnection> {
+    /// # Errors
+    async fn set_transaction_user(&mut self, user: &str) -> super::error::Result<()> {
+        let db_conn: &mut AsyncPgConnection = self;
+
+        db_conn.set_transaction_user(user).await
+    }
+}
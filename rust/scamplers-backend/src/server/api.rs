@@ -1,19 +1,64 @@
 use axum::{
     Router,
-    routing::{get, post},
+    routing::{get, patch, post},
 };
 use scamplers_core::{
     endpoint::Endpoint,
     model::{
-        institution::{Institution, InstitutionQuery, InstitutionSummary, NewInstitution},
-        lab::{Lab, LabQuery, LabSummary, NewLab},
-        person::{NewPerson, Person, PersonQuery, PersonSummary},
+        cdna::{Cdna, CdnaQuery, CdnaSummary, NewCdna},
+        chemistry::{Chemistry, ChemistryQuery},
+        chromium::{ChromiumRun, ChromiumRunQuery, ChromiumRunSummary, Gems, NewChromiumRun},
+        dataset_metadata::{
+            ChromiumDataset, ChromiumDatasetQuery, ChromiumDatasetSummary, NewChromiumDataset,
+        },
+        index_sets::{IndexSet, IndexSetQuery},
+        institution::{
+            Institution, InstitutionQuery, InstitutionSearchParams, InstitutionSummary,
+            InstitutionUpdate, NewInstitution,
+        },
+        lab::{Lab, LabQuery, LabSearchParams, LabSummary, LabUpdateWithMembers, NewLab},
+        library::{Library, LibraryQuery, LibrarySummary, NewLibrary},
+        library_type_specification::{
+            LibraryTypeSpecification, LibraryTypeSpecificationQuery,
+            LibraryTypeSpecificationUpdate, NewLibraryTypeSpecification,
+        },
+        lineage::{DatasetProvenance, SampleLineage},
+        measurements::{
+            CdnaMeasurement, LibraryMeasurement, MultiplexedSuspensionMeasurement,
+            NewCdnaMeasurement, NewLibraryMeasurement, NewMultiplexedSuspensionMeasurement,
+            NewSpecimenMeasurement, NewSuspensionMeasurement, SpecimenMeasurement,
+            SuspensionMeasurement,
+        },
+        multiplexed_suspension::{
+            MultiplexedSuspension, MultiplexedSuspensionQuery, MultiplexedSuspensionSummary,
+            MultiplexingTag, MultiplexingTagQuery, NewMultiplexedSuspension,
+        },
+        person::{NewPerson, Person, PersonQuery, PersonSearchParams, PersonSummary, PersonUpdate},
+        sample_metadata::{
+            CommitteeApproval, CommitteeApprovalQuery, CommitteeApprovalUpdate,
+            NewCommitteeApproval, NewSampleMetadata, NewSampleStatusTransition, Sample,
+            SampleQuery, SampleStatusTransition, SampleSummary,
+        },
+        search::{GlobalSearchQuery, SearchHit},
+        sequencing_run::{
+            NewSequencingRun, NewSequencingSubmission, SequencingRun, SequencingRunQuery,
+            SequencingRunSummary, SequencingRunUpdate, SequencingSubmission,
+        },
+        specimen::{NewSpecimen, Specimen, SpecimenQuery, SpecimenSummary},
+        suspension::{NewSuspension, Suspension, SuspensionQuery, SuspensionSummary},
     },
 };
-use scamplers_schema::lab::dsl::lab;
+use scamplers_schema::{
+    cdna::dsl::cdna, chromium_library::dsl::chromium_library, gems::dsl::gems,
+    institution::dsl::institution, lab::dsl::lab,
+    multiplexed_suspension::dsl::multiplexed_suspension, sample_metadata::dsl::sample_metadata,
+    sequencing_run::dsl::sequencing_run, specimen::dsl::specimen, suspension::dsl::suspension,
+};
 use uuid::Uuid;
 
-use crate::server::api::handler::{by_id, by_query, new_user, relatives, write};
+use crate::server::api::handler::{
+    by_id, by_query, by_query_params, by_readable_id, new_user, relatives, write, write_relatives,
+};
 
 use super::AppState;
 
@@ -25,7 +70,7 @@ pub(super) fn router() -> Router<AppState> {
         .route("/", get(|| async {}))
         .route(
             &Endpoint::<NewInstitution, Institution>::route(),
-            post(write::<NewInstitution>),
+            post(write::<NewInstitution>).patch(write::<InstitutionUpdate>),
         )
         .route(
             &Endpoint::<Uuid, Institution>::route(),
@@ -33,26 +78,283 @@ pub(super) fn router() -> Router<AppState> {
         )
         .route(
             &Endpoint::<InstitutionQuery, InstitutionSummary>::route(),
-            post(by_query::<InstitutionSummary>),
+            get(by_query_params::<InstitutionSummary, InstitutionSearchParams>)
+                .post(by_query::<InstitutionSummary>),
+        )
+        .route(
+            &format!("{}/people", Endpoint::<Uuid, Institution>::route()),
+            get(relatives::<institution, PersonSummary>),
+        )
+        .route(
+            &format!("{}/labs", Endpoint::<Uuid, Institution>::route()),
+            get(relatives::<institution, LabSummary>),
         )
         .route(
             &Endpoint::<NewPerson, Person>::route(),
-            post(write::<NewPerson>),
+            post(write::<NewPerson>).patch(write::<PersonUpdate>),
         )
         .route(&NewPerson::new_user_route(), post(new_user))
         .route(&Endpoint::<Uuid, Person>::route(), get(by_id::<Person>))
         .route(
             &Endpoint::<PersonQuery, PersonSummary>::route(),
-            post(by_query::<PersonSummary>),
+            get(by_query_params::<PersonSummary, PersonSearchParams>)
+                .post(by_query::<PersonSummary>),
+        )
+        .route(
+            &Endpoint::<NewLab, Lab>::route(),
+            post(write::<NewLab>).patch(write::<LabUpdateWithMembers>),
         )
-        .route(&Endpoint::<NewLab, Lab>::route(), post(write::<NewLab>))
         .route(&Endpoint::<Uuid, Lab>::route(), get(by_id::<Lab>))
         .route(
             &Endpoint::<LabQuery, LabSummary>::route(),
-            post(by_query::<LabSummary>),
+            get(by_query_params::<LabSummary, LabSearchParams>).post(by_query::<LabSummary>),
         )
         .route(
             &format!("{}/members", Endpoint::<Uuid, Lab>::route()),
             get(relatives::<lab, PersonSummary>),
         )
+        .route(
+            &Endpoint::<NewSampleMetadata, Sample>::route(),
+            post(write::<NewSampleMetadata>),
+        )
+        .route(&Endpoint::<Uuid, Sample>::route(), get(by_id::<Sample>))
+        .route(
+            &Endpoint::<Uuid, SampleLineage>::route(),
+            get(by_id::<SampleLineage>),
+        )
+        .route(
+            &Endpoint::<SampleQuery, SampleSummary>::route(),
+            post(by_query::<SampleSummary>),
+        )
+        .route(
+            &Endpoint::<NewCommitteeApproval, CommitteeApproval>::route(),
+            get(relatives::<sample_metadata, CommitteeApproval>)
+                .post(write_relatives::<sample_metadata, NewCommitteeApproval>),
+        )
+        .route(
+            &Endpoint::<NewSampleStatusTransition, SampleStatusTransition>::route(),
+            get(relatives::<sample_metadata, SampleStatusTransition>)
+                .post(write_relatives::<sample_metadata, NewSampleStatusTransition>),
+        )
+        .route(
+            &Endpoint::<CommitteeApprovalUpdate, CommitteeApproval>::route(),
+            patch(write::<CommitteeApprovalUpdate>),
+        )
+        .route(
+            &Endpoint::<CommitteeApprovalQuery, CommitteeApproval>::route(),
+            post(by_query::<CommitteeApproval>),
+        )
+        .route(
+            &Endpoint::<GlobalSearchQuery, SearchHit>::route(),
+            get(by_query_params::<SearchHit, GlobalSearchQuery>),
+        )
+        .merge(sample_processing_router())
+        .merge(library_prep_router())
+        .merge(sequencing_router())
+        .merge(measurement_router())
+}
+
+fn sample_processing_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            &Endpoint::<NewSpecimen, Specimen>::route(),
+            post(write::<NewSpecimen>),
+        )
+        .route(&Endpoint::<Uuid, Specimen>::route(), get(by_id::<Specimen>))
+        .route(
+            &Endpoint::<String, Specimen>::route(),
+            get(by_readable_id::<Specimen>),
+        )
+        .route(
+            &Endpoint::<SpecimenQuery, SpecimenSummary>::route(),
+            post(by_query::<SpecimenSummary>),
+        )
+        .route(
+            &Endpoint::<NewSuspension, Suspension>::route(),
+            post(write::<NewSuspension>),
+        )
+        .route(
+            &Endpoint::<Uuid, Suspension>::route(),
+            get(by_id::<Suspension>),
+        )
+        .route(
+            &Endpoint::<String, Suspension>::route(),
+            get(by_readable_id::<Suspension>),
+        )
+        .route(
+            &Endpoint::<SuspensionQuery, SuspensionSummary>::route(),
+            post(by_query::<SuspensionSummary>),
+        )
+        .route(
+            &Endpoint::<MultiplexingTagQuery, MultiplexingTag>::route(),
+            post(by_query::<MultiplexingTag>),
+        )
+        .route(
+            &Endpoint::<NewMultiplexedSuspension, MultiplexedSuspension>::route(),
+            post(write::<NewMultiplexedSuspension>),
+        )
+        .route(
+            &Endpoint::<Uuid, MultiplexedSuspension>::route(),
+            get(by_id::<MultiplexedSuspension>),
+        )
+        .route(
+            &Endpoint::<String, MultiplexedSuspension>::route(),
+            get(by_readable_id::<MultiplexedSuspension>),
+        )
+        .route(
+            &Endpoint::<MultiplexedSuspensionQuery, MultiplexedSuspensionSummary>::route(),
+            post(by_query::<MultiplexedSuspensionSummary>),
+        )
+}
+
+fn library_prep_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            &Endpoint::<String, Chemistry>::route(),
+            get(by_id::<Chemistry>),
+        )
+        .route(
+            &Endpoint::<ChemistryQuery, Chemistry>::route(),
+            post(by_query::<Chemistry>),
+        )
+        .route(
+            &Endpoint::<String, IndexSet>::route(),
+            get(by_id::<IndexSet>),
+        )
+        .route(
+            &Endpoint::<IndexSetQuery, IndexSet>::route(),
+            post(by_query::<IndexSet>),
+        )
+        .route(
+            &Endpoint::<NewLibraryTypeSpecification, LibraryTypeSpecification>::route(),
+            post(write::<NewLibraryTypeSpecification>)
+                .patch(write::<LibraryTypeSpecificationUpdate>),
+        )
+        .route(
+            &Endpoint::<(String, String), LibraryTypeSpecification>::route(),
+            get(by_id::<LibraryTypeSpecification>),
+        )
+        .route(
+            &Endpoint::<LibraryTypeSpecificationQuery, LibraryTypeSpecification>::route(),
+            post(by_query::<LibraryTypeSpecification>),
+        )
+        .route(
+            &Endpoint::<NewChromiumRun, ChromiumRun>::route(),
+            post(write::<NewChromiumRun>),
+        )
+        .route(
+            &Endpoint::<Uuid, ChromiumRun>::route(),
+            get(by_id::<ChromiumRun>),
+        )
+        .route(
+            &Endpoint::<String, ChromiumRun>::route(),
+            get(by_readable_id::<ChromiumRun>),
+        )
+        .route(
+            &Endpoint::<ChromiumRunQuery, ChromiumRunSummary>::route(),
+            post(by_query::<ChromiumRunSummary>),
+        )
+        .route(&Endpoint::<Uuid, Gems>::route(), get(by_id::<Gems>))
+        .route(
+            &format!("{}/cdna", Endpoint::<Uuid, Gems>::route()),
+            get(relatives::<gems, CdnaSummary>),
+        )
+        .route(&Endpoint::<NewCdna, Cdna>::route(), post(write::<NewCdna>))
+        .route(&Endpoint::<Uuid, Cdna>::route(), get(by_id::<Cdna>))
+        .route(
+            &Endpoint::<String, Cdna>::route(),
+            get(by_readable_id::<Cdna>),
+        )
+        .route(
+            &Endpoint::<CdnaQuery, CdnaSummary>::route(),
+            post(by_query::<CdnaSummary>),
+        )
+        .route(
+            &Endpoint::<NewLibrary, Library>::route(),
+            post(write::<NewLibrary>),
+        )
+        .route(&Endpoint::<Uuid, Library>::route(), get(by_id::<Library>))
+        .route(
+            &Endpoint::<String, Library>::route(),
+            get(by_readable_id::<Library>),
+        )
+        .route(
+            &Endpoint::<LibraryQuery, LibrarySummary>::route(),
+            post(by_query::<LibrarySummary>),
+        )
+}
+
+fn sequencing_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            &Endpoint::<NewSequencingRun, SequencingRun>::route(),
+            post(write::<NewSequencingRun>).patch(write::<SequencingRunUpdate>),
+        )
+        .route(
+            &Endpoint::<Uuid, SequencingRun>::route(),
+            get(by_id::<SequencingRun>),
+        )
+        .route(
+            &Endpoint::<String, SequencingRun>::route(),
+            get(by_readable_id::<SequencingRun>),
+        )
+        .route(
+            &Endpoint::<SequencingRunQuery, SequencingRunSummary>::route(),
+            post(by_query::<SequencingRunSummary>),
+        )
+        .route(
+            &Endpoint::<NewSequencingSubmission, SequencingSubmission>::route(),
+            get(relatives::<sequencing_run, SequencingSubmission>)
+                .post(write_relatives::<sequencing_run, NewSequencingSubmission>),
+        )
+        .route(
+            &Endpoint::<Uuid, SequencingRunSummary>::route(),
+            get(relatives::<chromium_library, SequencingRunSummary>),
+        )
+        .route(
+            &Endpoint::<NewChromiumDataset, ChromiumDataset>::route(),
+            post(write::<NewChromiumDataset>),
+        )
+        .route(
+            &Endpoint::<Uuid, ChromiumDataset>::route(),
+            get(by_id::<ChromiumDataset>),
+        )
+        .route(
+            &Endpoint::<Uuid, DatasetProvenance>::route(),
+            get(by_id::<DatasetProvenance>),
+        )
+        .route(
+            &Endpoint::<ChromiumDatasetQuery, ChromiumDatasetSummary>::route(),
+            post(by_query::<ChromiumDatasetSummary>),
+        )
+}
+
+fn measurement_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            &Endpoint::<NewSpecimenMeasurement, SpecimenMeasurement>::route(),
+            get(relatives::<specimen, SpecimenMeasurement>)
+                .post(write_relatives::<specimen, NewSpecimenMeasurement>),
+        )
+        .route(
+            &Endpoint::<NewSuspensionMeasurement, SuspensionMeasurement>::route(),
+            get(relatives::<suspension, SuspensionMeasurement>)
+                .post(write_relatives::<suspension, NewSuspensionMeasurement>),
+        )
+        .route(
+            &Endpoint::<NewMultiplexedSuspensionMeasurement, MultiplexedSuspensionMeasurement>::route(),
+            get(relatives::<multiplexed_suspension, MultiplexedSuspensionMeasurement>).post(
+                write_relatives::<multiplexed_suspension, NewMultiplexedSuspensionMeasurement>,
+            ),
+        )
+        .route(
+            &Endpoint::<NewCdnaMeasurement, CdnaMeasurement>::route(),
+            get(relatives::<cdna, CdnaMeasurement>)
+                .post(write_relatives::<cdna, NewCdnaMeasurement>),
+        )
+        .route(
+            &Endpoint::<NewLibraryMeasurement, LibraryMeasurement>::route(),
+            get(relatives::<chromium_library, LibraryMeasurement>)
+                .post(write_relatives::<chromium_library, NewLibraryMeasurement>),
+        )
 }
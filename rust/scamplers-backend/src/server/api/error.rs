@@ -30,7 +30,10 @@ pub enum Error {
 impl Error {
     fn staus_code(&self) -> axum::http::StatusCode {
         use Error::{_Permission, Database, MalformedRequest, SimpleData};
-        use db::error::Error::{DuplicateRecord, Other, RecordNotFound, ReferenceNotFound};
+        use db::error::Error::{
+            ConstraintViolation, DuplicateRecord, Other, PermissionDenied, RecordNotFound,
+            ReferenceNotFound,
+        };
 
         match self {
             SimpleData { .. } => StatusCode::UNPROCESSABLE_ENTITY,
@@ -40,7 +43,10 @@ impl Error {
                 Other { .. } => StatusCode::INTERNAL_SERVER_ERROR,
                 DuplicateRecord { .. } => StatusCode::CONFLICT,
                 RecordNotFound => StatusCode::NOT_FOUND,
-                ReferenceNotFound { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                PermissionDenied { .. } => StatusCode::FORBIDDEN,
+                ReferenceNotFound { .. } | ConstraintViolation { .. } => {
+                    StatusCode::UNPROCESSABLE_ENTITY
+                }
             },
             MalformedRequest { status, .. } => *status,
         }
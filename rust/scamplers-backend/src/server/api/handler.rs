@@ -3,16 +3,20 @@ use axum::{
     extract::{FromRequest, OptionalFromRequest, Path, State},
     response::{IntoResponse, Response},
 };
+use axum_extra::extract::Query;
 use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
 use garde::Validate;
-use scamplers_core::model::person::{CreatedUser, NewPerson};
+use scamplers_core::model::{
+    Page,
+    person::{CreatedUser, NewPerson},
+};
 use serde::{Serialize, de::DeserializeOwned};
 use valuable::Valuable;
 
 use crate::{
     db::{
         DbTransaction,
-        model::{self, FetchRelatives, person::WriteLogin},
+        model::{self, FetchRelatives, WriteRelatives, person::WriteLogin},
     },
     server::{
         AppState,
@@ -144,11 +148,37 @@ where
     Ok(Json(item))
 }
 
+pub async fn by_readable_id<Resource>(
+    User(user_id): User,
+    State(app_state): State<AppState>,
+    Path(readable_id): Path<String>,
+) -> super::error::Result<Json<Resource>>
+where
+    Resource: model::FetchByReadableId + Send,
+{
+    tracing::info!(deserialized_readable_id = readable_id);
+
+    let mut db_conn = app_state.db_conn().await?;
+
+    let item = db_conn
+        .transaction(|conn| {
+            async move {
+                conn.set_transaction_user(&user_id.to_string()).await?;
+
+                Resource::fetch_by_readable_id(&readable_id, conn).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(Json(item))
+}
+
 pub async fn by_query<Resource>(
     User(user_id): User,
     State(app_state): State<AppState>,
     query: Option<ValidJson<Resource::QueryParams>>,
-) -> super::error::Result<Json<Vec<Resource>>>
+) -> super::error::Result<Json<Page<Resource>>>
 where
     Resource: model::FetchByQuery + Send,
     Resource::QueryParams: Send + valuable::Valuable + Default,
@@ -172,6 +202,37 @@ where
     Ok(Json(item))
 }
 
+/// Like `by_query`, but takes the query from the URL's query string, in the form of `Params`
+pub(super) async fn by_query_params<Resource, Params>(
+    User(user_id): User,
+    State(app_state): State<AppState>,
+    Query(params): Query<Params>,
+) -> super::error::Result<Json<Page<Resource>>>
+where
+    Resource: model::FetchByQuery + Send,
+    Resource::QueryParams: Validate<Context: Default> + Send + Valuable,
+    Params: DeserializeOwned + Into<Resource::QueryParams>,
+{
+    let query = params.into();
+    query.validate()?;
+    tracing::info!(deserialized_query = query.as_value());
+
+    let mut db_conn = app_state.db_conn().await?;
+
+    let item = db_conn
+        .transaction(|conn| {
+            async move {
+                conn.set_transaction_user(&user_id.to_string()).await?;
+
+                Resource::fetch_by_query(&query, conn).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(Json(item))
+}
+
 pub(super) async fn relatives<Table, Relative>(
     User(user_id): User,
     State(app_state): State<AppState>,
@@ -199,3 +260,36 @@ where
 
     Ok(Json(item))
 }
+
+pub(super) async fn write_relatives<Table, Relative>(
+    User(user_id): User,
+    State(app_state): State<AppState>,
+    Path(id): Path<Table::Id>,
+    ValidJson(relatives): ValidJson<Vec<Relative>>,
+) -> super::error::Result<Json<Vec<Table::Returns>>>
+where
+    Table: WriteRelatives<Relative>,
+    Table::Id: Valuable + Send,
+    Table::Returns: Send,
+    Relative: Validate<Context: Default> + DeserializeOwned + Valuable + Send,
+{
+    tracing::info!(
+        deserialized_id = id.as_value(),
+        deserialized_data = relatives.as_value()
+    );
+
+    let mut db_conn = app_state.db_conn().await?;
+
+    let item = db_conn
+        .transaction(|conn| {
+            async move {
+                conn.set_transaction_user(&user_id.to_string()).await?;
+
+                Table::write_relatives(&id, relatives, conn).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(Json(item))
+}
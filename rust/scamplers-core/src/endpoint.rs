@@ -3,14 +3,67 @@ use std::marker::PhantomData;
 use uuid::Uuid;
 
 use crate::model::{
-    institution::{Institution, InstitutionQuery, InstitutionSummary, NewInstitution},
-    lab::{Lab, LabQuery, LabSummary, NewLab},
-    person::{NewPerson, Person, PersonQuery, PersonSummary},
+    cdna::{Cdna, CdnaQuery, CdnaSummary, NewCdna},
+    chemistry::{Chemistry, ChemistryQuery},
+    chromium::{ChromiumRun, ChromiumRunQuery, ChromiumRunSummary, Gems, NewChromiumRun},
+    dataset_metadata::{
+        ChromiumDataset, ChromiumDatasetQuery, ChromiumDatasetSummary, NewChromiumDataset,
+    },
+    index_sets::{IndexSet, IndexSetQuery},
+    institution::{
+        Institution, InstitutionQuery, InstitutionSearchParams, InstitutionSummary,
+        InstitutionUpdate, NewInstitution,
+    },
+    lab::{Lab, LabQuery, LabSearchParams, LabSummary, LabUpdateWithMembers, NewLab},
+    library::{Library, LibraryQuery, LibrarySummary, NewLibrary},
+    library_type_specification::{
+        LibraryTypeSpecification, LibraryTypeSpecificationQuery, LibraryTypeSpecificationUpdate,
+        NewLibraryTypeSpecification,
+    },
+    lineage::{DatasetProvenance, SampleLineage},
+    measurements::{
+        CdnaMeasurement, LibraryMeasurement, MultiplexedSuspensionMeasurement, NewCdnaMeasurement,
+        NewLibraryMeasurement, NewMultiplexedSuspensionMeasurement, NewSpecimenMeasurement,
+        NewSuspensionMeasurement, SpecimenMeasurement, SuspensionMeasurement,
+    },
+    multiplexed_suspension::{
+        MultiplexedSuspension, MultiplexedSuspensionQuery, MultiplexedSuspensionSummary,
+        MultiplexingTag, MultiplexingTagQuery, NewMultiplexedSuspension,
+    },
+    person::{NewPerson, Person, PersonQuery, PersonSearchParams, PersonSummary, PersonUpdate},
+    sample_metadata::{
+        CommitteeApproval, CommitteeApprovalQuery, CommitteeApprovalUpdate, NewCommitteeApproval,
+        NewSampleMetadata, NewSampleStatusTransition, Sample, SampleQuery, SampleStatusTransition,
+        SampleSummary,
+    },
+    search::{GlobalSearchQuery, SearchHit},
+    sequencing_run::{
+        NewSequencingRun, NewSequencingSubmission, SequencingRun, SequencingRunQuery,
+        SequencingRunSummary, SequencingRunUpdate, SequencingSubmission,
+    },
+    specimen::{NewSpecimen, Specimen, SpecimenQuery, SpecimenSummary},
+    suspension::{NewSuspension, Suspension, SuspensionQuery, SuspensionSummary},
 };
 
 pub struct Endpoint<Req, Resp>(PhantomData<Req>, PhantomData<Resp>);
 
+// The `GET` form of a search route, which takes the query as a query string rather than as a JSON body
+fn with_query_string<Params: serde::Serialize>(
+    route: String,
+    params: &Params,
+) -> Result<String, serde_html_form::ser::Error> {
+    let query_string = serde_html_form::to_string(params)?;
+
+    if query_string.is_empty() {
+        Ok(route)
+    } else {
+        Ok(format!("{route}?{query_string}"))
+    }
+}
+
 const SEARCH_SUFFIX: &str = "search";
+const READABLE_ID_INFIX: &str = "readable_id";
+const MEASUREMENTS_SUFFIX: &str = "measurements";
 
 const INSTITUTIONS: &str = "/institutions";
 impl Endpoint<NewInstitution, Institution> {
@@ -20,6 +73,13 @@ impl Endpoint<NewInstitution, Institution> {
     }
 }
 
+impl Endpoint<InstitutionUpdate, Institution> {
+    #[must_use]
+    pub fn route() -> String {
+        INSTITUTIONS.to_string()
+    }
+}
+
 impl Endpoint<Uuid, Institution> {
     #[must_use]
     pub fn route() -> String {
@@ -32,6 +92,14 @@ impl Endpoint<InstitutionQuery, InstitutionSummary> {
     pub fn route() -> String {
         format!("{INSTITUTIONS}/{SEARCH_SUFFIX}")
     }
+
+    /// # Errors
+    /// Fails if the query can't be written as a query string
+    pub fn route_with_query(
+        query: &InstitutionQuery,
+    ) -> Result<String, serde_html_form::ser::Error> {
+        with_query_string(Self::route(), &InstitutionSearchParams::from(query))
+    }
 }
 
 const PEOPLE: &str = "/people";
@@ -42,6 +110,13 @@ impl Endpoint<NewPerson, Person> {
     }
 }
 
+impl Endpoint<PersonUpdate, Person> {
+    #[must_use]
+    pub fn route() -> String {
+        PEOPLE.to_string()
+    }
+}
+
 impl Endpoint<Uuid, Person> {
     #[must_use]
     pub fn route() -> String {
@@ -54,6 +129,12 @@ impl Endpoint<PersonQuery, PersonSummary> {
     pub fn route() -> String {
         format!("{PEOPLE}/{SEARCH_SUFFIX}")
     }
+
+    /// # Errors
+    /// Fails if the query can't be written as a query string
+    pub fn route_with_query(query: &PersonQuery) -> Result<String, serde_html_form::ser::Error> {
+        with_query_string(Self::route(), &PersonSearchParams::from(query))
+    }
 }
 
 const LABS: &str = "/labs";
@@ -64,6 +145,13 @@ impl Endpoint<NewLab, Lab> {
     }
 }
 
+impl Endpoint<LabUpdateWithMembers, Lab> {
+    #[must_use]
+    pub fn route() -> String {
+        LABS.to_string()
+    }
+}
+
 impl Endpoint<Uuid, Lab> {
     #[must_use]
     pub fn route() -> String {
@@ -76,4 +164,439 @@ impl Endpoint<LabQuery, LabSummary> {
     pub fn route() -> String {
         format!("{LABS}/{SEARCH_SUFFIX}")
     }
+
+    /// # Errors
+    /// Fails if the query can't be written as a query string
+    pub fn route_with_query(query: &LabQuery) -> Result<String, serde_html_form::ser::Error> {
+        with_query_string(Self::route(), &LabSearchParams::from(query))
+    }
+}
+
+const SAMPLES: &str = "/samples";
+impl Endpoint<NewSampleMetadata, Sample> {
+    #[must_use]
+    pub fn route() -> String {
+        SAMPLES.to_string()
+    }
+}
+
+impl Endpoint<Uuid, Sample> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SAMPLES}/{{id}}")
+    }
+}
+
+impl Endpoint<SampleQuery, SampleSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SAMPLES}/{SEARCH_SUFFIX}")
+    }
+}
+
+impl Endpoint<NewCommitteeApproval, CommitteeApproval> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SAMPLES}/{{id}}/committee_approvals")
+    }
+}
+
+impl Endpoint<NewSampleStatusTransition, SampleStatusTransition> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SAMPLES}/{{id}}/status_transitions")
+    }
+}
+
+impl Endpoint<Uuid, SampleLineage> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SAMPLES}/{{id}}/lineage")
+    }
+}
+
+const COMMITTEE_APPROVALS: &str = "/committee_approvals";
+impl Endpoint<CommitteeApprovalUpdate, CommitteeApproval> {
+    #[must_use]
+    pub fn route() -> String {
+        COMMITTEE_APPROVALS.to_string()
+    }
+}
+
+impl Endpoint<CommitteeApprovalQuery, CommitteeApproval> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{COMMITTEE_APPROVALS}/{SEARCH_SUFFIX}")
+    }
+}
+
+const SPECIMENS: &str = "/specimens";
+impl Endpoint<NewSpecimen, Specimen> {
+    #[must_use]
+    pub fn route() -> String {
+        SPECIMENS.to_string()
+    }
+}
+
+impl Endpoint<Uuid, Specimen> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SPECIMENS}/{{id}}")
+    }
+}
+
+impl Endpoint<String, Specimen> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SPECIMENS}/{READABLE_ID_INFIX}/{{readable_id}}")
+    }
+}
+
+impl Endpoint<SpecimenQuery, SpecimenSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SPECIMENS}/{SEARCH_SUFFIX}")
+    }
+}
+
+impl Endpoint<NewSpecimenMeasurement, SpecimenMeasurement> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SPECIMENS}/{{id}}/{MEASUREMENTS_SUFFIX}")
+    }
+}
+
+const SUSPENSIONS: &str = "/suspensions";
+impl Endpoint<NewSuspension, Suspension> {
+    #[must_use]
+    pub fn route() -> String {
+        SUSPENSIONS.to_string()
+    }
+}
+
+impl Endpoint<Uuid, Suspension> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SUSPENSIONS}/{{id}}")
+    }
+}
+
+impl Endpoint<String, Suspension> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SUSPENSIONS}/{READABLE_ID_INFIX}/{{readable_id}}")
+    }
+}
+
+impl Endpoint<SuspensionQuery, SuspensionSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SUSPENSIONS}/{SEARCH_SUFFIX}")
+    }
+}
+
+impl Endpoint<NewSuspensionMeasurement, SuspensionMeasurement> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SUSPENSIONS}/{{id}}/{MEASUREMENTS_SUFFIX}")
+    }
+}
+
+const MULTIPLEXED_SUSPENSIONS: &str = "/multiplexed_suspensions";
+impl Endpoint<NewMultiplexedSuspension, MultiplexedSuspension> {
+    #[must_use]
+    pub fn route() -> String {
+        MULTIPLEXED_SUSPENSIONS.to_string()
+    }
+}
+
+impl Endpoint<Uuid, MultiplexedSuspension> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{MULTIPLEXED_SUSPENSIONS}/{{id}}")
+    }
+}
+
+impl Endpoint<String, MultiplexedSuspension> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{MULTIPLEXED_SUSPENSIONS}/{READABLE_ID_INFIX}/{{readable_id}}")
+    }
+}
+
+impl Endpoint<MultiplexedSuspensionQuery, MultiplexedSuspensionSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{MULTIPLEXED_SUSPENSIONS}/{SEARCH_SUFFIX}")
+    }
+}
+
+impl Endpoint<NewMultiplexedSuspensionMeasurement, MultiplexedSuspensionMeasurement> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{MULTIPLEXED_SUSPENSIONS}/{{id}}/{MEASUREMENTS_SUFFIX}")
+    }
+}
+
+const MULTIPLEXING_TAGS: &str = "/multiplexing_tags";
+impl Endpoint<MultiplexingTagQuery, MultiplexingTag> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{MULTIPLEXING_TAGS}/{SEARCH_SUFFIX}")
+    }
+}
+
+const CHROMIUM_RUNS: &str = "/chromium_runs";
+impl Endpoint<NewChromiumRun, ChromiumRun> {
+    #[must_use]
+    pub fn route() -> String {
+        CHROMIUM_RUNS.to_string()
+    }
+}
+
+impl Endpoint<Uuid, ChromiumRun> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CHROMIUM_RUNS}/{{id}}")
+    }
+}
+
+impl Endpoint<String, ChromiumRun> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CHROMIUM_RUNS}/{READABLE_ID_INFIX}/{{readable_id}}")
+    }
+}
+
+impl Endpoint<ChromiumRunQuery, ChromiumRunSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CHROMIUM_RUNS}/{SEARCH_SUFFIX}")
+    }
+}
+
+const CHEMISTRIES: &str = "/chemistries";
+impl Endpoint<String, Chemistry> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CHEMISTRIES}/{{name}}")
+    }
+}
+
+impl Endpoint<ChemistryQuery, Chemistry> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CHEMISTRIES}/{SEARCH_SUFFIX}")
+    }
+}
+
+const INDEX_SETS: &str = "/index_sets";
+impl Endpoint<String, IndexSet> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{INDEX_SETS}/{{name}}")
+    }
+}
+
+impl Endpoint<IndexSetQuery, IndexSet> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{INDEX_SETS}/{SEARCH_SUFFIX}")
+    }
+}
+
+const LIBRARY_TYPE_SPECIFICATIONS: &str = "/library_type_specifications";
+impl Endpoint<NewLibraryTypeSpecification, LibraryTypeSpecification> {
+    #[must_use]
+    pub fn route() -> String {
+        LIBRARY_TYPE_SPECIFICATIONS.to_string()
+    }
+}
+
+impl Endpoint<LibraryTypeSpecificationUpdate, LibraryTypeSpecification> {
+    #[must_use]
+    pub fn route() -> String {
+        LIBRARY_TYPE_SPECIFICATIONS.to_string()
+    }
+}
+
+impl Endpoint<(String, String), LibraryTypeSpecification> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{LIBRARY_TYPE_SPECIFICATIONS}/{{chemistry}}/{{library_type}}")
+    }
+}
+
+impl Endpoint<LibraryTypeSpecificationQuery, LibraryTypeSpecification> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{LIBRARY_TYPE_SPECIFICATIONS}/{SEARCH_SUFFIX}")
+    }
+}
+
+const GEMS: &str = "/gems";
+impl Endpoint<Uuid, Gems> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{GEMS}/{{id}}")
+    }
+}
+
+const CDNA: &str = "/cdna";
+impl Endpoint<NewCdna, Cdna> {
+    #[must_use]
+    pub fn route() -> String {
+        CDNA.to_string()
+    }
+}
+
+impl Endpoint<Uuid, Cdna> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CDNA}/{{id}}")
+    }
+}
+
+impl Endpoint<String, Cdna> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CDNA}/{READABLE_ID_INFIX}/{{readable_id}}")
+    }
+}
+
+impl Endpoint<CdnaQuery, CdnaSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CDNA}/{SEARCH_SUFFIX}")
+    }
+}
+
+impl Endpoint<NewCdnaMeasurement, CdnaMeasurement> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{CDNA}/{{id}}/{MEASUREMENTS_SUFFIX}")
+    }
+}
+
+const LIBRARIES: &str = "/libraries";
+impl Endpoint<NewLibrary, Library> {
+    #[must_use]
+    pub fn route() -> String {
+        LIBRARIES.to_string()
+    }
+}
+
+impl Endpoint<Uuid, Library> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{LIBRARIES}/{{id}}")
+    }
+}
+
+impl Endpoint<String, Library> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{LIBRARIES}/{READABLE_ID_INFIX}/{{readable_id}}")
+    }
+}
+
+impl Endpoint<LibraryQuery, LibrarySummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{LIBRARIES}/{SEARCH_SUFFIX}")
+    }
+}
+
+impl Endpoint<NewLibraryMeasurement, LibraryMeasurement> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{LIBRARIES}/{{id}}/{MEASUREMENTS_SUFFIX}")
+    }
+}
+
+const SEQUENCING_RUNS: &str = "/sequencing_runs";
+impl Endpoint<NewSequencingRun, SequencingRun> {
+    #[must_use]
+    pub fn route() -> String {
+        SEQUENCING_RUNS.to_string()
+    }
+}
+
+impl Endpoint<SequencingRunUpdate, SequencingRun> {
+    #[must_use]
+    pub fn route() -> String {
+        SEQUENCING_RUNS.to_string()
+    }
+}
+
+impl Endpoint<Uuid, SequencingRun> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SEQUENCING_RUNS}/{{id}}")
+    }
+}
+
+impl Endpoint<String, SequencingRun> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SEQUENCING_RUNS}/{READABLE_ID_INFIX}/{{readable_id}}")
+    }
+}
+
+impl Endpoint<SequencingRunQuery, SequencingRunSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SEQUENCING_RUNS}/{SEARCH_SUFFIX}")
+    }
+}
+
+impl Endpoint<NewSequencingSubmission, SequencingSubmission> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{SEQUENCING_RUNS}/{{id}}/libraries")
+    }
+}
+
+impl Endpoint<Uuid, SequencingRunSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{LIBRARIES}/{{id}}{SEQUENCING_RUNS}")
+    }
+}
+
+const DATASETS: &str = "/datasets";
+impl Endpoint<NewChromiumDataset, ChromiumDataset> {
+    #[must_use]
+    pub fn route() -> String {
+        DATASETS.to_string()
+    }
+}
+
+impl Endpoint<Uuid, ChromiumDataset> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{DATASETS}/{{id}}")
+    }
+}
+
+impl Endpoint<ChromiumDatasetQuery, ChromiumDatasetSummary> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{DATASETS}/{SEARCH_SUFFIX}")
+    }
+}
+
+impl Endpoint<Uuid, DatasetProvenance> {
+    #[must_use]
+    pub fn route() -> String {
+        format!("{DATASETS}/{{id}}/provenance")
+    }
+}
+
+const SEARCH: &str = "/search";
+impl Endpoint<GlobalSearchQuery, SearchHit> {
+    #[must_use]
+    pub fn route() -> String {
+        SEARCH.to_string()
+    }
 }
@@ -0,0 +1,134 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::model::{Pagination, measurements::NewCdnaMeasurement};
+
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_insertion, backend_ordering, backend_ordinal_columns_enum, backend_query_request,
+        backend_with_getters,
+    },
+    scamplers_schema::cdna,
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request,
+    frontend_with_getters,
+};
+
+#[cfg_attr(feature = "backend", backend_insertion(cdna))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewCdna {
+    /// Must be one of the library types of the chemistry used to make the GEMs
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub library_type: String,
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub readable_id: String,
+    pub prepared_at: OffsetDateTime,
+    pub gems_id: Uuid,
+    #[cfg_attr(feature = "backend", garde(range(min = 0)))]
+    pub n_amplification_cycles: i32,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub storage_location: Option<String>,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub notes: Option<Vec<String>>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(length(min = 1)))]
+    pub preparer_ids: Vec<Uuid>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(dive))]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    #[serde(default)]
+    pub measurements: Vec<NewCdnaMeasurement>,
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::model::{measurements::CdnaMeasurement, person::PersonSummary};
+
+    #[cfg(feature = "backend")]
+    use {scamplers_macros::backend_selection, scamplers_schema::cdna};
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(cdna))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct CdnaReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(cdna))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct CdnaSummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: CdnaReference,
+        library_type: String,
+        readable_id: String,
+        prepared_at: OffsetDateTime,
+        gems_id: Uuid,
+        n_amplification_cycles: i32,
+        storage_location: Option<String>,
+        notes: Option<Vec<String>>,
+    }
+
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct Cdna {
+        #[serde(flatten)]
+        summary: CdnaSummary,
+        preparers: Vec<PersonSummary>,
+        measurements: Vec<CdnaMeasurement>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl Cdna {
+        #[must_use]
+        pub fn new(
+            summary: CdnaSummary,
+            preparers: Vec<PersonSummary>,
+            measurements: Vec<CdnaMeasurement>,
+        ) -> Self {
+            Self {
+                summary,
+                preparers,
+                measurements,
+            }
+        }
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum CdnaOrdinalColumn {
+    #[default]
+    ReadableId,
+    PreparedAt,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct CdnaOrdering {
+    pub column: CdnaOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct CdnaQuery {
+    pub ids: Vec<Uuid>,
+    pub readable_id: Option<String>,
+    pub library_type: Option<String>,
+    pub gems_ids: Vec<Uuid>,
+    pub preparer_ids: Vec<Uuid>,
+    pub storage_location: Option<String>,
+    pub order_by: Vec<CdnaOrdering>,
+    pub pagination: Pagination,
+}
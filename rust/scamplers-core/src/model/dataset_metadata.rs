@@ -1 +1,325 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
 
+use crate::model::Pagination;
+
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_db_json, backend_insertion, backend_ordering, backend_ordinal_columns_enum,
+        backend_query_request, backend_with_getters,
+    },
+    scamplers_schema::dataset_metadata,
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request,
+    frontend_response, frontend_with_getters,
+};
+
+/// The headline metrics of a Cell Ranger run, as reported in `metrics_summary.csv`
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_json)]
+#[cfg_attr(feature = "typescript", frontend_response)]
+pub struct CellrangerMetrics {
+    pub estimated_number_of_cells: i32,
+    pub mean_reads_per_cell: i32,
+    pub median_genes_per_cell: i32,
+    /// A fraction between 0 and 1
+    pub sequencing_saturation: f32,
+}
+
+impl CellrangerMetrics {
+    /// Parses the two-line `metrics_summary.csv` written by `cellranger count`, in which numbers may be quoted and
+    /// contain thousands separators, and percentages are suffixed with `%`
+    /// # Errors
+    /// Returns an error if the file is not a header and a row of the same length, or if one of the extracted metrics is
+    /// missing or not a number
+    pub fn from_metrics_summary(csv: &str) -> Result<Self, MetricsSummaryError> {
+        let mut lines = csv.lines().filter(|l| !l.trim().is_empty());
+
+        let (Some(header), Some(row), None) = (lines.next(), lines.next(), lines.next()) else {
+            return Err(MetricsSummaryError::Malformed);
+        };
+
+        let header = split_csv_line(header);
+        let row = split_csv_line(row);
+
+        if header.len() != row.len() {
+            return Err(MetricsSummaryError::Malformed);
+        }
+
+        let get = |metric: &'static str| {
+            header
+                .iter()
+                .position(|h| h == metric)
+                .map(|i| row[i].as_str())
+                .ok_or(MetricsSummaryError::MissingMetric(metric))
+        };
+
+        let count = |metric| {
+            get(metric)?
+                .replace(',', "")
+                .parse()
+                .map_err(|_| MetricsSummaryError::InvalidMetric(metric))
+        };
+
+        let saturation_metric = "Sequencing Saturation";
+        let sequencing_saturation = get(saturation_metric)?
+            .strip_suffix('%')
+            .and_then(|s| s.parse::<f32>().ok())
+            .ok_or(MetricsSummaryError::InvalidMetric(saturation_metric))?
+            / 100.0;
+
+        Ok(Self {
+            estimated_number_of_cells: count("Estimated Number of Cells")?,
+            mean_reads_per_cell: count("Mean Reads per Cell")?,
+            median_genes_per_cell: count("Median Genes per Cell")?,
+            sequencing_saturation,
+        })
+    }
+}
+
+// `metrics_summary.csv` only quotes fields to protect thousands separators, so escaped quotes never appear
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut in_quotes = false;
+
+    for c in line.trim().chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+
+    fields
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MetricsSummaryError {
+    Malformed,
+    MissingMetric(&'static str),
+    InvalidMetric(&'static str),
+}
+
+impl std::fmt::Display for MetricsSummaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed => write!(
+                f,
+                "metrics summary must be a header row and one row of values"
+            ),
+            Self::MissingMetric(metric) => write!(f, "metrics summary is missing '{metric}'"),
+            Self::InvalidMetric(metric) => write!(f, "metrics summary has a malformed '{metric}'"),
+        }
+    }
+}
+
+impl std::error::Error for MetricsSummaryError {}
+
+/// `gems_id`, `web_summary` and `metrics_summary` are written to `chromium_dataset`, which shares its primary key with
+/// `dataset_metadata`
+#[cfg_attr(feature = "backend", backend_insertion(dataset_metadata))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewChromiumDataset {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub name: String,
+    pub lab_id: Uuid,
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub data_path: String,
+    pub delivered_at: OffsetDateTime,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion))]
+    pub gems_id: Uuid,
+    /// The contents of Cell Ranger's `web_summary.html`
+    #[cfg_attr(
+        feature = "backend",
+        diesel(skip_insertion),
+        garde(custom(is_web_summary()))
+    )]
+    pub web_summary: String,
+    /// The contents of Cell Ranger's `metrics_summary.csv`
+    #[cfg_attr(
+        feature = "backend",
+        diesel(skip_insertion),
+        garde(custom(is_metrics_summary()))
+    )]
+    pub metrics_summary: String,
+}
+
+#[cfg(feature = "backend")]
+fn is_web_summary() -> impl FnOnce(&String, &()) -> garde::Result {
+    |web_summary, ()| {
+        use scraper::{Html, Selector};
+
+        let start = web_summary.trim_start().get(..14).unwrap_or_default();
+        let is_html_document = start.eq_ignore_ascii_case("<!doctype html")
+            || start
+                .get(..5)
+                .is_some_and(|s| s.eq_ignore_ascii_case("<html"));
+
+        let body_contents = Selector::parse("body *").unwrap();
+        let has_body = Html::parse_document(web_summary)
+            .select(&body_contents)
+            .next()
+            .is_some();
+
+        if is_html_document && has_body {
+            Ok(())
+        } else {
+            Err(garde::Error::new(
+                "web summary must be an HTML document with a non-empty body",
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+fn is_metrics_summary() -> impl FnOnce(&String, &()) -> garde::Result {
+    |metrics_summary, ()| {
+        CellrangerMetrics::from_metrics_summary(metrics_summary)
+            .map(|_| ())
+            .map_err(|e| garde::Error::new(e.to_string()))
+    }
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use super::CellrangerMetrics;
+
+    #[cfg(feature = "backend")]
+    use {
+        scamplers_macros::backend_selection,
+        scamplers_schema::{chromium_dataset, dataset_metadata},
+    };
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(dataset_metadata))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct DatasetReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(dataset_metadata))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct DatasetMetadata {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: DatasetReference,
+        name: String,
+        lab_id: Uuid,
+        data_path: String,
+        delivered_at: OffsetDateTime,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(chromium_dataset))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct ChromiumDatasetSummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        metadata: DatasetMetadata,
+        gems_id: Uuid,
+        metrics: Vec<CellrangerMetrics>,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(chromium_dataset))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct ChromiumDataset {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        summary: ChromiumDatasetSummary,
+        web_summary: String,
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum ChromiumDatasetOrdinalColumn {
+    #[default]
+    DeliveredAt,
+    Name,
+    EstimatedNumberOfCells,
+    MedianGenesPerCell,
+    SequencingSaturation,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct ChromiumDatasetOrdering {
+    pub column: ChromiumDatasetOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct ChromiumDatasetQuery {
+    pub ids: Vec<Uuid>,
+    pub name: Option<String>,
+    pub lab_ids: Vec<Uuid>,
+    pub gems_ids: Vec<Uuid>,
+    pub min_estimated_number_of_cells: Option<i32>,
+    pub max_estimated_number_of_cells: Option<i32>,
+    pub min_median_genes_per_cell: Option<i32>,
+    pub max_median_genes_per_cell: Option<i32>,
+    pub min_sequencing_saturation: Option<f32>,
+    pub max_sequencing_saturation: Option<f32>,
+    pub order_by: Vec<ChromiumDatasetOrdering>,
+    pub pagination: Pagination,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{CellrangerMetrics, MetricsSummaryError};
+
+    const METRICS_SUMMARY: &str = "Estimated Number of Cells,Mean Reads per Cell,Median Genes per Cell,Number of \
+                                   Reads,Sequencing Saturation\n\"5,123\",\"45,678\",\"2,345\",\"234,012,345\",62.5%\n";
+
+    #[test]
+    fn parse_metrics_summary() {
+        let metrics = CellrangerMetrics::from_metrics_summary(METRICS_SUMMARY).unwrap();
+
+        assert_eq!(
+            metrics,
+            CellrangerMetrics {
+                estimated_number_of_cells: 5123,
+                mean_reads_per_cell: 45_678,
+                median_genes_per_cell: 2345,
+                sequencing_saturation: 0.625,
+            }
+        );
+    }
+
+    #[test]
+    fn reject_malformed_metrics_summary() {
+        assert_eq!(
+            CellrangerMetrics::from_metrics_summary("Estimated Number of Cells\n"),
+            Err(MetricsSummaryError::Malformed)
+        );
+
+        let missing_saturation =
+            "Estimated Number of Cells,Mean Reads per Cell,Median Genes per Cell\n1,2,3";
+        assert_eq!(
+            CellrangerMetrics::from_metrics_summary(missing_saturation),
+            Err(MetricsSummaryError::MissingMetric("Sequencing Saturation"))
+        );
+
+        let invalid_cells = METRICS_SUMMARY.replace("\"5,123\"", "many");
+        assert_eq!(
+            CellrangerMetrics::from_metrics_summary(&invalid_cells),
+            Err(MetricsSummaryError::InvalidMetric(
+                "Estimated Number of Cells"
+            ))
+        );
+    }
+}
@@ -1,20 +1,24 @@
 use uuid::Uuid;
 
-use crate::model::Pagination;
+use crate::model::{Pagination, SimilaritySearch, filter::Filter, impl_search_params};
+
+#[cfg(feature = "backend")]
+use crate::model::{filter::is_valid_filter, is_offset_paginated};
 
 #[cfg(feature = "backend")]
 use {
     scamplers_macros::{
-        backend_insertion, backend_ordering, backend_ordinal_columns_enum, backend_query_request,
-        backend_selection, backend_with_getters,
+        backend_filter_columns_enum, backend_insertion, backend_ordering,
+        backend_ordinal_columns_enum, backend_query_request, backend_selection,
+        backend_with_getters,
     },
     scamplers_schema::institution,
 };
 
 #[cfg(feature = "typescript")]
 use scamplers_macros::{
-    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request,
-    frontend_response, frontend_with_getters,
+    frontend_enum, frontend_filter_columns_enum, frontend_insertion, frontend_ordering,
+    frontend_query_request, frontend_response, frontend_update, frontend_with_getters,
 };
 
 #[cfg_attr(feature = "backend", backend_insertion(institution), derive(Clone))]
@@ -23,6 +27,75 @@ pub struct NewInstitution {
     pub id: Uuid,
     #[cfg_attr(feature = "backend", garde(length(min = 1)))]
     pub name: String,
+    /// The institution's Research Organization Registry identifier, without the `https://ror.org/` prefix
+    #[cfg_attr(
+        feature = "backend",
+        garde(inner(pattern(r"^0[a-hj-km-np-tv-z0-9]{6}[0-9]{2}$")))
+    )]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub ror_id: Option<String>,
+    /// An ISO 3166-1 alpha-2 country code
+    #[cfg_attr(feature = "backend", garde(inner(pattern("^[A-Z]{2}$"))))]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub country: Option<String>,
+}
+
+/// A wrong `ror_id` or `country` can be cleared with `remove_ror_id` or `remove_country`.
+// The `remove_*` flags have no columns of their own, so unlike other updates this one doesn't derive a changeset
+#[cfg_attr(
+    feature = "backend",
+    derive(
+        serde::Deserialize,
+        diesel::prelude::Identifiable,
+        valuable::Valuable,
+        garde::Validate,
+        Debug,
+        Default
+    ),
+    diesel(table_name = institution),
+    serde(default),
+    garde(allow_unvalidated)
+)]
+#[cfg_attr(feature = "typescript", frontend_update)]
+pub struct InstitutionUpdate {
+    pub id: Uuid,
+    #[cfg_attr(feature = "backend", garde(inner(length(min = 1))))]
+    pub name: Option<String>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(inner(pattern(r"^0[a-hj-km-np-tv-z0-9]{6}[0-9]{2}$")))
+    )]
+    pub ror_id: Option<String>,
+    #[cfg_attr(feature = "backend", garde(inner(pattern("^[A-Z]{2}$"))))]
+    pub country: Option<String>,
+    /// Clears `ror_id`, so cannot be combined with it
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(excludes_value("ror_id", self.ror_id.as_ref())))
+    )]
+    pub remove_ror_id: bool,
+    /// Clears `country`, so cannot be combined with it
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(excludes_value("country", self.country.as_ref())))
+    )]
+    pub remove_country: bool,
+}
+
+#[cfg(feature = "backend")]
+fn excludes_value<'a>(
+    field: &'a str,
+    value: Option<&'a String>,
+) -> impl FnOnce(&bool, &()) -> garde::Result + 'a {
+    move |remove, ()| {
+        if *remove && value.is_some() {
+            Err(garde::Error::new(format!(
+                "cannot both set and remove an institution's {field}"
+            )))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[cfg_attr(feature = "backend", backend_with_getters)]
@@ -48,6 +121,8 @@ mod read {
         #[cfg_attr(feature = "backend", diesel(embed))]
         reference: InstitutionReference,
         name: String,
+        ror_id: Option<String>,
+        country: Option<String>,
     }
 }
 pub use read::*;
@@ -100,6 +175,32 @@ impl Institution {
     pub fn name(&self) -> String {
         self.0.name()
     }
+
+    #[cfg(feature = "backend")]
+    #[must_use]
+    pub fn ror_id(&self) -> Option<&str> {
+        self.0.ror_id().as_deref()
+    }
+
+    #[cfg(feature = "typescript")]
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn ror_id(&self) -> Option<String> {
+        self.0.ror_id()
+    }
+
+    #[cfg(feature = "backend")]
+    #[must_use]
+    pub fn country(&self) -> Option<&str> {
+        self.0.country().as_deref()
+    }
+
+    #[cfg(feature = "typescript")]
+    #[wasm_bindgen(getter)]
+    #[must_use]
+    pub fn country(&self) -> Option<String> {
+        self.0.country()
+    }
 }
 
 #[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
@@ -116,11 +217,37 @@ pub struct InstitutionOrdering {
     pub descending: bool,
 }
 
+#[cfg_attr(feature = "backend", backend_filter_columns_enum(institution))]
+#[cfg_attr(feature = "typescript", frontend_filter_columns_enum)]
+pub enum InstitutionFilterColumn {
+    Id,
+    Name,
+    RorId,
+    Country,
+}
+
 #[cfg_attr(feature = "backend", backend_query_request)]
 #[cfg_attr(feature = "typescript", frontend_query_request)]
 pub struct InstitutionQuery {
     pub ids: Vec<Uuid>,
     pub name: Option<String>,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub similar_name: Option<SimilaritySearch>,
+    #[cfg_attr(feature = "backend", garde(custom(is_valid_filter())), valuable(skip))]
+    pub filter: Filter<InstitutionFilterColumn>,
     pub order_by: Vec<InstitutionOrdering>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_offset_paginated(self.similar_name.as_ref())))
+    )]
     pub pagination: Pagination,
 }
+
+impl_search_params!(
+    InstitutionQuery,
+    InstitutionSearchParams,
+    InstitutionOrdering,
+    InstitutionOrdinalColumn,
+    InstitutionFilterColumn,
+    [(Name, "name")]
+);
@@ -0,0 +1,133 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::model::{Pagination, measurements::NewLibraryMeasurement};
+
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_insertion, backend_ordering, backend_ordinal_columns_enum, backend_query_request,
+        backend_with_getters,
+    },
+    scamplers_schema::chromium_library,
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request,
+    frontend_with_getters,
+};
+
+/// Whether `index_set_name` refers to a single or dual index set is determined when the library is written
+#[cfg_attr(feature = "backend", backend_insertion(chromium_library))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewLibrary {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub readable_id: String,
+    pub cdna_id: Uuid,
+    /// Must belong to the index kit prescribed for the cDNA's chemistry and library type
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(length(min = 1)))]
+    pub index_set_name: String,
+    #[cfg_attr(feature = "backend", garde(range(min = 1)))]
+    pub number_of_sample_index_pcr_cycles: i32,
+    #[cfg_attr(feature = "backend", garde(range(min = 1)))]
+    pub target_reads_per_cell: i32,
+    pub prepared_at: OffsetDateTime,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub notes: Option<Vec<String>>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(length(min = 1)))]
+    pub preparer_ids: Vec<Uuid>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(dive))]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    #[serde(default)]
+    pub measurements: Vec<NewLibraryMeasurement>,
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::model::{measurements::LibraryMeasurement, person::PersonSummary};
+
+    #[cfg(feature = "backend")]
+    use {scamplers_macros::backend_selection, scamplers_schema::chromium_library};
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(chromium_library))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct LibraryReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(chromium_library))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct LibrarySummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: LibraryReference,
+        readable_id: String,
+        cdna_id: Uuid,
+        single_index_set_name: Option<String>,
+        dual_index_set_name: Option<String>,
+        number_of_sample_index_pcr_cycles: i32,
+        target_reads_per_cell: i32,
+        prepared_at: OffsetDateTime,
+        notes: Option<Vec<String>>,
+    }
+
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct Library {
+        #[serde(flatten)]
+        summary: LibrarySummary,
+        preparers: Vec<PersonSummary>,
+        measurements: Vec<LibraryMeasurement>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl Library {
+        #[must_use]
+        pub fn new(
+            summary: LibrarySummary,
+            preparers: Vec<PersonSummary>,
+            measurements: Vec<LibraryMeasurement>,
+        ) -> Self {
+            Self {
+                summary,
+                preparers,
+                measurements,
+            }
+        }
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum LibraryOrdinalColumn {
+    #[default]
+    ReadableId,
+    PreparedAt,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct LibraryOrdering {
+    pub column: LibraryOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct LibraryQuery {
+    pub ids: Vec<Uuid>,
+    pub readable_id: Option<String>,
+    pub cdna_ids: Vec<Uuid>,
+    pub order_by: Vec<LibraryOrdering>,
+    pub pagination: Pagination,
+}
@@ -0,0 +1,121 @@
+use crate::model::Pagination;
+
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_insertion, backend_ordering, backend_ordinal_columns_enum, backend_query_request,
+        backend_update, backend_with_getters,
+    },
+    scamplers_schema::library_type_specification,
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request, frontend_update,
+    frontend_with_getters,
+};
+
+/// Volumes are in microliters. As with units, "µ" may be written as "u" in field names.
+#[cfg_attr(feature = "backend", backend_insertion(library_type_specification))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewLibraryTypeSpecification {
+    pub chemistry: String,
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub library_type: String,
+    pub index_kit: String,
+    #[serde(rename = "cdna_volume_µl", alias = "cdna_volume_ul")]
+    #[cfg_attr(
+        feature = "backend",
+        diesel(column_name = cdna_volume_l),
+        garde(range(min = 0.0))
+    )]
+    pub cdna_volume_ul: f32,
+    #[serde(rename = "library_volume_µl", alias = "library_volume_ul")]
+    #[cfg_attr(
+        feature = "backend",
+        diesel(column_name = library_volume_l),
+        garde(range(min = 0.0))
+    )]
+    pub library_volume_ul: f32,
+}
+
+/// Identifies a specification by its chemistry and library type, which cannot themselves be changed
+#[cfg_attr(
+    feature = "backend",
+    backend_update(library_type_specification),
+    diesel(primary_key(chemistry, library_type)),
+    derive(garde::Validate),
+    garde(allow_unvalidated)
+)]
+#[cfg_attr(feature = "typescript", frontend_update)]
+pub struct LibraryTypeSpecificationUpdate {
+    pub chemistry: String,
+    pub library_type: String,
+    pub index_kit: Option<String>,
+    #[serde(rename = "cdna_volume_µl", alias = "cdna_volume_ul")]
+    #[cfg_attr(
+        feature = "backend",
+        diesel(column_name = cdna_volume_l),
+        garde(inner(range(min = 0.0)))
+    )]
+    pub cdna_volume_ul: Option<f32>,
+    #[serde(rename = "library_volume_µl", alias = "library_volume_ul")]
+    #[cfg_attr(
+        feature = "backend",
+        diesel(column_name = library_volume_l),
+        garde(inner(range(min = 0.0)))
+    )]
+    pub library_volume_ul: Option<f32>,
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    #[cfg(feature = "backend")]
+    use {scamplers_macros::backend_selection, scamplers_schema::library_type_specification};
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    /// The index kit and volumes to use when preparing a library of a given type with a given chemistry
+    #[cfg_attr(feature = "backend", backend_selection(library_type_specification))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct LibraryTypeSpecification {
+        chemistry: String,
+        library_type: String,
+        index_kit: String,
+        #[serde(rename = "cdna_volume_µl")]
+        #[cfg_attr(feature = "backend", diesel(column_name = cdna_volume_l))]
+        cdna_volume_ul: f32,
+        #[serde(rename = "library_volume_µl")]
+        #[cfg_attr(feature = "backend", diesel(column_name = library_volume_l))]
+        library_volume_ul: f32,
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum LibraryTypeSpecificationOrdinalColumn {
+    #[default]
+    Chemistry,
+    LibraryType,
+    IndexKit,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct LibraryTypeSpecificationOrdering {
+    pub column: LibraryTypeSpecificationOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct LibraryTypeSpecificationQuery {
+    pub chemistries: Vec<String>,
+    pub library_types: Vec<String>,
+    pub index_kits: Vec<String>,
+    pub order_by: Vec<LibraryTypeSpecificationOrdering>,
+    pub pagination: Pagination,
+}
@@ -1,15 +1,25 @@
-// use time::OffsetDateTime;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
+use crate::model::{Pagination, SimilaritySearch};
+
 #[cfg(feature = "backend")]
 use {
-    scamplers_macros::{backend_db_enum, backend_insertion},
-    scamplers_schema::{committee_approval, sample_metadata},
+    crate::model::is_offset_paginated,
+    scamplers_macros::{
+        backend_db_enum, backend_insertion, backend_ordering, backend_ordinal_columns_enum,
+        backend_query_request, backend_with_getters,
+    },
+    scamplers_schema::{committee_approval, sample_metadata, sample_status_transition},
 };
 
 #[cfg(feature = "typescript")]
-use scamplers_macros::{frontend_enum, frontend_insertion};
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request, frontend_update,
+    frontend_with_getters,
+};
 
+#[derive(PartialEq)]
 #[cfg_attr(feature = "backend", backend_db_enum)]
 #[cfg_attr(feature = "typescript", frontend_enum)]
 pub enum Species {
@@ -25,6 +35,7 @@ pub enum Species {
     Unknown,
 }
 
+#[derive(PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "backend", backend_db_enum)]
 #[cfg_attr(feature = "typescript", frontend_enum)]
 pub enum ComplianceCommitteeType {
@@ -35,15 +46,121 @@ pub enum ComplianceCommitteeType {
     Unknown,
 }
 
+/// Where a sample is in its lifecycle. A received sample may be processed, and ends up either exhausted, returned to
+/// the submitting lab or disposed of. A sample can also be returned or disposed of without ever being processed.
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum SampleStatus {
+    #[default]
+    Received,
+    InProcessing,
+    Exhausted,
+    Returned,
+    Disposed,
+}
+
+impl SampleStatus {
+    #[must_use]
+    pub fn can_transition_to(self, next: Self) -> bool {
+        use SampleStatus::{Disposed, Exhausted, InProcessing, Received, Returned};
+
+        matches!(
+            (self, next),
+            (Received, InProcessing | Returned | Disposed)
+                | (InProcessing, Exhausted | Returned | Disposed)
+        )
+    }
+}
+
 #[cfg_attr(feature = "backend", backend_insertion(committee_approval))]
 #[cfg_attr(feature = "typescript", frontend_insertion)]
 pub struct NewCommitteeApproval {
     #[serde(default)]
+    #[cfg_attr(feature = "typescript", builder(default))]
     pub sample_id: Option<Uuid>,
     pub institution_id: Uuid,
     pub committee_type: ComplianceCommitteeType,
     #[cfg_attr(feature = "backend", garde(length(min = 1)))]
     pub compliance_identifier: String,
+    /// Defaults to the time at which the approval is recorded
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub approved_at: Option<OffsetDateTime>,
+    /// An approval without an expiry date remains in effect indefinitely
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_after_approval(self.approved_at.as_ref())))
+    )]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+/// Identifies an approval by its institution, committee type and sample, which cannot themselves be changed. An approval
+/// can be expired ahead of time by setting `expires_at` to the present, or made indefinite with `remove_expiry`.
+// `remove_expiry` has no column of its own, so unlike other updates this one doesn't derive a changeset
+#[cfg_attr(
+    feature = "backend",
+    derive(
+        serde::Deserialize,
+        diesel::prelude::Identifiable,
+        valuable::Valuable,
+        garde::Validate,
+        Debug,
+        Default
+    ),
+    diesel(
+        table_name = committee_approval,
+        primary_key(institution_id, committee_type, sample_id)
+    ),
+    serde(default),
+    garde(allow_unvalidated)
+)]
+#[cfg_attr(feature = "typescript", frontend_update)]
+pub struct CommitteeApprovalUpdate {
+    pub institution_id: Uuid,
+    pub committee_type: ComplianceCommitteeType,
+    pub sample_id: Uuid,
+    #[cfg_attr(feature = "backend", garde(inner(length(min = 1))))]
+    pub compliance_identifier: Option<String>,
+    pub approved_at: Option<OffsetDateTime>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_after_approval(self.approved_at.as_ref())))
+    )]
+    pub expires_at: Option<OffsetDateTime>,
+    /// Clears `expires_at`, so cannot be combined with it
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(excludes_expiry(self.expires_at.as_ref())))
+    )]
+    pub remove_expiry: bool,
+}
+
+#[cfg(feature = "backend")]
+fn excludes_expiry(
+    expires_at: Option<&OffsetDateTime>,
+) -> impl FnOnce(&bool, &()) -> garde::Result + '_ {
+    move |remove_expiry, ()| {
+        if *remove_expiry && expires_at.is_some() {
+            Err(garde::Error::new(
+                "cannot both set and remove an approval's expiry",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+fn is_after_approval(
+    approved_at: Option<&OffsetDateTime>,
+) -> impl FnOnce(&Option<OffsetDateTime>, &()) -> garde::Result + '_ {
+    move |expires_at, ()| match (approved_at, expires_at) {
+        (Some(approved_at), Some(expires_at)) if expires_at <= approved_at => Err(
+            garde::Error::new("approval must expire after it was granted"),
+        ),
+        _ => Ok(()),
+    }
 }
 
 #[cfg_attr(feature = "backend", backend_insertion(sample_metadata))]
@@ -53,17 +170,256 @@ pub struct NewSampleMetadata {
     pub name: String,
     pub submitted_by: Uuid,
     pub lab_id: Uuid,
-    // #[cfg_attr(feature = "backend", valuable(skip))]
-    // pub received_at: OffsetDateTime,
+    pub received_at: OffsetDateTime,
     #[cfg_attr(feature = "backend", garde(length(min = 1)))]
     pub species: Vec<Species>,
     #[cfg_attr(feature = "backend", garde(length(min = 1)))]
     pub tissue: String,
-    #[cfg_attr(feature = "backend", diesel(skip_insertion))]
+    /// Human samples must be covered by an IRB approval that is in effect when the sample is received: granted by then and
+    /// not yet expired
+    #[cfg_attr(
+        feature = "backend",
+        diesel(skip_insertion),
+        garde(dive, custom(has_irb_approval(&self.species, &self.received_at)))
+    )]
+    #[cfg_attr(feature = "typescript", builder(default))]
     #[serde(default)]
     pub committee_approvals: Vec<NewCommitteeApproval>,
+    #[cfg_attr(feature = "typescript", builder(default))]
     pub notes: Option<Vec<String>>,
-    // #[cfg_attr(feature = "backend", valuable(skip))]
-    // pub returned_at: Option<OffsetDateTime>,
+    #[cfg_attr(feature = "backend", garde(custom(is_after_receipt(&self.received_at))))]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub returned_at: Option<OffsetDateTime>,
+    #[cfg_attr(feature = "backend", garde(custom(is_specified_with(self.returned_at.as_ref()))))]
+    #[cfg_attr(feature = "typescript", builder(default))]
     pub returned_by: Option<Uuid>,
 }
+
+/// Moves a sample to `to_status`, which must be reachable from its current status
+#[cfg_attr(feature = "backend", backend_insertion(sample_status_transition))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewSampleStatusTransition {
+    #[serde(default)]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub sample_id: Option<Uuid>,
+    pub to_status: SampleStatus,
+    pub transitioned_by: Uuid,
+    /// Defaults to the time at which the transition is recorded
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub transitioned_at: Option<OffsetDateTime>,
+}
+
+#[cfg(feature = "backend")]
+fn has_irb_approval<'a>(
+    species: &'a [Species],
+    received_at: &'a OffsetDateTime,
+) -> impl FnOnce(&Vec<NewCommitteeApproval>, &()) -> garde::Result + 'a {
+    move |committee_approvals, ()| {
+        if !species.contains(&Species::HomoSapiens) {
+            return Ok(());
+        }
+
+        let has_irb_approval = committee_approvals.iter().any(
+            |NewCommitteeApproval {
+                 committee_type,
+                 approved_at,
+                 expires_at,
+                 ..
+             }| {
+                *committee_type == ComplianceCommitteeType::Irb
+                    && approved_at.is_none_or(|approved_at| approved_at <= *received_at)
+                    && expires_at.is_none_or(|expires_at| expires_at > *received_at)
+            },
+        );
+
+        if has_irb_approval {
+            Ok(())
+        } else {
+            Err(garde::Error::new(
+                "human samples require an IRB approval that is in effect when the sample is received",
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+fn is_after_receipt(
+    received_at: &OffsetDateTime,
+) -> impl FnOnce(&Option<OffsetDateTime>, &()) -> garde::Result + '_ {
+    move |returned_at, ()| match returned_at {
+        Some(returned_at) if returned_at < received_at => Err(garde::Error::new(
+            "sample cannot be returned before it was received",
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "backend")]
+fn is_specified_with(
+    returned_at: Option<&OffsetDateTime>,
+) -> impl FnOnce(&Option<Uuid>, &()) -> garde::Result + '_ {
+    move |returned_by, ()| {
+        if returned_by.is_some() == returned_at.is_some() {
+            Ok(())
+        } else {
+            Err(garde::Error::new(
+                "`returned_at` and `returned_by` must be specified together",
+            ))
+        }
+    }
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::model::{
+        institution::InstitutionSummary,
+        sample_metadata::{ComplianceCommitteeType, SampleStatus, Species},
+    };
+
+    #[cfg(feature = "backend")]
+    use {
+        scamplers_macros::backend_selection,
+        scamplers_schema::{committee_approval, sample_metadata, sample_status_transition},
+    };
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(committee_approval))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct CommitteeApproval {
+        sample_id: Uuid,
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        institution: InstitutionSummary,
+        committee_type: ComplianceCommitteeType,
+        compliance_identifier: String,
+        approved_at: OffsetDateTime,
+        expires_at: Option<OffsetDateTime>,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(sample_metadata))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SampleSummary {
+        id: Uuid,
+        name: String,
+        submitted_by: Uuid,
+        lab_id: Uuid,
+        received_at: OffsetDateTime,
+        species: Vec<Species>,
+        tissue: String,
+        notes: Option<Vec<String>>,
+        returned_at: Option<OffsetDateTime>,
+        returned_by: Option<Uuid>,
+        status: SampleStatus,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(sample_status_transition))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SampleStatusTransition {
+        sample_id: Uuid,
+        from_status: SampleStatus,
+        to_status: SampleStatus,
+        transitioned_at: OffsetDateTime,
+        transitioned_by: Uuid,
+    }
+
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct Sample {
+        #[serde(flatten)]
+        summary: SampleSummary,
+        committee_approvals: Vec<CommitteeApproval>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl Sample {
+        #[must_use]
+        pub fn new(summary: SampleSummary, committee_approvals: Vec<CommitteeApproval>) -> Self {
+            Self {
+                summary,
+                committee_approvals,
+            }
+        }
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum SampleOrdinalColumn {
+    #[default]
+    Name,
+    ReceivedAt,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct SampleOrdering {
+    pub column: SampleOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct SampleQuery {
+    pub ids: Vec<Uuid>,
+    pub name: Option<String>,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub similar_name: Option<SimilaritySearch>,
+    pub tissue: Option<String>,
+    pub lab_ids: Vec<Uuid>,
+    pub submitter_ids: Vec<Uuid>,
+    /// Matches samples from at least one of these species
+    pub any_species: Vec<Species>,
+    /// Matches samples from every one of these species, and possibly others
+    pub all_species: Vec<Species>,
+    /// The bounds of the receipt and return date ranges are inclusive
+    pub received_after: Option<OffsetDateTime>,
+    pub received_before: Option<OffsetDateTime>,
+    /// Samples that haven't been returned never fall within a return date range
+    pub returned_after: Option<OffsetDateTime>,
+    pub returned_before: Option<OffsetDateTime>,
+    /// Matches samples that have no approval from this type of committee in effect, either because none was ever
+    /// recorded or because it has expired
+    pub missing_committee_approval: Option<ComplianceCommitteeType>,
+    /// Samples that are still with us are either `received` or `in_processing`
+    pub statuses: Vec<SampleStatus>,
+    pub order_by: Vec<SampleOrdering>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_offset_paginated(self.similar_name.as_ref())))
+    )]
+    pub pagination: Pagination,
+}
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum CommitteeApprovalOrdinalColumn {
+    #[default]
+    ExpiresAt,
+    ApprovedAt,
+    ComplianceIdentifier,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct CommitteeApprovalOrdering {
+    pub column: CommitteeApprovalOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct CommitteeApprovalQuery {
+    pub sample_ids: Vec<Uuid>,
+    pub institution_ids: Vec<Uuid>,
+    pub committee_types: Vec<ComplianceCommitteeType>,
+    /// `true` matches approvals that have expired, `false` matches those still in effect
+    pub expired: Option<bool>,
+    pub order_by: Vec<CommitteeApprovalOrdering>,
+    pub pagination: Pagination,
+}
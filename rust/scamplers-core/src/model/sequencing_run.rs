@@ -1 +1,182 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
 
+use crate::model::Pagination;
+
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_insertion, backend_ordering, backend_ordinal_columns_enum, backend_query_request,
+        backend_update, backend_with_getters,
+    },
+    scamplers_schema::{chromium_sequencing_submissions, sequencing_run},
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request, frontend_update,
+    frontend_with_getters,
+};
+
+/// The sequencing run a library is submitted to is taken from the route, or is the run being created
+#[cfg_attr(
+    feature = "backend",
+    backend_insertion(chromium_sequencing_submissions)
+)]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewSequencingSubmission {
+    pub library_id: Uuid,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub fastq_paths: Option<Vec<String>>,
+    pub submitted_at: OffsetDateTime,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(sequencing_run))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewSequencingRun {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub readable_id: String,
+    pub begun_at: OffsetDateTime,
+    #[cfg_attr(feature = "backend", garde(custom(is_after_start(&self.begun_at))))]
+    pub finished_at: OffsetDateTime,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub notes: Option<Vec<String>>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(dive))]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    #[serde(default)]
+    pub libraries: Vec<NewSequencingSubmission>,
+}
+
+/// When only one of `begun_at` and `finished_at` is given, the backend checks it against the other stored time
+#[cfg_attr(
+    feature = "backend",
+    backend_update(sequencing_run),
+    derive(garde::Validate),
+    garde(allow_unvalidated)
+)]
+#[cfg_attr(feature = "typescript", frontend_update)]
+pub struct SequencingRunUpdate {
+    pub id: Uuid,
+    #[cfg_attr(feature = "backend", garde(inner(length(min = 1))))]
+    pub readable_id: Option<String>,
+    pub begun_at: Option<OffsetDateTime>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_after_optional_start(self.begun_at.as_ref())))
+    )]
+    pub finished_at: Option<OffsetDateTime>,
+    pub notes: Option<Vec<String>>,
+}
+
+#[cfg(feature = "backend")]
+fn is_after_start(
+    begun_at: &OffsetDateTime,
+) -> impl FnOnce(&OffsetDateTime, &()) -> garde::Result + '_ {
+    move |finished_at, ()| {
+        if finished_at < begun_at {
+            Err(garde::Error::new(
+                "sequencing run cannot finish before it began",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+fn is_after_optional_start(
+    begun_at: Option<&OffsetDateTime>,
+) -> impl FnOnce(&Option<OffsetDateTime>, &()) -> garde::Result + '_ {
+    move |finished_at, ()| match (begun_at, finished_at) {
+        (Some(begun_at), Some(finished_at)) => is_after_start(begun_at)(finished_at, &()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    #[cfg(feature = "backend")]
+    use {
+        scamplers_macros::backend_selection,
+        scamplers_schema::{chromium_sequencing_submissions, sequencing_run},
+    };
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(
+        feature = "backend",
+        backend_selection(chromium_sequencing_submissions)
+    )]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SequencingSubmission {
+        library_id: Uuid,
+        sequencing_run_id: Uuid,
+        fastq_paths: Option<Vec<String>>,
+        submitted_at: OffsetDateTime,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(sequencing_run))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SequencingRunReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(sequencing_run))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SequencingRunSummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: SequencingRunReference,
+        readable_id: String,
+        begun_at: OffsetDateTime,
+        finished_at: OffsetDateTime,
+        notes: Option<Vec<String>>,
+    }
+
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SequencingRun {
+        #[serde(flatten)]
+        summary: SequencingRunSummary,
+        libraries: Vec<SequencingSubmission>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl SequencingRun {
+        #[must_use]
+        pub fn new(summary: SequencingRunSummary, libraries: Vec<SequencingSubmission>) -> Self {
+            Self { summary, libraries }
+        }
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum SequencingRunOrdinalColumn {
+    #[default]
+    BegunAt,
+    ReadableId,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct SequencingRunOrdering {
+    pub column: SequencingRunOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct SequencingRunQuery {
+    pub ids: Vec<Uuid>,
+    pub readable_id: Option<String>,
+    pub order_by: Vec<SequencingRunOrdering>,
+    pub pagination: Pagination,
+}
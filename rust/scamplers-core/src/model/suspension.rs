@@ -0,0 +1,218 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::model::{Pagination, measurements::NewSuspensionMeasurement};
+
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_db_enum, backend_insertion, backend_ordering, backend_ordinal_columns_enum,
+        backend_query_request, backend_with_getters,
+    },
+    scamplers_schema::suspension,
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request,
+    frontend_with_getters,
+};
+
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum BiologicalMaterial {
+    #[default]
+    Cells,
+    Nuclei,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(suspension))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewSuspension {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub readable_id: String,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(has_metadata(self.parent_specimen_id.as_ref())))
+    )]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub metadata_id: Option<Uuid>,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub parent_specimen_id: Option<Uuid>,
+    pub biological_material: BiologicalMaterial,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(has_creation_time(self.parent_specimen_id.as_ref())))
+    )]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub created_at: Option<OffsetDateTime>,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub pooled_into_id: Option<Uuid>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_pooling_correctly_specified(self.pooled_into_id.as_ref())))
+    )]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub multiplexing_tag_id: Option<Uuid>,
+    #[cfg_attr(feature = "backend", garde(range(min = 0.0)))]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub lysis_duration_min: Option<f32>,
+    #[cfg_attr(feature = "backend", garde(range(min = 0.0)))]
+    pub target_cell_recovery: f32,
+    #[cfg_attr(feature = "backend", garde(range(min = 0)))]
+    pub target_reads_per_cell: i32,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub notes: Option<Vec<String>>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(length(min = 1)))]
+    pub preparer_ids: Vec<Uuid>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(dive))]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    #[serde(default)]
+    pub measurements: Vec<NewSuspensionMeasurement>,
+}
+
+// The following three functions mirror the check constraints on the `suspension` table so that a client gets a useful
+// error message rather than a database error
+
+#[cfg(feature = "backend")]
+fn has_metadata(
+    parent_specimen_id: Option<&Uuid>,
+) -> impl FnOnce(&Option<Uuid>, &()) -> garde::Result + '_ {
+    move |metadata_id, ()| {
+        if metadata_id.is_some() == parent_specimen_id.is_some() {
+            Err(garde::Error::new(
+                "exactly one of `metadata_id` and `parent_specimen_id` must be specified, as a suspension derived from \
+                 a specimen inherits the specimen's metadata",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+fn has_creation_time(
+    parent_specimen_id: Option<&Uuid>,
+) -> impl FnOnce(&Option<OffsetDateTime>, &()) -> garde::Result + '_ {
+    move |created_at, ()| {
+        if created_at.is_some() == parent_specimen_id.is_some() {
+            Ok(())
+        } else {
+            Err(garde::Error::new(
+                "`created_at` must be specified if and only if the suspension is derived from a specimen",
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+fn is_pooling_correctly_specified(
+    pooled_into_id: Option<&Uuid>,
+) -> impl FnOnce(&Option<Uuid>, &()) -> garde::Result + '_ {
+    move |multiplexing_tag_id, ()| {
+        if multiplexing_tag_id.is_some() == pooled_into_id.is_some() {
+            Ok(())
+        } else {
+            Err(garde::Error::new(
+                "`pooled_into_id` and `multiplexing_tag_id` must be specified together",
+            ))
+        }
+    }
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::model::{
+        measurements::SuspensionMeasurement, person::PersonSummary, suspension::BiologicalMaterial,
+    };
+
+    #[cfg(feature = "backend")]
+    use {scamplers_macros::backend_selection, scamplers_schema::suspension};
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(suspension))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SuspensionReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(suspension))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SuspensionSummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: SuspensionReference,
+        readable_id: String,
+        metadata_id: Option<Uuid>,
+        parent_specimen_id: Option<Uuid>,
+        biological_material: BiologicalMaterial,
+        created_at: Option<OffsetDateTime>,
+        pooled_into_id: Option<Uuid>,
+        multiplexing_tag_id: Option<Uuid>,
+        lysis_duration_min: Option<f32>,
+        target_cell_recovery: f32,
+        target_reads_per_cell: i32,
+        notes: Option<Vec<String>>,
+    }
+
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct Suspension {
+        #[serde(flatten)]
+        summary: SuspensionSummary,
+        preparers: Vec<PersonSummary>,
+        measurements: Vec<SuspensionMeasurement>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl Suspension {
+        #[must_use]
+        pub fn new(
+            summary: SuspensionSummary,
+            preparers: Vec<PersonSummary>,
+            measurements: Vec<SuspensionMeasurement>,
+        ) -> Self {
+            Self {
+                summary,
+                preparers,
+                measurements,
+            }
+        }
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum SuspensionOrdinalColumn {
+    #[default]
+    ReadableId,
+    CreatedAt,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct SuspensionOrdering {
+    pub column: SuspensionOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct SuspensionQuery {
+    pub ids: Vec<Uuid>,
+    pub readable_id: Option<String>,
+    pub metadata_ids: Vec<Uuid>,
+    pub parent_specimen_ids: Vec<Uuid>,
+    pub biological_material: Option<BiologicalMaterial>,
+    pub order_by: Vec<SuspensionOrdering>,
+    pub pagination: Pagination,
+}
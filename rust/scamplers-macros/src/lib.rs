@@ -38,6 +38,16 @@ pub fn frontend_enum(_attr: TokenStream, input: TokenStream) -> TokenStream {
     frontend::enum_(input)
 }
 
+#[proc_macro_attribute]
+pub fn frontend_filter_columns_enum(_attr: TokenStream, input: TokenStream) -> TokenStream {
+    frontend::filter_columns_enum(input)
+}
+
+#[proc_macro_attribute]
+pub fn frontend_json(_attr: TokenStream, input: TokenStream) -> TokenStream {
+    frontend::json(input)
+}
+
 #[proc_macro_attribute]
 pub fn backend_insertion(attr: TokenStream, input: TokenStream) -> TokenStream {
     backend::insertion(attr, input)
@@ -73,6 +83,11 @@ pub fn backend_ordinal_columns_enum(_attr: TokenStream, input: TokenStream) -> T
     backend::ordinal_columns_enum(input)
 }
 
+#[proc_macro_attribute]
+pub fn backend_filter_columns_enum(attr: TokenStream, input: TokenStream) -> TokenStream {
+    backend::filter_columns_enum(attr, input)
+}
+
 #[proc_macro_attribute]
 pub fn backend_db_enum(_attr: TokenStream, input: TokenStream) -> TokenStream {
     backend::db_enum(input)
@@ -1,5 +1,5 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{ItemEnum, ItemImpl, ItemStruct, parse_macro_input};
 
 use crate::macros::common;
@@ -58,7 +58,7 @@ pub fn ordering(input: TokenStream) -> TokenStream {
     let struct_item = parse_macro_input!(input as ItemStruct);
 
     let output = quote! {
-        #[derive(serde::Deserialize, valuable::Valuable, garde::Validate, Debug, Default)]
+        #[derive(serde::Deserialize, valuable::Valuable, garde::Validate, Debug, Default, Clone)]
         #[serde(default)]
         #[garde(allow_unvalidated)]
         #struct_item
@@ -87,7 +87,7 @@ pub fn query_request(input: TokenStream) -> TokenStream {
 }
 
 pub fn ordinal_columns_enum(input: TokenStream) -> TokenStream {
-    let enum_with_derives = derive_enum(input);
+    let enum_with_derives = derive_enum(input, true);
 
     let enum_item = parse_macro_input!(enum_with_derives as ItemEnum);
 
@@ -99,8 +99,44 @@ pub fn ordinal_columns_enum(input: TokenStream) -> TokenStream {
     output.into()
 }
 
+pub fn filter_columns_enum(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let table_name = parse_macro_input!(attr as syn::Path);
+
+    let enum_with_derives = derive_enum(input, false);
+    let enum_item = parse_macro_input!(enum_with_derives as ItemEnum);
+
+    let ItemEnum {
+        ident, variants, ..
+    } = &enum_item;
+
+    // Each variant names a column of the table, whose SQL type determines the conditions that can be applied to it
+    let column_types = variants.iter().map(|variant| {
+        let variant = &variant.ident;
+        let column = format_ident!("{}", heck::AsSnekCase(variant.to_string()).to_string());
+
+        quote! {
+            Self::#variant => <<#table_name::#column as diesel::Expression>::SqlType as crate::model::filter::FilterSqlType>::COLUMN_TYPE
+        }
+    });
+
+    let output = quote! {
+        #[derive(Debug, valuable::Valuable)]
+        #enum_item
+
+        impl crate::model::filter::FilterColumn for #ident {
+            fn column_type(self) -> crate::model::filter::FilterColumnType {
+                match self {
+                    #(#column_types),*
+                }
+            }
+        }
+    };
+
+    output.into()
+}
+
 pub fn db_enum(input: TokenStream) -> TokenStream {
-    let enum_with_derives = derive_enum(input);
+    let enum_with_derives = derive_enum(input, true);
     let enum_item = parse_macro_input!(enum_with_derives as ItemEnum);
 
     let ItemEnum { ident, .. } = &enum_item;
@@ -138,21 +174,26 @@ pub fn db_enum(input: TokenStream) -> TokenStream {
 }
 
 pub fn db_json(input: TokenStream) -> TokenStream {
-    let struct_item = parse_macro_input!(input as ItemStruct);
+    let item = parse_macro_input!(input as syn::Item);
 
-    let ItemStruct { ident, .. } = &struct_item;
+    let (syn::Item::Struct(ItemStruct { ident, .. }) | syn::Item::Enum(ItemEnum { ident, .. })) =
+        &item
+    else {
+        panic!("expected struct or enum");
+    };
 
     let output = quote! {
-        #[derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression, Debug, Default)]
-        #[diesel(sql_type = diesel::sql_types::Text)]
-        #struct_item
+        #[derive(serde::Deserialize, serde::Serialize, diesel::deserialize::FromSqlRow, diesel::expression::AsExpression, valuable::Valuable, garde::Validate, Clone, Debug)]
+        #[diesel(sql_type = diesel::sql_types::Jsonb)]
+        #[garde(allow_unvalidated)]
+        #item
 
         impl diesel::deserialize::FromSql<diesel::sql_types::Jsonb, diesel::pg::Pg> for #ident {
             fn from_sql(bytes: <diesel::pg::Pg as diesel::backend::Backend>::RawValue<'_>) -> diesel::deserialize::Result<Self> {
                 use diesel::{deserialize::FromSql, sql_types, pg::Pg};
 
                 let json: serde_json::Value = FromSql::<sql_types::Jsonb, Pg>::from_sql(bytes)?;
-                Ok(serde_json::from_value(json).unwrap_or_default())
+                Ok(serde_json::from_value(json)?)
             }
         }
 
@@ -163,7 +204,7 @@ pub fn db_json(input: TokenStream) -> TokenStream {
             ) -> diesel::serialize::Result {
                 use diesel::{serialize::ToSql, sql_types, pg::Pg};
 
-                let as_json: serde_json::to_value(self).unwrap();
+                let as_json = serde_json::to_value(self)?;
                 ToSql::<sql_types::Jsonb, Pg>::to_sql(&as_json, &mut out.reborrow())
             }
         }
@@ -20,14 +20,14 @@ pub fn scamplers_client(attr: TokenStream, input: TokenStream) -> TokenStream {
         };
 
         assert!(
-            inner_elems.len() == 2,
-            "expected 2 types (a data type and a return type), found {}",
+            inner_elems.len() == 3,
+            "expected an HTTP method, a data type and a return type, found {} elements",
             inner_elems.len()
         );
 
         assert!(
             inner_elems.iter().all(|e| matches!(e, Expr::Path(_))),
-            "expected paths to types"
+            "expected an HTTP method followed by paths to types"
         );
 
         let inner_elems: Vec<_> = inner_elems
@@ -40,18 +40,26 @@ pub fn scamplers_client(attr: TokenStream, input: TokenStream) -> TokenStream {
             })
             .collect();
 
-        let param_type = inner_elems[0];
+        // Updates share a route with creation, so each pair states the HTTP method that distinguishes them
+        let http_method = inner_elems[0];
+        assert!(
+            ["POST", "PATCH", "PUT"].contains(&http_method.to_string().as_str()),
+            "expected POST, PATCH or PUT, found {http_method}"
+        );
+        let http_method = quote! {reqwest::Method::#http_method};
+
+        let param_type = inner_elems[1];
         let snek_case_param_type = heck::AsSnekCase(param_type.to_string());
 
         let function_name = format_ident!("send_{snek_case_param_type}");
 
-        let return_type = inner_elems[1];
+        let return_type = inner_elems[2];
 
         let method = quote! {
             pub async fn #function_name(&self, data: &#param_type, api_key: Option<String>) -> Result<#return_type, wasm_bindgen::JsValue> {
                 let endpoint = crate::endpoint::Endpoint::<#param_type, #return_type>::route();
 
-                self.send_request(data, &endpoint, api_key).await
+                self.send_request(#http_method, data, &endpoint, api_key).await
             }
         };
 
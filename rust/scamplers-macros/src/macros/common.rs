@@ -5,11 +5,13 @@ use syn::{
     Field, Fields, Ident, ItemEnum, ItemMod, ItemStruct, Type, TypePath, parse_macro_input, parse2,
 };
 
-pub(super) fn derive_enum(input: TokenStream) -> TokenStream {
+pub(super) fn derive_enum(input: TokenStream, with_default: bool) -> TokenStream {
     let item = parse_macro_input!(input as ItemEnum);
 
+    let default = with_default.then(|| quote! {Default,});
+
     let output = quote! {
-        #[derive(serde::Deserialize, serde::Serialize, Default, Clone, Copy)]
+        #[derive(serde::Deserialize, serde::Serialize, #default Clone, Copy)]
         #[serde(rename_all = "snake_case")]
         #item
     };
@@ -1,6 +1,6 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{ItemEnum, ItemImpl, ItemStruct, parse_macro_input};
+use syn::{ItemEnum, ItemImpl, ItemStruct, parse_macro_input, parse_quote};
 
 use crate::macros::common;
 
@@ -123,7 +123,15 @@ pub fn update(input: TokenStream) -> TokenStream {
 }
 
 pub fn enum_(input: TokenStream) -> TokenStream {
-    let enum_with_derives = derive_enum(input);
+    wasm_enum(input, true)
+}
+
+pub fn filter_columns_enum(input: TokenStream) -> TokenStream {
+    wasm_enum(input, false)
+}
+
+fn wasm_enum(input: TokenStream, with_default: bool) -> TokenStream {
+    let enum_with_derives = derive_enum(input, with_default);
 
     let enum_item = parse_macro_input!(enum_with_derives as ItemEnum);
 
@@ -134,3 +142,56 @@ pub fn enum_(input: TokenStream) -> TokenStream {
 
     output.into()
 }
+
+pub fn json(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as syn::Item);
+
+    let (syn::Item::Struct(ItemStruct {
+        ident, generics, ..
+    })
+    | syn::Item::Enum(ItemEnum {
+        ident, generics, ..
+    })) = &item
+    else {
+        panic!("expected struct or enum");
+    };
+
+    // Generic types cross the boundary as JSON too, so their type parameters must be (de)serializable
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(serde::Serialize));
+        param.bounds.push(parse_quote!(serde::de::DeserializeOwned));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // wasm-bindgen can't export enums with data, so these types cross the boundary as plain JS objects
+    let output = quote! {
+        #[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+        #item
+
+        impl #impl_generics wasm_bindgen::describe::WasmDescribe for #ident #ty_generics #where_clause {
+            fn describe() {
+                <wasm_bindgen::JsValue as wasm_bindgen::describe::WasmDescribe>::describe();
+            }
+        }
+
+        impl #impl_generics wasm_bindgen::convert::IntoWasmAbi for #ident #ty_generics #where_clause {
+            type Abi = <wasm_bindgen::JsValue as wasm_bindgen::convert::IntoWasmAbi>::Abi;
+
+            fn into_abi(self) -> Self::Abi {
+                serde_wasm_bindgen::to_value(&self).unwrap().into_abi()
+            }
+        }
+
+        impl #impl_generics wasm_bindgen::convert::FromWasmAbi for #ident #ty_generics #where_clause {
+            type Abi = <wasm_bindgen::JsValue as wasm_bindgen::convert::FromWasmAbi>::Abi;
+
+            unsafe fn from_abi(js: Self::Abi) -> Self {
+                let js = unsafe { wasm_bindgen::JsValue::from_abi(js) };
+                serde_wasm_bindgen::from_value(js).unwrap()
+            }
+        }
+    };
+
+    output.into()
+}
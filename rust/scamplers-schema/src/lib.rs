@@ -16,7 +16,7 @@ diesel::table! {
         gems_id -> Uuid,
         n_amplification_cycles -> Int4,
         storage_location -> Nullable<Text>,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
     }
 }
 
@@ -41,19 +41,20 @@ diesel::table! {
         name -> Text,
         description -> Text,
         definition -> Jsonb,
-        library_types -> Array<Nullable<Text>>,
+        library_types -> Array<Text>,
         cmdline -> Text,
     }
 }
 
 diesel::table! {
-    chip_loading (gems_id, suspension_id, multiplexed_suspension_id) {
+    chip_loading (id) {
         gems_id -> Uuid,
-        suspension_id -> Uuid,
-        multiplexed_suspension_id -> Uuid,
+        suspension_id -> Nullable<Uuid>,
+        multiplexed_suspension_id -> Nullable<Uuid>,
         suspension_volume_loaded -> Jsonb,
         buffer_volume_loaded -> Jsonb,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
+        id -> Uuid,
     }
 }
 
@@ -61,8 +62,11 @@ diesel::table! {
     chromium_dataset (id) {
         id -> Uuid,
         gems_id -> Uuid,
-        metrics -> Array<Nullable<Jsonb>>,
+        metrics -> Array<Jsonb>,
         web_summary -> Text,
+        estimated_number_of_cells -> Nullable<Int4>,
+        median_genes_per_cell -> Nullable<Int4>,
+        sequencing_saturation -> Nullable<Float4>,
     }
 }
 
@@ -77,7 +81,7 @@ diesel::table! {
         number_of_sample_index_pcr_cycles -> Int4,
         target_reads_per_cell -> Int4,
         prepared_at -> Timestamptz,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
     }
 }
 
@@ -106,7 +110,7 @@ diesel::table! {
         run_at -> Timestamptz,
         run_by -> Uuid,
         succeeded -> Bool,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
     }
 }
 
@@ -114,7 +118,7 @@ diesel::table! {
     chromium_sequencing_submissions (library_id, sequencing_run_id) {
         library_id -> Uuid,
         sequencing_run_id -> Uuid,
-        fastq_paths -> Nullable<Array<Nullable<Text>>>,
+        fastq_paths -> Nullable<Array<Text>>,
         submitted_at -> Timestamptz,
     }
 }
@@ -125,6 +129,8 @@ diesel::table! {
         sample_id -> Uuid,
         committee_type -> Text,
         compliance_identifier -> Text,
+        approved_at -> Timestamptz,
+        expires_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -172,6 +178,8 @@ diesel::table! {
         id -> Uuid,
         link -> Text,
         name -> Text,
+        ror_id -> Nullable<Text>,
+        country -> Nullable<Text>,
     }
 }
 
@@ -211,7 +219,7 @@ diesel::table! {
         name -> Text,
         readable_id -> Text,
         pooled_at -> Timestamptz,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
     }
 }
 
@@ -263,11 +271,23 @@ diesel::table! {
         submitted_by -> Uuid,
         lab_id -> Uuid,
         received_at -> Timestamptz,
-        species -> Array<Nullable<Text>>,
+        species -> Array<Text>,
         tissue -> Text,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
         returned_at -> Nullable<Timestamptz>,
         returned_by -> Nullable<Uuid>,
+        status -> Text,
+    }
+}
+
+diesel::table! {
+    sample_status_transition (id) {
+        id -> Uuid,
+        sample_id -> Uuid,
+        from_status -> Text,
+        to_status -> Text,
+        transitioned_at -> Timestamptz,
+        transitioned_by -> Uuid,
     }
 }
 
@@ -278,7 +298,7 @@ diesel::table! {
         readable_id -> Text,
         begun_at -> Timestamptz,
         finished_at -> Timestamptz,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
     }
 }
 
@@ -287,7 +307,7 @@ diesel::table! {
         name -> Text,
         kit -> Text,
         well -> Text,
-        sequences -> Array<Nullable<Text>>,
+        sequences -> Array<Text>,
     }
 }
 
@@ -301,7 +321,7 @@ diesel::table! {
         type_ -> Text,
         embedded_in -> Nullable<Text>,
         preserved_with -> Nullable<Text>,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
     }
 }
 
@@ -323,13 +343,13 @@ diesel::table! {
         parent_specimen_id -> Nullable<Uuid>,
         is_derived -> Nullable<Bool>,
         biological_material -> Text,
-        created_at -> Timestamptz,
+        created_at -> Nullable<Timestamptz>,
         pooled_into_id -> Nullable<Uuid>,
         multiplexing_tag_id -> Nullable<Uuid>,
         lysis_duration_min -> Nullable<Float4>,
         target_cell_recovery -> Float4,
         target_reads_per_cell -> Int4,
-        notes -> Nullable<Array<Nullable<Text>>>,
+        notes -> Nullable<Array<Text>>,
     }
 }
 
@@ -386,6 +406,8 @@ diesel::joinable!(multiplexed_suspension_preparers -> multiplexed_suspension (su
 diesel::joinable!(multiplexed_suspension_preparers -> person (prepared_by));
 diesel::joinable!(person -> institution (institution_id));
 diesel::joinable!(sample_metadata -> lab (lab_id));
+diesel::joinable!(sample_status_transition -> person (transitioned_by));
+diesel::joinable!(sample_status_transition -> sample_metadata (sample_id));
 diesel::joinable!(single_index_set -> index_kit (kit));
 diesel::joinable!(specimen -> sample_metadata (metadata_id));
 diesel::joinable!(specimen_measurement -> person (measured_by));
@@ -426,6 +448,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     multiplexing_tag,
     person,
     sample_metadata,
+    sample_status_transition,
     sequencing_run,
     single_index_set,
     specimen,
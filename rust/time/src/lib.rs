@@ -0,0 +1,203 @@
+use {
+    _time::format_description::well_known::Rfc3339,
+    serde::{Deserialize, Serialize},
+    std::{fmt::Display, str::FromStr},
+};
+
+#[cfg(feature = "backend")]
+use diesel::{deserialize::FromSqlRow, expression::AsExpression, sql_types};
+
+#[cfg_attr(feature = "backend", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "backend", diesel(sql_type = sql_types::Timestamptz))]
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Debug, Hash, Deserialize, Serialize)]
+pub struct OffsetDateTime(#[serde(with = "_time::serde::rfc3339")] _time::OffsetDateTime);
+
+impl Default for OffsetDateTime {
+    fn default() -> Self {
+        Self(_time::OffsetDateTime::UNIX_EPOCH)
+    }
+}
+
+impl Display for OffsetDateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self(inner) = self;
+        let formatted = inner.format(&Rfc3339).map_err(|_| std::fmt::Error)?;
+
+        formatted.fmt(f)
+    }
+}
+
+impl FromStr for OffsetDateTime {
+    type Err = _time::error::Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(_time::OffsetDateTime::parse(s, &Rfc3339)?))
+    }
+}
+
+impl From<_time::OffsetDateTime> for OffsetDateTime {
+    fn from(value: _time::OffsetDateTime) -> Self {
+        Self(value)
+    }
+}
+
+impl From<OffsetDateTime> for _time::OffsetDateTime {
+    fn from(value: OffsetDateTime) -> Self {
+        let OffsetDateTime(inner) = value;
+        inner
+    }
+}
+
+impl OffsetDateTime {
+    #[must_use]
+    pub fn now_utc() -> Self {
+        Self(_time::OffsetDateTime::now_utc())
+    }
+
+    #[must_use]
+    pub fn unix_timestamp(&self) -> i64 {
+        let Self(inner) = self;
+        inner.unix_timestamp()
+    }
+}
+
+#[cfg(feature = "typescript")]
+mod typescript {
+    use std::str::FromStr;
+    use wasm_bindgen::{
+        JsValue,
+        convert::{
+            FromWasmAbi, IntoWasmAbi, OptionFromWasmAbi, OptionIntoWasmAbi, TryFromJsValue,
+            VectorFromWasmAbi, VectorIntoWasmAbi, js_value_vector_from_abi,
+            js_value_vector_into_abi,
+        },
+        describe::{WasmDescribe, WasmDescribeVector},
+    };
+
+    // Timestamps cross the wasm boundary as RFC 3339 strings, which is what both `Date.prototype.toISOString` produces
+    // and the `Date` constructor accepts
+    impl WasmDescribe for super::OffsetDateTime {
+        fn describe() {
+            String::describe();
+        }
+    }
+
+    impl From<super::OffsetDateTime> for JsValue {
+        fn from(val: super::OffsetDateTime) -> Self {
+            val.to_string().into()
+        }
+    }
+
+    impl TryFromJsValue for super::OffsetDateTime {
+        type Error = _time::error::Parse;
+
+        fn try_from_js_value(value: JsValue) -> Result<Self, Self::Error> {
+            Self::from_str(&String::try_from_js_value(value).unwrap())
+        }
+    }
+
+    impl IntoWasmAbi for super::OffsetDateTime {
+        type Abi = <String as IntoWasmAbi>::Abi;
+
+        fn into_abi(self) -> Self::Abi {
+            self.to_string().into_abi()
+        }
+    }
+
+    impl FromWasmAbi for super::OffsetDateTime {
+        type Abi = <Self as IntoWasmAbi>::Abi;
+
+        unsafe fn from_abi(js: Self::Abi) -> Self {
+            unsafe { String::from_abi(js).parse().unwrap() }
+        }
+    }
+
+    impl OptionIntoWasmAbi for super::OffsetDateTime {
+        fn none() -> Self::Abi {
+            <String as OptionIntoWasmAbi>::none()
+        }
+    }
+
+    impl OptionFromWasmAbi for super::OffsetDateTime {
+        fn is_none(abi: &Self::Abi) -> bool {
+            <String as OptionFromWasmAbi>::is_none(abi)
+        }
+    }
+
+    impl WasmDescribeVector for super::OffsetDateTime {
+        fn describe_vector() {
+            Vec::<String>::describe();
+        }
+    }
+
+    impl VectorIntoWasmAbi for super::OffsetDateTime {
+        type Abi = <String as VectorIntoWasmAbi>::Abi;
+
+        fn vector_into_abi(vector: Box<[Self]>) -> Self::Abi {
+            js_value_vector_into_abi(vector)
+        }
+    }
+
+    impl VectorFromWasmAbi for super::OffsetDateTime {
+        type Abi = <String as VectorFromWasmAbi>::Abi;
+
+        unsafe fn vector_from_abi(js: Self::Abi) -> Box<[Self]> {
+            unsafe { js_value_vector_from_abi(js) }
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+mod backend {
+    use {
+        super::OffsetDateTime,
+        diesel::{
+            backend::Backend,
+            deserialize::FromSql,
+            pg::Pg,
+            serialize::{Output, ToSql},
+            sql_types,
+        },
+        valuable::{Valuable, Value},
+    };
+
+    // `valuable` has no notion of a timestamp, so we log the number of seconds since the epoch
+    impl Valuable for OffsetDateTime {
+        fn as_value(&self) -> Value<'_> {
+            Value::I64(self.unix_timestamp())
+        }
+
+        fn visit(&self, visit: &mut dyn valuable::Visit) {
+            visit.visit_value(self.as_value());
+        }
+    }
+
+    impl FromSql<sql_types::Timestamptz, Pg> for OffsetDateTime {
+        fn from_sql(bytes: <Pg as Backend>::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            Ok(Self(<_time::OffsetDateTime as FromSql<
+                sql_types::Timestamptz,
+                Pg,
+            >>::from_sql(bytes)?))
+        }
+    }
+
+    impl ToSql<sql_types::Timestamptz, Pg> for OffsetDateTime {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> diesel::serialize::Result {
+            let Self(inner) = self;
+            <_time::OffsetDateTime as ToSql<sql_types::Timestamptz, Pg>>::to_sql(inner, out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OffsetDateTime;
+
+    #[test]
+    fn rfc3339_round_trip() {
+        let timestamp = "2025-06-17T12:30:00Z";
+
+        let parsed: OffsetDateTime = timestamp.parse().unwrap();
+        assert_eq!(parsed.to_string(), timestamp);
+    }
+}
@@ -1,9 +1,21 @@
 #[cfg(feature = "typescript")]
 use {
     crate::model::{
-        institution::{Institution, NewInstitution},
-        lab::{Lab, NewLab},
-        person::{CreatedUser, NewPerson, Person},
+        cdna::{Cdna, NewCdna},
+        chromium::{ChromiumRun, NewChromiumRun},
+        dataset_metadata::{ChromiumDataset, NewChromiumDataset},
+        institution::{Institution, InstitutionUpdate, NewInstitution},
+        lab::{Lab, LabUpdateWithMembers, NewLab},
+        library::{Library, NewLibrary},
+        library_type_specification::{
+            LibraryTypeSpecification, LibraryTypeSpecificationUpdate, NewLibraryTypeSpecification,
+        },
+        multiplexed_suspension::{MultiplexedSuspension, NewMultiplexedSuspension},
+        person::{CreatedUser, NewPerson, Person, PersonUpdate},
+        sample_metadata::{NewSampleMetadata, Sample},
+        sequencing_run::{NewSequencingRun, SequencingRun, SequencingRunUpdate},
+        specimen::{NewSpecimen, Specimen},
+        suspension::{NewSuspension, Suspension},
     },
     scamplers_macros::scamplers_client,
     serde::{Serialize, de::DeserializeOwned},
@@ -12,7 +24,26 @@ use {
 
 #[cfg(feature = "typescript")]
 #[wasm_bindgen]
-#[scamplers_client([(NewInstitution, Institution), (NewPerson, Person), (NewLab, Lab)])]
+#[scamplers_client([
+    (POST, NewInstitution, Institution),
+    (PATCH, InstitutionUpdate, Institution),
+    (POST, NewPerson, Person),
+    (PATCH, PersonUpdate, Person),
+    (POST, NewLab, Lab),
+    (PATCH, LabUpdateWithMembers, Lab),
+    (POST, NewSampleMetadata, Sample),
+    (POST, NewSpecimen, Specimen),
+    (POST, NewSuspension, Suspension),
+    (POST, NewMultiplexedSuspension, MultiplexedSuspension),
+    (POST, NewChromiumRun, ChromiumRun),
+    (POST, NewCdna, Cdna),
+    (POST, NewLibrary, Library),
+    (POST, NewSequencingRun, SequencingRun),
+    (PATCH, SequencingRunUpdate, SequencingRun),
+    (POST, NewChromiumDataset, ChromiumDataset),
+    (POST, NewLibraryTypeSpecification, LibraryTypeSpecification),
+    (PATCH, LibraryTypeSpecificationUpdate, LibraryTypeSpecification)
+])]
 struct Client {
     backend_url: String,
     client: reqwest::Client,
@@ -46,6 +77,7 @@ impl Client {
 
     async fn send_request<Req, Resp>(
         &self,
+        method: reqwest::Method,
         data: &Req,
         route: &str,
         api_key: Option<String>,
@@ -59,7 +91,9 @@ impl Client {
             client,
         } = self;
 
-        let mut request = client.post(format!("{backend_url}{route}")).json(data);
+        let mut request = client
+            .request(method, format!("{backend_url}{route}"))
+            .json(data);
 
         if let Some(api_key) = api_key {
             request = request.header("X-API-Key", api_key);
@@ -91,7 +125,12 @@ impl Client {
         #[derive(Serialize)]
         struct NewMsLogin<'a>(&'a NewPerson);
 
-        self.send_request(&NewMsLogin(data), &NewPerson::new_user_route(), None)
-            .await
+        self.send_request(
+            reqwest::Method::POST,
+            &NewMsLogin(data),
+            &NewPerson::new_user_route(),
+            None,
+        )
+        .await
     }
 }
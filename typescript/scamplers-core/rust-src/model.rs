@@ -1,15 +1,31 @@
+pub mod cdna;
+pub mod chemistry;
 pub mod chromium;
 pub mod dataset_metadata;
+pub mod filter;
 pub mod index_sets;
 pub mod institution;
 pub mod lab;
+pub mod library;
+pub mod library_type_specification;
+pub mod lineage;
+pub mod measurements;
+pub mod multiplexed_suspension;
 pub mod person;
 pub mod sample_metadata;
+pub mod search;
 pub mod sequencing_run;
+pub mod specimen;
+pub mod suspension;
+pub mod units;
+
+use uuid::Uuid;
 
 #[cfg(feature = "typescript")]
 use wasm_bindgen::prelude::*;
 
+/// Results are paginated either by `offset` or, for large tables, by `cursor`. Paging by cursor stays fast however
+/// deep you go and doesn't skip or repeat records when others are created in the meantime.
 #[cfg_attr(
     feature = "typescript",
     derive(Clone, serde::Serialize),
@@ -23,6 +39,11 @@ use wasm_bindgen::prelude::*;
 pub struct Pagination {
     pub limit: i64,
     pub offset: i64,
+    /// The `next_cursor` of the previous page, which should be treated as opaque. When set, `offset` is ignored.
+    /// Resources that aren't identified by a UUID are always paginated by offset.
+    pub cursor: Option<Uuid>,
+    /// Counting every match takes longer than fetching a page, so `total_count` is only set if this is
+    pub count_total: bool,
 }
 
 impl Default for Pagination {
@@ -30,17 +51,357 @@ impl Default for Pagination {
         Self {
             limit: 500,
             offset: 0,
+            cursor: None,
+            count_total: false,
+        }
+    }
+}
+
+/// Matches names that are similar to `text`, which tolerates typos, and ranks the closest matches first. Ranked results
+/// are always paginated by offset, so a query with a similarity search can't set `pagination.cursor`.
+#[cfg_attr(
+    feature = "typescript",
+    derive(Clone, serde::Serialize),
+    wasm_bindgen(getter_with_clone, setter)
+)]
+#[cfg_attr(
+    feature = "backend",
+    derive(serde::Deserialize, valuable::Valuable, garde::Validate, Debug),
+    serde(default)
+)]
+pub struct SimilaritySearch {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub text: String,
+    /// How similar a name must be to `text` to match, from 0 to 1 (identical)
+    #[cfg_attr(feature = "backend", garde(range(min = 0.0, max = 1.0)))]
+    pub threshold: f32,
+}
+
+impl Default for SimilaritySearch {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            threshold: 0.3,
+        }
+    }
+}
+
+/// Rejects a cursor alongside a similarity search, whose results are paginated by offset
+#[cfg(feature = "backend")]
+pub(crate) fn is_offset_paginated(
+    similar_name: Option<&SimilaritySearch>,
+) -> impl FnOnce(&Pagination, &()) -> garde::Result + '_ {
+    move |pagination, ()| {
+        if similar_name.is_some() && pagination.cursor.is_some() {
+            Err(garde::Error::new(
+                "a cursor cannot be combined with a similarity search",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// An ordering that can be written in a query string, as a column name that's optionally followed by `:desc`
+pub trait OrderingParam: Sized {
+    fn column_name(&self) -> &'static str;
+    fn is_descending(&self) -> bool;
+    fn from_column_name(name: &str, descending: bool) -> Option<Self>;
+}
+
+/// Defines the query-string form of a search request, so that searches can be made with `GET` and shared as links, and
+/// implements conversions between it and the query. Every field of the query can be written in the query string:
+/// - `ids` and `order_by` may be repeated, as in `?ids=...&ids=...&order_by=name:desc`
+/// - `similar_name` and `similarity_threshold` make up `similar_name`
+/// - `filter` is the JSON form of the query's filter
+/// - `limit`, `offset`, `cursor`, and `count_total` make up `pagination`
+///
+/// Any other parameter is rejected. The query-specific fields in the final list must be `Option`s, and are written as
+/// they are in the query.
+macro_rules! impl_search_params {
+    (
+        $query:ident,
+        $params:ident,
+        $ordering:ident,
+        $ordinal_col_enum:ident,
+        $filter_col_enum:ident,
+        [$(($variant:ident, $column_name:literal)),*]
+        $(, [$($field:ident: $field_type:ty),*])?
+    ) => {
+        impl $crate::model::OrderingParam for $ordering {
+            fn column_name(&self) -> &'static str {
+                match self.column {
+                    $($ordinal_col_enum::$variant => $column_name,)*
+                }
+            }
+
+            fn is_descending(&self) -> bool {
+                self.descending
+            }
+
+            fn from_column_name(name: &str, descending: bool) -> Option<Self> {
+                let column = match name {
+                    $($column_name => $ordinal_col_enum::$variant,)*
+                    _ => return None,
+                };
+
+                Some(Self { column, descending })
+            }
+        }
+
+        #[doc = concat!("The query-string form of a [`", stringify!($query), "`]")]
+        #[derive(serde::Serialize, serde::Deserialize, Default)]
+        #[cfg_attr(feature = "backend", derive(Debug))]
+        #[serde(default, deny_unknown_fields)]
+        pub struct $params {
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            pub ids: Vec<uuid::Uuid>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub name: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub similar_name: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub similarity_threshold: Option<f32>,
+            $($(
+                #[serde(skip_serializing_if = "Option::is_none")]
+                pub $field: $field_type,
+            )*)?
+            #[serde(
+                skip_serializing_if = "crate::model::filter::Filter::is_empty",
+                with = "crate::model::filter_param"
+            )]
+            pub filter: $crate::model::filter::Filter<$filter_col_enum>,
+            #[serde(skip_serializing_if = "Vec::is_empty", with = "crate::model::ordering_params")]
+            pub order_by: Vec<$ordering>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub limit: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub offset: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub cursor: Option<uuid::Uuid>,
+            #[serde(skip_serializing_if = "std::ops::Not::not")]
+            pub count_total: bool,
+        }
+
+        impl From<$params> for $query {
+            fn from(params: $params) -> Self {
+                let $params {
+                    ids,
+                    name,
+                    similar_name,
+                    similarity_threshold,
+                    $($($field,)*)?
+                    filter,
+                    order_by,
+                    limit,
+                    offset,
+                    cursor,
+                    count_total,
+                } = params;
+
+                let mut query = Self {
+                    ids,
+                    name,
+                    similar_name: $crate::model::SimilaritySearch::from_params(
+                        similar_name,
+                        similarity_threshold,
+                    ),
+                    $($($field,)*)?
+                    filter,
+                    pagination: $crate::model::Pagination::from_params(
+                        limit,
+                        offset,
+                        cursor,
+                        count_total,
+                    ),
+                    ..Default::default()
+                };
+
+                // An empty `order_by` keeps the default ordering
+                if !order_by.is_empty() {
+                    query.order_by = order_by;
+                }
+
+                query
+            }
+        }
+
+        impl From<&$query> for $params {
+            fn from(query: &$query) -> Self {
+                let (similar_name, similarity_threshold) =
+                    $crate::model::SimilaritySearch::to_params(query.similar_name.as_ref());
+                let (limit, offset) = query.pagination.to_params();
+
+                Self {
+                    ids: query.ids.clone(),
+                    name: query.name.clone(),
+                    similar_name,
+                    similarity_threshold,
+                    $($($field: query.$field.clone(),)*)?
+                    filter: query.filter.clone(),
+                    order_by: query.order_by.clone(),
+                    limit,
+                    offset,
+                    cursor: query.pagination.cursor,
+                    count_total: query.pagination.count_total,
+                }
+            }
         }
+    };
+}
+pub(crate) use impl_search_params;
+
+// Values that are left at their defaults are left out of the query string
+impl Pagination {
+    fn from_params(
+        limit: Option<i64>,
+        offset: Option<i64>,
+        cursor: Option<Uuid>,
+        count_total: bool,
+    ) -> Self {
+        let default = Self::default();
+
+        Self {
+            limit: limit.unwrap_or(default.limit),
+            offset: offset.unwrap_or(default.offset),
+            cursor,
+            count_total,
+        }
+    }
+
+    fn to_params(&self) -> (Option<i64>, Option<i64>) {
+        let default = Self::default();
+
+        (
+            Some(self.limit).filter(|l| *l != default.limit),
+            Some(self.offset).filter(|o| *o != default.offset),
+        )
+    }
+}
+
+impl SimilaritySearch {
+    // A threshold without any text becomes a search for empty text, which fails validation rather than being dropped
+    fn from_params(text: Option<String>, threshold: Option<f32>) -> Option<Self> {
+        if text.is_none() && threshold.is_none() {
+            return None;
+        }
+
+        let default = Self::default();
+
+        Some(Self {
+            text: text.unwrap_or(default.text),
+            threshold: threshold.unwrap_or(default.threshold),
+        })
+    }
+
+    fn to_params(search: Option<&Self>) -> (Option<String>, Option<f32>) {
+        search.map_or((None, None), |Self { text, threshold }| {
+            (Some(text.clone()), Some(*threshold))
+        })
     }
 }
 
+pub(crate) mod ordering_params {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    use super::OrderingParam;
+
+    pub(crate) fn serialize<O: OrderingParam, S: Serializer>(
+        order_by: &[O],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(order_by.iter().map(|o| {
+            if o.is_descending() {
+                format!("{}:desc", o.column_name())
+            } else {
+                o.column_name().to_string()
+            }
+        }))
+    }
+
+    pub(crate) fn deserialize<'de, O: OrderingParam, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<O>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|param| {
+                let (name, descending) = match param.split_once(':') {
+                    None => (param.as_str(), false),
+                    Some((name, "asc")) => (name, false),
+                    Some((name, "desc")) => (name, true),
+                    Some(_) => {
+                        return Err(D::Error::custom(format!(
+                            "'{param}' should be a column, optionally followed by ':asc' or ':desc'"
+                        )));
+                    }
+                };
+
+                O::from_column_name(name, descending)
+                    .ok_or_else(|| D::Error::custom(format!("cannot order by '{name}'")))
+            })
+            .collect()
+    }
+}
+
+// A filter is written in a query string as JSON
+pub(crate) mod filter_param {
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned, de::Error as _,
+        ser::Error as _,
+    };
+
+    use super::filter::Filter;
+
+    pub(crate) fn serialize<C: Serialize, S: Serializer>(
+        filter: &Filter<C>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let json = serde_json::to_string(filter).map_err(S::Error::custom)?;
+        serializer.serialize_str(&json)
+    }
+
+    pub(crate) fn deserialize<'de, C: DeserializeOwned, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Filter<C>, D::Error> {
+        let json = String::deserialize(deserializer)?;
+        serde_json::from_str(&json).map_err(D::Error::custom)
+    }
+}
+
+/// One page of search results
+#[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+#[cfg_attr(feature = "typescript", derive(serde::Deserialize))]
+pub struct Page<T> {
+    pub results: Vec<T>,
+    /// Pass this as `pagination.cursor` to fetch the next page. Unset on the last page.
+    pub next_cursor: Option<Uuid>,
+    pub total_count: Option<i64>,
+}
+
 #[cfg(feature = "typescript")]
 #[wasm_bindgen]
 impl Pagination {
     #[wasm_bindgen(constructor)]
     #[must_use]
     pub fn new(limit: i64, offset: i64) -> Self {
-        Self { limit, offset }
+        Self {
+            limit,
+            offset,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "typescript")]
+#[wasm_bindgen]
+impl SimilaritySearch {
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            ..Default::default()
+        }
     }
 }
 
@@ -109,4 +470,150 @@ mod tests {
             }
         );
     }
+
+    #[cfg(feature = "backend")]
+    #[test]
+    fn query_string_round_trip() {
+        use pretty_assertions::assert_eq;
+
+        use super::{Pagination, SimilaritySearch, filter::Filter};
+        use crate::{
+            endpoint::Endpoint,
+            model::person::{
+                PersonFilterColumn, PersonOrdering, PersonOrdinalColumn, PersonQuery,
+                PersonSearchParams, PersonSummary,
+            },
+        };
+
+        let ids = vec![uuid::Uuid::default(); 2];
+        let query = PersonQuery {
+            ids: ids.clone(),
+            name: Some("ahmed said".to_string()),
+            similar_name: Some(SimilaritySearch {
+                text: "ahmed".to_string(),
+                threshold: 0.5,
+            }),
+            email: Some("ahmed@example.com".to_string()),
+            filter: Filter::IsNull {
+                column: PersonFilterColumn::Orcid,
+            },
+            order_by: vec![
+                PersonOrdering {
+                    column: PersonOrdinalColumn::Email,
+                    descending: true,
+                },
+                PersonOrdering::default(),
+            ],
+            pagination: Pagination {
+                limit: 10,
+                count_total: true,
+                ..Default::default()
+            },
+        };
+
+        let route = Endpoint::<PersonQuery, PersonSummary>::route_with_query(&query).unwrap();
+        let (path, query_string) = route.split_once('?').unwrap();
+        let nil = "00000000-0000-0000-0000-000000000000";
+
+        assert_eq!(path, "/people/search");
+        assert_eq!(
+            query_string,
+            format!(
+                "ids={nil}&ids={nil}&name=ahmed+said&similar_name=ahmed&similarity_threshold=0.5&\
+                 email=ahmed%40example.com&filter=%7B%22is_null%22%3A%7B%22column%22%3A%22orcid%22%7D%7D&\
+                 order_by=email%3Adesc&order_by=name&limit=10&count_total=true"
+            )
+        );
+
+        let params: PersonSearchParams = serde_html_form::from_str(query_string).unwrap();
+        let parsed = PersonQuery::from(params);
+
+        assert_eq!(
+            serde_html_form::to_string(PersonSearchParams::from(&parsed)).unwrap(),
+            query_string
+        );
+        assert_eq!(parsed.ids, ids);
+        assert_eq!(parsed.similar_name.unwrap().text, "ahmed");
+        assert_eq!(parsed.email, query.email);
+        assert!(matches!(
+            parsed.filter,
+            Filter::IsNull {
+                column: PersonFilterColumn::Orcid
+            }
+        ));
+        assert_eq!(parsed.pagination.offset, 0);
+    }
+
+    #[cfg(feature = "backend")]
+    #[test]
+    fn empty_query_string() {
+        use crate::{
+            endpoint::Endpoint,
+            model::lab::{LabQuery, LabSummary},
+        };
+
+        let route = Endpoint::<LabQuery, LabSummary>::route_with_query(&LabQuery {
+            order_by: Vec::new(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(route, "/labs/search");
+    }
+
+    #[cfg(feature = "backend")]
+    #[test]
+    fn invalid_search_params() {
+        use garde::Validate;
+
+        use crate::model::{
+            lab::{LabQuery, LabSearchParams},
+            person::PersonSearchParams,
+            search::GlobalSearchQuery,
+        };
+
+        for (query_string, message) in [
+            ("order_by=budget", "cannot order by 'budget'"),
+            (
+                "order_by=name:up",
+                "'name:up' should be a column, optionally followed by ':asc' or ':desc'",
+            ),
+            ("email=rick%40example.com", "unknown field `email`"),
+            ("pi=rick", "unknown field `pi`"),
+            (
+                "filter=%7B%22eq%22%3A%7B%22column%22%3A%22budget%22%2C%22value%22%3A%221%22%7D%7D",
+                "unknown variant `budget`",
+            ),
+        ] {
+            let err = serde_html_form::from_str::<LabSearchParams>(query_string).unwrap_err();
+
+            assert!(err.to_string().contains(message), "{err}");
+        }
+
+        serde_html_form::from_str::<PersonSearchParams>("email=rick%40example.com").unwrap();
+
+        // A threshold on its own isn't dropped, but fails validation for lack of anything to compare names to
+        let params: LabSearchParams =
+            serde_html_form::from_str("similarity_threshold=0.5").unwrap();
+        LabQuery::from(params).validate().unwrap_err();
+
+        // Ranked results are paginated by offset, so a cursor would otherwise be ignored
+        let params: LabSearchParams = serde_html_form::from_str(
+            "similar_name=rick&cursor=0197a5a4-7e4b-7c2e-9b1a-3f8e6d2c1b0a",
+        )
+        .unwrap();
+        let err = LabQuery::from(params).validate().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("a cursor cannot be combined with a similarity search"),
+            "{err}"
+        );
+
+        let err =
+            serde_html_form::from_str::<GlobalSearchQuery>("q=rick&treshold=0.5").unwrap_err();
+        assert!(
+            err.to_string().contains("unknown field `treshold`"),
+            "{err}"
+        );
+    }
 }
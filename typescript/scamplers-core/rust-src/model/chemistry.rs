@@ -0,0 +1,58 @@
+use crate::model::Pagination;
+
+#[cfg(feature = "backend")]
+use scamplers_macros::{
+    backend_ordering, backend_ordinal_columns_enum, backend_query_request, backend_with_getters,
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_ordering, frontend_query_request, frontend_with_getters,
+};
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    #[cfg(feature = "backend")]
+    use {scamplers_macros::backend_selection, scamplers_schema::chemistry};
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    /// A 10x Genomics chemistry. Its full Cell Ranger definition is stored alongside it, but clients only need to know
+    /// which library types it supports and how to pass it to Cell Ranger.
+    #[cfg_attr(feature = "backend", backend_selection(chemistry))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct Chemistry {
+        name: String,
+        description: String,
+        library_types: Vec<String>,
+        cmdline: String,
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum ChemistryOrdinalColumn {
+    #[default]
+    Name,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct ChemistryOrdering {
+    pub column: ChemistryOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct ChemistryQuery {
+    pub names: Vec<String>,
+    pub description: Option<String>,
+    /// Only return chemistries that support this library type
+    pub library_type: Option<String>,
+    pub order_by: Vec<ChemistryOrdering>,
+    pub pagination: Pagination,
+}
@@ -1 +1,257 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
 
+use crate::model::{Pagination, units::Quantity};
+
+#[cfg(feature = "backend")]
+use {
+    crate::model::units::{Dimension, is_quantity_of},
+    scamplers_macros::{
+        backend_db_enum, backend_insertion, backend_ordering, backend_ordinal_columns_enum,
+        backend_query_request, backend_with_getters,
+    },
+    scamplers_schema::{chip_loading, chromium_run, gems},
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request,
+    frontend_with_getters,
+};
+
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum ChipType {
+    ChipA,
+    ChipB,
+    ChipE,
+    ChipG,
+    ChipH,
+    #[default]
+    ChipJ,
+    ChipK,
+    ChipQ,
+    GemXThreePrime,
+    GemXFivePrime,
+}
+
+impl ChipType {
+    /// The number of channels on a chip, each of which produces one GEMs. Every chip we run has eight.
+    pub const MAX_WELLS: usize = 8;
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(chip_loading))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewChipLoading {
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub suspension_id: Option<Uuid>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(has_suspension(self.suspension_id.as_ref())))
+    )]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub multiplexed_suspension_id: Option<Uuid>,
+    #[cfg_attr(feature = "backend", garde(custom(is_quantity_of(Dimension::Volume))))]
+    pub suspension_volume_loaded: Quantity,
+    #[cfg_attr(feature = "backend", garde(custom(is_quantity_of(Dimension::Volume))))]
+    pub buffer_volume_loaded: Quantity,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub notes: Option<Vec<String>>,
+}
+
+// Mirrors the `has_suspension` check constraint on `chip_loading`
+#[cfg(feature = "backend")]
+fn has_suspension(
+    suspension_id: Option<&Uuid>,
+) -> impl FnOnce(&Option<Uuid>, &()) -> garde::Result + '_ {
+    move |multiplexed_suspension_id, ()| {
+        if suspension_id.is_some() == multiplexed_suspension_id.is_some() {
+            Err(garde::Error::new(
+                "exactly one of `suspension_id` and `multiplexed_suspension_id` must be specified",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// `n_samples` is not part of this struct because it is computed from `loading`
+#[cfg_attr(feature = "backend", backend_insertion(gems))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewGems {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub readable_id: String,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub chemistry: Option<String>,
+    #[cfg_attr(
+        feature = "backend",
+        diesel(skip_insertion),
+        garde(length(min = 1), dive)
+    )]
+    pub loading: Vec<NewChipLoading>,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(chromium_run))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewChromiumRun {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub readable_id: String,
+    pub chip: ChipType,
+    pub run_at: OffsetDateTime,
+    pub run_by: Uuid,
+    pub succeeded: bool,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub notes: Option<Vec<String>>,
+    #[cfg_attr(
+        feature = "backend",
+        diesel(skip_insertion),
+        garde(length(min = 1), custom(fits_on_chip(self.chip)), dive)
+    )]
+    pub gems: Vec<NewGems>,
+}
+
+#[cfg(feature = "backend")]
+fn fits_on_chip(chip: ChipType) -> impl FnOnce(&Vec<NewGems>, &()) -> garde::Result {
+    move |gems, ()| {
+        if gems.len() > ChipType::MAX_WELLS {
+            return Err(garde::Error::new(format!(
+                "{} GEMs were specified, but a {chip:?} chip only has {} wells",
+                gems.len(),
+                ChipType::MAX_WELLS
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::model::{chromium::ChipType, units::Quantity};
+
+    #[cfg(feature = "backend")]
+    use {
+        scamplers_macros::backend_selection,
+        scamplers_schema::{chip_loading, chromium_run, gems},
+    };
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(chip_loading))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct ChipLoading {
+        id: Uuid,
+        gems_id: Uuid,
+        suspension_id: Option<Uuid>,
+        multiplexed_suspension_id: Option<Uuid>,
+        suspension_volume_loaded: Quantity,
+        buffer_volume_loaded: Quantity,
+        notes: Option<Vec<String>>,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(gems))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct GemsReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(gems))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct GemsSummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: GemsReference,
+        readable_id: String,
+        n_samples: i32,
+        chemistry: Option<String>,
+        chromium_run_id: Uuid,
+    }
+
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct Gems {
+        #[serde(flatten)]
+        summary: GemsSummary,
+        loading: Vec<ChipLoading>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl Gems {
+        #[must_use]
+        pub fn new(summary: GemsSummary, loading: Vec<ChipLoading>) -> Self {
+            Self { summary, loading }
+        }
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(chromium_run))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct ChromiumRunReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(chromium_run))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct ChromiumRunSummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: ChromiumRunReference,
+        readable_id: String,
+        chip: ChipType,
+        run_at: OffsetDateTime,
+        run_by: Uuid,
+        succeeded: bool,
+        notes: Option<Vec<String>>,
+    }
+
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct ChromiumRun {
+        #[serde(flatten)]
+        summary: ChromiumRunSummary,
+        gems: Vec<Gems>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl ChromiumRun {
+        #[must_use]
+        pub fn new(summary: ChromiumRunSummary, gems: Vec<Gems>) -> Self {
+            Self { summary, gems }
+        }
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum ChromiumRunOrdinalColumn {
+    #[default]
+    RunAt,
+    ReadableId,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct ChromiumRunOrdering {
+    pub column: ChromiumRunOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct ChromiumRunQuery {
+    pub ids: Vec<Uuid>,
+    pub readable_id: Option<String>,
+    pub chip: Option<ChipType>,
+    pub run_by: Option<Uuid>,
+    pub succeeded: Option<bool>,
+    pub order_by: Vec<ChromiumRunOrdering>,
+    pub pagination: Pagination,
+}
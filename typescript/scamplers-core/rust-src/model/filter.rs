@@ -0,0 +1,226 @@
+#[cfg(feature = "typescript")]
+use scamplers_macros::frontend_json;
+
+/// A condition on the columns of a resource, or a combination of such conditions. `C` is the resource's enum of
+/// filterable columns, so a filter that names any other column is rejected when it's deserialized.
+///
+/// Values are always passed as strings and must be parseable as the column's type. The default filter is an empty `and`,
+/// which matches everything.
+#[cfg_attr(
+    feature = "backend",
+    derive(serde::Deserialize, serde::Serialize, Clone, Debug)
+)]
+#[cfg_attr(feature = "typescript", frontend_json)]
+#[serde(rename_all = "snake_case")]
+pub enum Filter<C> {
+    And(Vec<Filter<C>>),
+    /// An empty `or` matches nothing
+    Or(Vec<Filter<C>>),
+    Not(Box<Filter<C>>),
+    Eq {
+        column: C,
+        value: String,
+    },
+    /// A case-insensitive SQL `LIKE` pattern, in which `%` matches any sequence of characters
+    Ilike {
+        column: C,
+        pattern: String,
+    },
+    In {
+        column: C,
+        values: Vec<String>,
+    },
+    /// Both bounds are inclusive, and at least one must be specified
+    Range {
+        column: C,
+        min: Option<String>,
+        max: Option<String>,
+    },
+    IsNull {
+        column: C,
+    },
+}
+
+impl<C> Default for Filter<C> {
+    fn default() -> Self {
+        Self::And(Vec::new())
+    }
+}
+
+impl<C> Filter<C> {
+    /// Whether this is the default filter
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::And(filters) if filters.is_empty())
+    }
+}
+
+/// The type of a filterable column, which determines the conditions that can be applied to it
+#[cfg(feature = "backend")]
+#[derive(Clone, Copy, PartialEq)]
+pub enum FilterColumnType {
+    Uuid,
+    Text,
+    NullableText,
+}
+
+/// Implemented by `backend_filter_columns_enum`, which reads each column's type from the schema
+#[cfg(feature = "backend")]
+pub trait FilterColumn: Copy {
+    fn column_type(self) -> FilterColumnType;
+}
+
+/// The SQL types of the columns that can be filtered on
+#[cfg(feature = "backend")]
+pub trait FilterSqlType {
+    const COLUMN_TYPE: FilterColumnType;
+}
+
+#[cfg(feature = "backend")]
+impl FilterSqlType for diesel::sql_types::Uuid {
+    const COLUMN_TYPE: FilterColumnType = FilterColumnType::Uuid;
+}
+
+#[cfg(feature = "backend")]
+impl FilterSqlType for diesel::sql_types::Text {
+    const COLUMN_TYPE: FilterColumnType = FilterColumnType::Text;
+}
+
+#[cfg(feature = "backend")]
+impl FilterSqlType for diesel::sql_types::Nullable<diesel::sql_types::Text> {
+    const COLUMN_TYPE: FilterColumnType = FilterColumnType::NullableText;
+}
+
+#[cfg(feature = "backend")]
+impl<C: FilterColumn> Filter<C> {
+    fn check(&self) -> Result<(), String> {
+        use FilterColumnType::{NullableText, Text, Uuid};
+
+        let is_uuid = |value: &String| value.parse::<uuid::Uuid>().is_ok();
+
+        match self {
+            Self::And(filters) | Self::Or(filters) => {
+                for filter in filters {
+                    filter.check()?;
+                }
+            }
+            Self::Not(filter) => filter.check()?,
+            Self::Eq { column, value } => {
+                if column.column_type() == Uuid && !is_uuid(value) {
+                    return Err(format!("'{value}' is not a UUID"));
+                }
+            }
+            Self::In { column, values } => {
+                if let Some(value) = values
+                    .iter()
+                    .find(|v| column.column_type() == Uuid && !is_uuid(v))
+                {
+                    return Err(format!("'{value}' is not a UUID"));
+                }
+            }
+            Self::Ilike { column, .. } => {
+                if column.column_type() == Uuid {
+                    return Err("ilike can only be applied to text columns".to_string());
+                }
+            }
+            Self::Range { column, min, max } => {
+                if !matches!(column.column_type(), Text | NullableText) {
+                    return Err("range can only be applied to text columns".to_string());
+                }
+                if min.is_none() && max.is_none() {
+                    return Err("range must specify at least one of min and max".to_string());
+                }
+            }
+            Self::IsNull { column } => {
+                if column.column_type() != NullableText {
+                    return Err("is_null can only be applied to nullable columns".to_string());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that each condition in a filter can be applied to the column it names
+#[cfg(feature = "backend")]
+pub(crate) fn is_valid_filter<C: FilterColumn>() -> impl FnOnce(&Filter<C>, &()) -> garde::Result {
+    |filter, ()| filter.check().map_err(garde::Error::new)
+}
+
+#[cfg(all(test, feature = "backend"))]
+mod tests {
+    use garde::Validate;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{FilterColumn, FilterColumnType};
+    use crate::model::{lab::LabQuery, person::PersonFilterColumn};
+
+    fn lab_query(filter: &serde_json::Value) -> serde_json::Result<LabQuery> {
+        serde_json::from_value(json!({ "filter": filter }))
+    }
+
+    #[test]
+    fn valid_filter() {
+        let query = lab_query(&json!({
+            "or": [
+                { "ilike": { "column": "name", "pattern": "%sanchez%" } },
+                { "eq": { "column": "pi_id", "value": "0197a5e6-3b2c-7f0e-8a4d-5c1b9e2f7a30" } },
+                { "not": { "range": { "column": "delivery_dir", "min": "m" } } }
+            ]
+        }))
+        .unwrap();
+
+        query.validate().unwrap();
+    }
+
+    #[test]
+    fn column_types_from_schema() {
+        let column_types = [
+            (PersonFilterColumn::InstitutionId, FilterColumnType::Uuid),
+            (PersonFilterColumn::Name, FilterColumnType::Text),
+            (PersonFilterColumn::Email, FilterColumnType::NullableText),
+        ];
+
+        for (column, column_type) in column_types {
+            assert!(column.column_type() == column_type);
+        }
+    }
+
+    #[test]
+    fn unknown_column() {
+        let err = lab_query(&json!({ "eq": { "column": "budget", "value": "1" } })).unwrap_err();
+
+        assert!(err.to_string().contains("unknown variant `budget`"));
+    }
+
+    #[test]
+    fn invalid_conditions() {
+        let invalid = [
+            (
+                json!({ "eq": { "column": "pi_id", "value": "rick" } }),
+                "'rick' is not a UUID",
+            ),
+            (
+                json!({ "and": [{ "ilike": { "column": "id", "pattern": "%" } }] }),
+                "ilike can only be applied to text columns",
+            ),
+            (
+                json!({ "not": { "is_null": { "column": "name" } } }),
+                "is_null can only be applied to nullable columns",
+            ),
+            (
+                json!({ "range": { "column": "name" } }),
+                "range must specify at least one of min and max",
+            ),
+        ];
+
+        for (filter, message) in invalid {
+            let report = lab_query(&filter).unwrap().validate().unwrap_err();
+            let (_, error) = report.iter().next().unwrap();
+
+            assert_eq!(error.message(), message);
+        }
+    }
+}
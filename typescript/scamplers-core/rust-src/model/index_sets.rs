@@ -1 +1,96 @@
+use crate::model::Pagination;
 
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_ordering, backend_ordinal_columns_enum, backend_query_request, backend_selection,
+    },
+    scamplers_schema::{dual_index_set, single_index_set},
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{frontend_enum, frontend_json, frontend_ordering, frontend_query_request};
+
+/// A single-index set, whose four oligos are pooled in one well
+#[cfg_attr(feature = "backend", backend_selection(single_index_set))]
+#[cfg_attr(feature = "typescript", frontend_json)]
+pub struct SingleIndexSetSummary {
+    pub name: String,
+    pub kit: String,
+    pub well: String,
+    pub sequences: Vec<String>,
+}
+
+/// A dual-index set. The i5 index is read differently depending on the sequencer, so 10x Genomics publishes both
+/// workflows.
+#[cfg_attr(feature = "backend", backend_selection(dual_index_set))]
+#[cfg_attr(feature = "typescript", frontend_json)]
+pub struct DualIndexSetSummary {
+    pub name: String,
+    pub kit: String,
+    pub well: String,
+    pub index_i7: String,
+    pub index2_workflow_a_i5: String,
+    pub index2_workflow_b_i5: String,
+}
+
+#[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+#[cfg_attr(feature = "typescript", frontend_json)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IndexSet {
+    Single(SingleIndexSetSummary),
+    Dual(DualIndexSetSummary),
+}
+
+impl IndexSet {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Single(SingleIndexSetSummary { name, .. })
+            | Self::Dual(DualIndexSetSummary { name, .. }) => name,
+        }
+    }
+
+    #[must_use]
+    pub fn kit(&self) -> &str {
+        match self {
+            Self::Single(SingleIndexSetSummary { kit, .. })
+            | Self::Dual(DualIndexSetSummary { kit, .. }) => kit,
+        }
+    }
+
+    #[must_use]
+    pub fn well(&self) -> &str {
+        match self {
+            Self::Single(SingleIndexSetSummary { well, .. })
+            | Self::Dual(DualIndexSetSummary { well, .. }) => well,
+        }
+    }
+}
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum IndexSetOrdinalColumn {
+    #[default]
+    Name,
+    Kit,
+    Well,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct IndexSetOrdering {
+    pub column: IndexSetOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct IndexSetQuery {
+    pub kits: Vec<String>,
+    pub wells: Vec<String>,
+    /// Matches index sets containing an oligo of which this is a substring
+    pub sequence: Option<String>,
+    pub order_by: Vec<IndexSetOrdering>,
+    pub pagination: Pagination,
+}
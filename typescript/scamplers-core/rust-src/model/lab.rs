@@ -1,18 +1,21 @@
-use crate::model::Pagination;
+use crate::model::{Pagination, SimilaritySearch, filter::Filter, impl_search_params};
+
+#[cfg(feature = "backend")]
+use crate::model::{filter::is_valid_filter, is_offset_paginated};
 
 #[cfg(feature = "backend")]
 use {
     scamplers_macros::{
-        backend_insertion, backend_ordering, backend_ordinal_columns_enum, backend_query_request,
-        backend_update, backend_with_getters,
+        backend_filter_columns_enum, backend_insertion, backend_ordering,
+        backend_ordinal_columns_enum, backend_query_request, backend_update, backend_with_getters,
     },
     scamplers_schema::lab,
 };
 
 #[cfg(feature = "typescript")]
 use scamplers_macros::{
-    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request, frontend_update,
-    frontend_with_getters,
+    frontend_enum, frontend_filter_columns_enum, frontend_insertion, frontend_ordering,
+    frontend_query_request, frontend_update, frontend_with_getters,
 };
 
 use uuid::Uuid;
@@ -102,32 +105,73 @@ pub struct LabOrdering {
     pub descending: bool,
 }
 
+#[cfg_attr(feature = "backend", backend_filter_columns_enum(lab))]
+#[cfg_attr(feature = "typescript", frontend_filter_columns_enum)]
+pub enum LabFilterColumn {
+    Id,
+    Name,
+    PiId,
+    DeliveryDir,
+}
+
 #[cfg_attr(feature = "backend", backend_query_request)]
 #[cfg_attr(feature = "typescript", frontend_query_request)]
 pub struct LabQuery {
     pub ids: Vec<Uuid>,
     pub name: Option<String>,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub similar_name: Option<SimilaritySearch>,
+    #[cfg_attr(feature = "backend", garde(custom(is_valid_filter())), valuable(skip))]
+    pub filter: Filter<LabFilterColumn>,
     pub order_by: Vec<LabOrdering>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_offset_paginated(self.similar_name.as_ref())))
+    )]
     pub pagination: Pagination,
 }
 
-#[cfg_attr(feature = "backend", backend_update(lab))]
+impl_search_params!(
+    LabQuery,
+    LabSearchParams,
+    LabOrdering,
+    LabOrdinalColumn,
+    LabFilterColumn,
+    [(Name, "name")]
+);
+
+#[cfg_attr(
+    feature = "backend",
+    backend_update(lab),
+    derive(garde::Validate),
+    garde(allow_unvalidated)
+)]
 #[cfg_attr(feature = "typescript", frontend_update)]
 pub struct LabUpdate {
     pub id: Uuid,
+    #[cfg_attr(feature = "backend", garde(inner(length(min = 1))))]
     pub name: Option<String>,
     pub pi_id: Option<Uuid>,
+    #[cfg_attr(feature = "backend", garde(inner(length(min = 1))))]
     pub delivery_dir: Option<String>,
 }
 
 #[cfg_attr(
     feature = "backend",
-    derive(serde::Deserialize, Default),
-    serde(default)
+    derive(
+        serde::Deserialize,
+        Default,
+        valuable::Valuable,
+        garde::Validate,
+        Debug
+    ),
+    serde(default),
+    garde(allow_unvalidated)
 )]
 #[cfg_attr(feature = "typescript", frontend_update)]
 pub struct LabUpdateWithMembers {
     #[serde(flatten)]
+    #[cfg_attr(feature = "backend", garde(dive))]
     pub update: LabUpdate,
     pub add_members: Vec<Uuid>,
     pub remove_members: Vec<Uuid>,
@@ -0,0 +1,113 @@
+#[cfg(feature = "backend")]
+use scamplers_macros::{backend_db_enum, backend_with_getters};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{frontend_enum, frontend_with_getters};
+
+/// The kinds of record that make up a sample's lineage, in the order in which they're made
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum LineageNodeType {
+    #[default]
+    Sample,
+    Specimen,
+    Suspension,
+    MultiplexedSuspension,
+    Gems,
+    Cdna,
+    Library,
+    SequencingRun,
+    Dataset,
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use uuid::Uuid;
+
+    use crate::model::{lineage::LineageNodeType, sample_metadata::CommitteeApproval};
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    /// `label` is a sample's or dataset's name, and the readable id of anything else
+    #[cfg_attr(
+        feature = "backend",
+        derive(serde::Serialize, diesel::QueryableByName, Debug)
+    )]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct LineageNode {
+        #[serde(rename = "type")]
+        #[cfg_attr(feature = "backend", diesel(sql_type = diesel::sql_types::Text))]
+        type_: LineageNodeType,
+        #[cfg_attr(feature = "backend", diesel(sql_type = diesel::sql_types::Uuid))]
+        id: Uuid,
+        #[cfg_attr(feature = "backend", diesel(sql_type = diesel::sql_types::Text))]
+        label: String,
+    }
+
+    /// Points from a record to one that was made from it
+    #[cfg_attr(
+        feature = "backend",
+        derive(serde::Serialize, diesel::QueryableByName, Debug)
+    )]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct LineageEdge {
+        #[cfg_attr(feature = "backend", diesel(sql_type = diesel::sql_types::Uuid))]
+        parent_id: Uuid,
+        #[cfg_attr(feature = "backend", diesel(sql_type = diesel::sql_types::Uuid))]
+        child_id: Uuid,
+    }
+
+    /// Everything made from a sample, along with the sample's committee approvals
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SampleLineage {
+        nodes: Vec<LineageNode>,
+        edges: Vec<LineageEdge>,
+        committee_approvals: Vec<CommitteeApproval>,
+    }
+
+    /// Everything a dataset was made from, back to the samples it came from and their committee approvals
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct DatasetProvenance {
+        nodes: Vec<LineageNode>,
+        edges: Vec<LineageEdge>,
+        committee_approvals: Vec<CommitteeApproval>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl SampleLineage {
+        #[must_use]
+        pub fn new(
+            nodes: Vec<LineageNode>,
+            edges: Vec<LineageEdge>,
+            committee_approvals: Vec<CommitteeApproval>,
+        ) -> Self {
+            Self {
+                nodes,
+                edges,
+                committee_approvals,
+            }
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    impl DatasetProvenance {
+        #[must_use]
+        pub fn new(
+            nodes: Vec<LineageNode>,
+            edges: Vec<LineageEdge>,
+            committee_approvals: Vec<CommitteeApproval>,
+        ) -> Self {
+            Self {
+                nodes,
+                edges,
+                committee_approvals,
+            }
+        }
+    }
+}
+pub use read::*;
@@ -0,0 +1,213 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::model::units::Quantity;
+
+#[cfg(feature = "backend")]
+use {
+    crate::model::units::{Dimension, is_quantity_of},
+    scamplers_macros::{backend_db_json, backend_insertion, backend_with_getters},
+    scamplers_schema::{
+        cdna_measurement, chromium_library_measurement, multiplexed_suspension_measurement,
+        specimen_measurement, suspension_measurement,
+    },
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{frontend_insertion, frontend_json, frontend_with_getters};
+
+/// A single QC value. Quantities with a physical unit carry that unit with them, so that values submitted in different
+/// units can be compared after conversion
+#[cfg_attr(feature = "backend", backend_db_json)]
+#[cfg_attr(feature = "typescript", frontend_json)]
+#[serde(tag = "quantity", rename_all = "snake_case")]
+pub enum Measurement {
+    CellConcentration {
+        #[serde(flatten)]
+        #[cfg_attr(
+            feature = "backend",
+            garde(custom(is_quantity_of(Dimension::CellConcentration)))
+        )]
+        concentration: Quantity,
+    },
+    /// percentage of live cells
+    Viability {
+        #[cfg_attr(feature = "backend", garde(range(min = 0.0, max = 100.0)))]
+        value: f32,
+    },
+    MassConcentration {
+        #[serde(flatten)]
+        #[cfg_attr(
+            feature = "backend",
+            garde(custom(is_quantity_of(Dimension::MassConcentration)))
+        )]
+        concentration: Quantity,
+    },
+    Molarity {
+        #[serde(flatten)]
+        #[cfg_attr(
+            feature = "backend",
+            garde(custom(is_quantity_of(Dimension::MolarConcentration)))
+        )]
+        concentration: Quantity,
+    },
+    /// bp
+    MeanLibrarySize {
+        #[cfg_attr(feature = "backend", garde(range(min = 0.0)))]
+        value: f32,
+    },
+    Volume {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", garde(custom(is_quantity_of(Dimension::Volume))))]
+        volume: Quantity,
+    },
+    /// RNA integrity number
+    Rin {
+        #[cfg_attr(feature = "backend", garde(range(min = 1.0, max = 10.0)))]
+        value: f32,
+    },
+    /// percentage of RNA fragments longer than 200 nucleotides
+    Dv200 {
+        #[cfg_attr(feature = "backend", garde(range(min = 0.0, max = 100.0)))]
+        value: f32,
+    },
+}
+
+/// The contents of the `data` column of every `*_measurement` table
+#[cfg_attr(feature = "backend", backend_db_json)]
+#[cfg_attr(feature = "typescript", frontend_json)]
+pub struct MeasurementData {
+    pub measured_at: OffsetDateTime,
+    #[serde(default)]
+    pub instrument_name: Option<String>,
+    #[serde(flatten)]
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub measurement: Measurement,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(specimen_measurement))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewSpecimenMeasurement {
+    #[serde(default)]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub specimen_id: Option<Uuid>,
+    pub measured_by: Uuid,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub data: MeasurementData,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(suspension_measurement))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewSuspensionMeasurement {
+    #[serde(default)]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub suspension_id: Option<Uuid>,
+    pub measured_by: Uuid,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub data: MeasurementData,
+}
+
+#[cfg_attr(
+    feature = "backend",
+    backend_insertion(multiplexed_suspension_measurement)
+)]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewMultiplexedSuspensionMeasurement {
+    #[serde(default)]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub suspension_id: Option<Uuid>,
+    pub measured_by: Uuid,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub data: MeasurementData,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(cdna_measurement))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewCdnaMeasurement {
+    #[serde(default)]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub cdna_id: Option<Uuid>,
+    pub measured_by: Uuid,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub data: MeasurementData,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(chromium_library_measurement))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewLibraryMeasurement {
+    #[serde(default)]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub library_id: Option<Uuid>,
+    pub measured_by: Uuid,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub data: MeasurementData,
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use uuid::Uuid;
+
+    use crate::model::measurements::MeasurementData;
+
+    #[cfg(feature = "backend")]
+    use {
+        scamplers_macros::backend_selection,
+        scamplers_schema::{
+            cdna_measurement, chromium_library_measurement, multiplexed_suspension_measurement,
+            specimen_measurement, suspension_measurement,
+        },
+    };
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(specimen_measurement))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SpecimenMeasurement {
+        id: Uuid,
+        specimen_id: Uuid,
+        measured_by: Uuid,
+        data: MeasurementData,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(suspension_measurement))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SuspensionMeasurement {
+        id: Uuid,
+        suspension_id: Uuid,
+        measured_by: Uuid,
+        data: MeasurementData,
+    }
+
+    #[cfg_attr(
+        feature = "backend",
+        backend_selection(multiplexed_suspension_measurement)
+    )]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct MultiplexedSuspensionMeasurement {
+        id: Uuid,
+        suspension_id: Uuid,
+        measured_by: Uuid,
+        data: MeasurementData,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(cdna_measurement))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct CdnaMeasurement {
+        id: Uuid,
+        cdna_id: Uuid,
+        measured_by: Uuid,
+        data: MeasurementData,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(chromium_library_measurement))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct LibraryMeasurement {
+        id: Uuid,
+        library_id: Uuid,
+        measured_by: Uuid,
+        data: MeasurementData,
+    }
+}
+pub use read::*;
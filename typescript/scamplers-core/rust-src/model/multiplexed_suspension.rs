@@ -0,0 +1,226 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::model::{Pagination, measurements::NewMultiplexedSuspensionMeasurement};
+
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_db_enum, backend_insertion, backend_ordering, backend_ordinal_columns_enum,
+        backend_query_request, backend_with_getters,
+    },
+    scamplers_schema::multiplexed_suspension,
+    std::collections::HashSet,
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request,
+    frontend_with_getters,
+};
+
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum MultiplexingTagType {
+    TotalSeqA,
+    TotalSeqB,
+    TotalSeqC,
+    #[default]
+    CellPlex,
+    FlexBarcode,
+}
+
+/// A suspension to be pooled, along with the tag that identifies it within the pool
+#[cfg_attr(
+    feature = "backend",
+    derive(serde::Deserialize, valuable::Valuable, garde::Validate, Debug),
+    garde(allow_unvalidated)
+)]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct PooledSuspension {
+    pub suspension_id: Uuid,
+    pub multiplexing_tag_id: Uuid,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(multiplexed_suspension))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewMultiplexedSuspension {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub name: String,
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub readable_id: String,
+    pub pooled_at: OffsetDateTime,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub notes: Option<Vec<String>>,
+    #[cfg_attr(
+        feature = "backend",
+        diesel(skip_insertion),
+        garde(length(min = 1), custom(is_unambiguous_pool()))
+    )]
+    pub suspensions: Vec<PooledSuspension>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(length(min = 1)))]
+    pub preparer_ids: Vec<Uuid>,
+    #[cfg_attr(feature = "backend", diesel(skip_insertion), garde(dive))]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    #[serde(default)]
+    pub measurements: Vec<NewMultiplexedSuspensionMeasurement>,
+}
+
+// A suspension can only be in a pool once, and two suspensions in the same pool can't be told apart if they share a tag
+#[cfg(feature = "backend")]
+fn is_unambiguous_pool() -> impl FnOnce(&Vec<PooledSuspension>, &()) -> garde::Result {
+    |suspensions, ()| {
+        let mut suspension_ids = HashSet::with_capacity(suspensions.len());
+        let mut tag_ids = HashSet::with_capacity(suspensions.len());
+
+        for PooledSuspension {
+            suspension_id,
+            multiplexing_tag_id,
+        } in suspensions
+        {
+            if !suspension_ids.insert(suspension_id) {
+                return Err(garde::Error::new(format!(
+                    "suspension {suspension_id} appears more than once in this pool"
+                )));
+            }
+
+            if !tag_ids.insert(multiplexing_tag_id) {
+                return Err(garde::Error::new(format!(
+                    "multiplexing tag {multiplexing_tag_id} is assigned to more than one suspension in this pool"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use crate::model::{
+        measurements::MultiplexedSuspensionMeasurement,
+        multiplexed_suspension::MultiplexingTagType, person::PersonSummary,
+        suspension::SuspensionSummary,
+    };
+
+    #[cfg(feature = "backend")]
+    use {
+        scamplers_macros::backend_selection,
+        scamplers_schema::{multiplexed_suspension, multiplexing_tag},
+    };
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(multiplexing_tag))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct MultiplexingTag {
+        id: Uuid,
+        tag_id: String,
+        #[serde(rename = "type")]
+        type_: MultiplexingTagType,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(multiplexed_suspension))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct MultiplexedSuspensionReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(multiplexed_suspension))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct MultiplexedSuspensionSummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: MultiplexedSuspensionReference,
+        name: String,
+        readable_id: String,
+        pooled_at: OffsetDateTime,
+        notes: Option<Vec<String>>,
+    }
+
+    #[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct MultiplexedSuspension {
+        #[serde(flatten)]
+        summary: MultiplexedSuspensionSummary,
+        suspensions: Vec<SuspensionSummary>,
+        preparers: Vec<PersonSummary>,
+        measurements: Vec<MultiplexedSuspensionMeasurement>,
+    }
+
+    #[cfg(feature = "backend")]
+    impl MultiplexedSuspension {
+        #[must_use]
+        pub fn new(
+            summary: MultiplexedSuspensionSummary,
+            suspensions: Vec<SuspensionSummary>,
+            preparers: Vec<PersonSummary>,
+            measurements: Vec<MultiplexedSuspensionMeasurement>,
+        ) -> Self {
+            Self {
+                summary,
+                suspensions,
+                preparers,
+                measurements,
+            }
+        }
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum MultiplexingTagOrdinalColumn {
+    #[default]
+    TagId,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct MultiplexingTagOrdering {
+    pub column: MultiplexingTagOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct MultiplexingTagQuery {
+    pub tag_id: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<MultiplexingTagType>,
+    pub order_by: Vec<MultiplexingTagOrdering>,
+    pub pagination: Pagination,
+}
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum MultiplexedSuspensionOrdinalColumn {
+    #[default]
+    ReadableId,
+    Name,
+    PooledAt,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct MultiplexedSuspensionOrdering {
+    pub column: MultiplexedSuspensionOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct MultiplexedSuspensionQuery {
+    pub ids: Vec<Uuid>,
+    pub name: Option<String>,
+    pub readable_id: Option<String>,
+    pub order_by: Vec<MultiplexedSuspensionOrdering>,
+    pub pagination: Pagination,
+}
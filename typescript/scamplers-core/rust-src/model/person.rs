@@ -1,18 +1,21 @@
-use super::Pagination;
+use super::{Pagination, SimilaritySearch, filter::Filter, impl_search_params};
+
+#[cfg(feature = "backend")]
+use super::{filter::is_valid_filter, is_offset_paginated};
 
 #[cfg(feature = "backend")]
 use {
     scamplers_macros::{
-        backend_db_enum, backend_insertion, backend_ordering, backend_ordinal_columns_enum,
-        backend_query_request, backend_update, backend_with_getters,
+        backend_db_enum, backend_filter_columns_enum, backend_insertion, backend_ordering,
+        backend_ordinal_columns_enum, backend_query_request, backend_update, backend_with_getters,
     },
     scamplers_schema::person,
 };
 
 #[cfg(feature = "typescript")]
 use scamplers_macros::{
-    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request, frontend_update,
-    frontend_with_getters,
+    frontend_enum, frontend_filter_columns_enum, frontend_insertion, frontend_ordering,
+    frontend_query_request, frontend_update, frontend_with_getters,
 };
 
 use uuid::Uuid;
@@ -137,35 +140,79 @@ pub struct PersonOrdering {
     pub descending: bool,
 }
 
+#[cfg_attr(feature = "backend", backend_filter_columns_enum(person))]
+#[cfg_attr(feature = "typescript", frontend_filter_columns_enum)]
+pub enum PersonFilterColumn {
+    Id,
+    Name,
+    Email,
+    InstitutionId,
+    Orcid,
+}
+
 #[cfg_attr(feature = "backend", backend_query_request)]
 #[cfg_attr(feature = "typescript", frontend_query_request)]
 pub struct PersonQuery {
     pub ids: Vec<Uuid>,
     pub name: Option<String>,
+    #[cfg_attr(feature = "backend", garde(dive))]
+    pub similar_name: Option<SimilaritySearch>,
     pub email: Option<String>,
+    #[cfg_attr(feature = "backend", garde(custom(is_valid_filter())), valuable(skip))]
+    pub filter: Filter<PersonFilterColumn>,
     pub order_by: Vec<PersonOrdering>,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_offset_paginated(self.similar_name.as_ref())))
+    )]
     pub pagination: Pagination,
 }
 
-#[cfg_attr(feature = "backend", backend_update(person))]
+impl_search_params!(
+    PersonQuery,
+    PersonSearchParams,
+    PersonOrdering,
+    PersonOrdinalColumn,
+    PersonFilterColumn,
+    [(Name, "name"), (Email, "email")],
+    [email: Option<String>]
+);
+
+#[cfg_attr(
+    feature = "backend",
+    backend_update(person),
+    derive(garde::Validate),
+    garde(allow_unvalidated)
+)]
 #[cfg_attr(feature = "typescript", frontend_update)]
 pub struct PersonDataUpdate {
     pub id: Uuid,
+    #[cfg_attr(feature = "backend", garde(inner(length(min = 1))))]
     pub name: Option<String>,
+    #[cfg_attr(feature = "backend", garde(inner(email)))]
     pub email: Option<String>,
     pub ms_user_id: Option<Uuid>,
     pub orcid: Option<String>,
     pub institution_id: Option<Uuid>,
 }
 
+/// Only app admins can add or remove roles
 #[cfg_attr(
     feature = "backend",
-    derive(serde::Deserialize, Default),
-    serde(default)
+    derive(
+        serde::Deserialize,
+        Default,
+        valuable::Valuable,
+        garde::Validate,
+        Debug
+    ),
+    serde(default),
+    garde(allow_unvalidated)
 )]
 #[cfg_attr(feature = "typescript", frontend_update)]
 pub struct PersonUpdate {
     #[serde(flatten)]
+    #[cfg_attr(feature = "backend", garde(dive))]
     pub data_update: PersonDataUpdate,
     pub add_roles: Vec<UserRole>,
     pub remove_roles: Vec<UserRole>,
@@ -0,0 +1,72 @@
+use crate::model::{
+    institution::InstitutionSummary, lab::LabSummary, person::PersonSummary,
+    sample_metadata::SampleSummary,
+};
+
+#[cfg(feature = "typescript")]
+use wasm_bindgen::prelude::*;
+
+/// Searches the names of people, labs, institutions, and samples at once, for the frontend's global search box
+#[cfg_attr(
+    feature = "typescript",
+    derive(Clone, serde::Serialize),
+    wasm_bindgen(getter_with_clone, setter)
+)]
+#[cfg_attr(
+    feature = "backend",
+    derive(serde::Deserialize, valuable::Valuable, garde::Validate, Debug),
+    serde(default, deny_unknown_fields)
+)]
+pub struct GlobalSearchQuery {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub q: String,
+    /// How similar a name must be to `q` to match, from 0 to 1 (identical)
+    #[cfg_attr(feature = "backend", garde(range(min = 0.0, max = 1.0)))]
+    pub threshold: f32,
+    /// The maximum number of hits across all kinds of record
+    #[cfg_attr(feature = "backend", garde(range(min = 1, max = 100)))]
+    pub limit: i64,
+}
+
+impl Default for GlobalSearchQuery {
+    fn default() -> Self {
+        Self {
+            q: String::new(),
+            threshold: 0.3,
+            limit: 20,
+        }
+    }
+}
+
+#[cfg(feature = "typescript")]
+#[wasm_bindgen]
+impl GlobalSearchQuery {
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new(q: String) -> Self {
+        Self {
+            q,
+            ..Default::default()
+        }
+    }
+}
+
+/// The record a search hit refers to, tagged with its `type`
+#[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+#[cfg_attr(feature = "typescript", derive(serde::Deserialize))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SearchRecord {
+    Person(PersonSummary),
+    Lab(LabSummary),
+    Institution(InstitutionSummary),
+    Sample(SampleSummary),
+}
+
+/// Hits are ordered from most to least similar
+#[cfg_attr(feature = "backend", derive(serde::Serialize, Debug))]
+#[cfg_attr(feature = "typescript", derive(serde::Deserialize))]
+pub struct SearchHit {
+    pub similarity: f32,
+    #[serde(flatten)]
+    pub record: SearchRecord,
+}
@@ -0,0 +1,177 @@
+use uuid::Uuid;
+
+use crate::model::Pagination;
+
+#[cfg(feature = "backend")]
+use {
+    scamplers_macros::{
+        backend_db_enum, backend_insertion, backend_ordering, backend_ordinal_columns_enum,
+        backend_query_request, backend_with_getters,
+    },
+    scamplers_schema::specimen,
+};
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::{
+    frontend_enum, frontend_insertion, frontend_ordering, frontend_query_request,
+    frontend_with_getters,
+};
+
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum SpecimenType {
+    Block,
+    Fluid,
+    #[default]
+    Tissue,
+}
+
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum EmbeddingMatrix {
+    CarboxymethylCellulose,
+    OptimalCuttingTemperatureCompound,
+    #[default]
+    Paraffin,
+}
+
+#[derive(PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum PreservationMethod {
+    Cryopreservation,
+    DspFixation,
+    #[default]
+    FormaldehydeDerivativeFixation,
+    Freezing,
+}
+
+#[cfg_attr(feature = "backend", backend_insertion(specimen))]
+#[cfg_attr(feature = "typescript", frontend_insertion)]
+pub struct NewSpecimen {
+    #[cfg_attr(feature = "backend", garde(length(min = 1)))]
+    pub readable_id: String,
+    pub metadata_id: Uuid,
+    #[serde(rename = "type")]
+    pub type_: SpecimenType,
+    #[cfg_attr(
+        feature = "backend",
+        garde(custom(is_valid_embedding(self.type_, self.preserved_with)))
+    )]
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub embedded_in: Option<EmbeddingMatrix>,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub preserved_with: Option<PreservationMethod>,
+    #[cfg_attr(feature = "typescript", builder(default))]
+    pub notes: Option<Vec<String>>,
+}
+
+/// Checks that a specimen's type, embedding matrix, and preservation method make physical sense together
+#[cfg(feature = "backend")]
+fn is_valid_embedding(
+    type_: SpecimenType,
+    preserved_with: Option<PreservationMethod>,
+) -> impl FnOnce(&Option<EmbeddingMatrix>, &()) -> garde::Result {
+    use EmbeddingMatrix::{CarboxymethylCellulose, OptimalCuttingTemperatureCompound, Paraffin};
+    use PreservationMethod::{FormaldehydeDerivativeFixation, Freezing};
+    use SpecimenType::{Block, Fluid, Tissue};
+
+    move |embedded_in, ()| {
+        let err = |message: &str| Err(garde::Error::new(message));
+
+        match (type_, embedded_in, preserved_with) {
+            (Block, None, _) => err("a block must be embedded in a matrix"),
+            (Tissue | Fluid, Some(_), _) => err("only a block can be embedded in a matrix"),
+            (Block, Some(Paraffin), p) if p != Some(FormaldehydeDerivativeFixation) => {
+                err("a paraffin-embedded block must be fixed with a formaldehyde derivative")
+            }
+            (Block, Some(OptimalCuttingTemperatureCompound | CarboxymethylCellulose), Some(p))
+                if !matches!(p, Freezing | FormaldehydeDerivativeFixation) =>
+            {
+                err(
+                    "a block embedded in OCT or CMC must be frozen or fixed with a formaldehyde derivative",
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg_attr(feature = "backend", backend_with_getters)]
+#[cfg_attr(feature = "typescript", frontend_with_getters)]
+mod read {
+    use uuid::Uuid;
+
+    use crate::model::{
+        sample_metadata::SampleSummary,
+        specimen::{EmbeddingMatrix, PreservationMethod, SpecimenType},
+    };
+
+    #[cfg(feature = "backend")]
+    use {scamplers_macros::backend_selection, scamplers_schema::specimen};
+
+    #[cfg(feature = "typescript")]
+    use scamplers_macros::frontend_response;
+
+    #[cfg_attr(feature = "backend", backend_selection(specimen))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SpecimenReference {
+        id: Uuid,
+        link: String,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(specimen))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct SpecimenSummary {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        reference: SpecimenReference,
+        readable_id: String,
+        #[serde(rename = "type")]
+        type_: SpecimenType,
+        embedded_in: Option<EmbeddingMatrix>,
+        preserved_with: Option<PreservationMethod>,
+        notes: Option<Vec<String>>,
+    }
+
+    #[cfg_attr(feature = "backend", backend_selection(specimen))]
+    #[cfg_attr(feature = "typescript", frontend_response)]
+    pub struct Specimen {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        summary: SpecimenSummary,
+        #[cfg_attr(feature = "backend", diesel(embed))]
+        metadata: SampleSummary,
+    }
+}
+pub use read::*;
+
+#[cfg_attr(feature = "backend", backend_ordinal_columns_enum)]
+#[cfg_attr(feature = "typescript", frontend_enum)]
+pub enum SpecimenOrdinalColumn {
+    #[default]
+    ReadableId,
+}
+
+#[cfg_attr(feature = "backend", backend_ordering)]
+#[cfg_attr(feature = "typescript", frontend_ordering)]
+pub struct SpecimenOrdering {
+    pub column: SpecimenOrdinalColumn,
+    pub descending: bool,
+}
+
+#[cfg_attr(feature = "backend", backend_query_request)]
+#[cfg_attr(feature = "typescript", frontend_query_request)]
+pub struct SpecimenQuery {
+    pub ids: Vec<Uuid>,
+    pub readable_id: Option<String>,
+    pub metadata_ids: Vec<Uuid>,
+    #[serde(rename = "type")]
+    pub type_: Option<SpecimenType>,
+    pub embedded_in: Option<EmbeddingMatrix>,
+    pub preserved_with: Option<PreservationMethod>,
+    pub order_by: Vec<SpecimenOrdering>,
+    pub pagination: Pagination,
+}
@@ -0,0 +1,249 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "backend")]
+use scamplers_macros::backend_db_json;
+
+#[cfg(feature = "typescript")]
+use scamplers_macros::frontend_json;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "backend", derive(valuable::Valuable))]
+pub enum Dimension {
+    Volume,
+    Mass,
+    MassConcentration,
+    MolarConcentration,
+    CellConcentration,
+}
+
+/// Units are (de)serialized as their usual abbreviations. Because "µ" is awkward to type, "u" is accepted in its place
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "backend", derive(valuable::Valuable))]
+pub enum Unit {
+    #[serde(rename = "nl")]
+    Nanoliter,
+    #[serde(rename = "µl", alias = "ul")]
+    Microliter,
+    #[serde(rename = "ml")]
+    Milliliter,
+    #[serde(rename = "pg")]
+    Picogram,
+    #[serde(rename = "ng")]
+    Nanogram,
+    #[serde(rename = "µg", alias = "ug")]
+    Microgram,
+    #[serde(rename = "pg/µl", alias = "pg/ul")]
+    PicogramPerMicroliter,
+    #[serde(rename = "ng/µl", alias = "ng/ul")]
+    NanogramPerMicroliter,
+    #[serde(rename = "ng/ml")]
+    NanogramPerMilliliter,
+    #[serde(rename = "pM")]
+    Picomolar,
+    #[serde(rename = "nM")]
+    Nanomolar,
+    #[serde(rename = "cells/µl", alias = "cells/ul")]
+    CellsPerMicroliter,
+    #[serde(rename = "cells/ml")]
+    CellsPerMilliliter,
+}
+
+impl Unit {
+    #[must_use]
+    pub fn dimension(self) -> Dimension {
+        use Unit::{
+            CellsPerMicroliter, CellsPerMilliliter, Microgram, Microliter, Milliliter, Nanogram,
+            NanogramPerMicroliter, NanogramPerMilliliter, Nanoliter, Nanomolar, Picogram,
+            PicogramPerMicroliter, Picomolar,
+        };
+
+        match self {
+            Nanoliter | Microliter | Milliliter => Dimension::Volume,
+            Picogram | Nanogram | Microgram => Dimension::Mass,
+            PicogramPerMicroliter | NanogramPerMicroliter | NanogramPerMilliliter => {
+                Dimension::MassConcentration
+            }
+            Picomolar | Nanomolar => Dimension::MolarConcentration,
+            CellsPerMicroliter | CellsPerMilliliter => Dimension::CellConcentration,
+        }
+    }
+
+    /// The multiplier that converts a value in this unit into the canonical unit of its dimension (µl, ng, ng/µl, nM,
+    /// and cells/µl respectively)
+    fn factor(self) -> f32 {
+        use Unit::{
+            CellsPerMicroliter, CellsPerMilliliter, Microgram, Microliter, Milliliter, Nanogram,
+            NanogramPerMicroliter, NanogramPerMilliliter, Nanoliter, Nanomolar, Picogram,
+            PicogramPerMicroliter, Picomolar,
+        };
+
+        match self {
+            Microliter | Nanogram | NanogramPerMicroliter | Nanomolar | CellsPerMicroliter => 1.0,
+            Nanoliter
+            | Picogram
+            | PicogramPerMicroliter
+            | NanogramPerMilliliter
+            | Picomolar
+            | CellsPerMilliliter => 1e-3,
+            Milliliter | Microgram => 1e3,
+        }
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Unit::{
+            CellsPerMicroliter, CellsPerMilliliter, Microgram, Microliter, Milliliter, Nanogram,
+            NanogramPerMicroliter, NanogramPerMilliliter, Nanoliter, Nanomolar, Picogram,
+            PicogramPerMicroliter, Picomolar,
+        };
+
+        let abbreviation = match self {
+            Nanoliter => "nl",
+            Microliter => "µl",
+            Milliliter => "ml",
+            Picogram => "pg",
+            Nanogram => "ng",
+            Microgram => "µg",
+            PicogramPerMicroliter => "pg/µl",
+            NanogramPerMicroliter => "ng/µl",
+            NanogramPerMilliliter => "ng/ml",
+            Picomolar => "pM",
+            Nanomolar => "nM",
+            CellsPerMicroliter => "cells/µl",
+            CellsPerMilliliter => "cells/ml",
+        };
+
+        abbreviation.fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IncompatibleUnits {
+    pub from: Unit,
+    pub to: Unit,
+}
+
+impl Display for IncompatibleUnits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { from, to } = self;
+        write!(f, "cannot convert {from} to {to}")
+    }
+}
+
+impl std::error::Error for IncompatibleUnits {}
+
+#[derive(Copy, PartialEq)]
+#[cfg_attr(feature = "backend", backend_db_json)]
+#[cfg_attr(feature = "typescript", frontend_json)]
+pub struct Quantity {
+    pub value: f32,
+    pub unit: Unit,
+}
+
+impl Quantity {
+    #[must_use]
+    pub fn new(value: f32, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    /// # Errors
+    /// Returns an error if `unit` measures a different dimension than this quantity's unit
+    pub fn convert_to(self, unit: Unit) -> Result<Self, IncompatibleUnits> {
+        Ok(Self::new(self.value_in(unit)?, unit))
+    }
+
+    /// # Errors
+    /// Returns an error if `unit` measures a different dimension than this quantity's unit
+    pub fn value_in(self, unit: Unit) -> Result<f32, IncompatibleUnits> {
+        let Self { value, unit: from } = self;
+
+        if from.dimension() != unit.dimension() {
+            return Err(IncompatibleUnits { from, to: unit });
+        }
+
+        Ok(value * from.factor() / unit.factor())
+    }
+}
+
+/// Validates that a quantity measures `dimension` and is non-negative
+#[cfg(feature = "backend")]
+pub(crate) fn is_quantity_of(dimension: Dimension) -> impl FnOnce(&Quantity, &()) -> garde::Result {
+    move |Quantity { value, unit }, ()| {
+        if unit.dimension() != dimension {
+            return Err(garde::Error::new(format!(
+                "expected a unit of {dimension:?}, found {unit}"
+            )));
+        }
+
+        if *value < 0.0 {
+            return Err(garde::Error::new("quantity cannot be negative"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{IncompatibleUnits, Quantity, Unit};
+
+    #[test]
+    fn convert_within_dimension() {
+        let volume = Quantity::new(1.5, Unit::Milliliter);
+
+        assert_eq!(
+            volume.convert_to(Unit::Microliter).unwrap(),
+            Quantity::new(1500.0, Unit::Microliter)
+        );
+
+        let concentration = Quantity::new(2000.0, Unit::CellsPerMilliliter);
+        assert_eq!(
+            concentration.convert_to(Unit::CellsPerMicroliter).unwrap(),
+            Quantity::new(2.0, Unit::CellsPerMicroliter)
+        );
+    }
+
+    #[test]
+    fn convert_across_dimensions() {
+        let volume = Quantity::new(10.0, Unit::Microliter);
+
+        assert_eq!(
+            volume.convert_to(Unit::Nanomolar).unwrap_err(),
+            IncompatibleUnits {
+                from: Unit::Microliter,
+                to: Unit::Nanomolar
+            }
+        );
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let quantity = Quantity::new(3.5, Unit::NanogramPerMicroliter);
+
+        let json = serde_json::to_value(quantity).unwrap();
+        assert_eq!(json, serde_json::json!({"value": 3.5, "unit": "ng/µl"}));
+
+        let deserialized: Quantity = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, quantity);
+
+        let ascii: Quantity = serde_json::from_str(r#"{"value": 3.5, "unit": "ng/ul"}"#).unwrap();
+        assert_eq!(ascii, quantity);
+    }
+
+    #[cfg(feature = "backend")]
+    #[test]
+    fn reject_incompatible_unit() {
+        use super::{Dimension, is_quantity_of};
+
+        let is_volume = || is_quantity_of(Dimension::Volume);
+
+        is_volume()(&Quantity::new(10.0, Unit::Microliter), &()).unwrap();
+        is_volume()(&Quantity::new(10.0, Unit::Nanomolar), &()).unwrap_err();
+        is_volume()(&Quantity::new(-1.0, Unit::Microliter), &()).unwrap_err();
+    }
+}